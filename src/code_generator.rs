@@ -1,184 +1,3220 @@
-use proc_macro2::{TokenStream, Span};
+use proc_macro2::TokenStream;
 use quote::{quote, format_ident};
-use syn::Ident;
-use crate::parser::ParsedStruct;
+use syn::ext::IdentExt;
+use syn::{parse_quote, Generics, Ident};
+use crate::parser::{Backend, ColumnCodec, ParsedField, ParsedStruct};
+use crate::utils::{count_placeholders, is_datetime_type, is_option, is_string_type, option_inner, type_contains_ident};
 use crate::sql_generator::{
-    generate_create_table_sql,
-    generate_insert_sql,
+    Statement, column_type,
+    generate_create_schema_sql, generate_create_table_sql,
+    generate_comment_sqls,
+    generate_insert_sql, generate_insert_present_sql,
     generate_update_sql,
-    generate_delete_sql,
+    generate_update_returning_sql,
+    generate_delete_by_column_sql, generate_delete_sql, generate_has_many_in_sql, generate_has_many_where_sql,
     generate_select_sql,
-    generate_select_by_id_sql
+    generate_select_by_id_sql,
+    generate_select_by_column_sql,
+    generate_search_by_column_sql,
+    generate_select_first_by_column_sql,
+    generate_select_by_json_path_sql,
+    generate_select_by_column_in_sql,
+    generate_select_page_sql,
+    generate_projection_select_sql,
+    generate_count_sql,
+    generate_count_by_sql,
+    generate_count_where_sql,
+    generate_filter_sql, generate_filter_condition_sql,
+    generate_exists_where_sql,
+    generate_add_column_sql,
+    generate_insert_ignore_sql,
+    generate_upsert_many_sql,
+    generate_select_by_key_sql,
+    generate_increment_sql,
+    generate_select_where_sql
 };
 
+/// 生成后端对应的数据库类型
+fn database_type(parsed: &ParsedStruct) -> TokenStream {
+    match parsed.options.backend {
+        Backend::Sqlite => quote!(sqlx::Sqlite),
+        Backend::Postgres => quote!(sqlx::Postgres),
+        Backend::MySql => quote!(sqlx::MySql),
+        Backend::Any => quote!(sqlx::Any),
+    }
+}
+
+/// 单条语句最多可绑定的参数个数，SQLite 默认为 32766，Postgres 和 MySQL 为 65535，批量语句按此分批
+fn max_params(parsed: &ParsedStruct) -> usize {
+    match parsed.options.backend {
+        Backend::Sqlite => 32766,
+        Backend::Postgres | Backend::MySql | Backend::Any => 65535,
+    }
+}
+
+/// 在运行时拼接占位符的闭包，参数为从0开始的绑定序号，规则与 `Backend::placeholder` 相同
+fn placeholder_fn(parsed: &ParsedStruct) -> TokenStream {
+    match parsed.options.backend {
+        Backend::Sqlite | Backend::Postgres => quote! { |i: usize| format!("${}", i + 1) },
+        Backend::MySql | Backend::Any => quote! { |_: usize| "?".to_string() },
+    }
+}
+
+/// 生成后端对应的连接池类型
+fn pool_type(parsed: &ParsedStruct) -> TokenStream {
+    let db = database_type(parsed);
+    quote!(sqlx::Pool<#db>)
+}
+
+/// 生成后端对应的行类型
+fn row_type(parsed: &ParsedStruct) -> TokenStream {
+    match parsed.options.backend {
+        Backend::Sqlite => quote!(sqlx::sqlite::SqliteRow),
+        Backend::Postgres => quote!(sqlx::postgres::PgRow),
+        Backend::MySql => quote!(sqlx::mysql::MySqlRow),
+        Backend::Any => quote!(sqlx::any::AnyRow),
+    }
+}
+
+/// 生成方法返回的错误类型
+fn error_type(parsed: &ParsedStruct) -> TokenStream {
+    match &parsed.options.error {
+        Some(error) => quote!(#error),
+        None => quote!(sqlx::Error),
+    }
+}
+
+/// 结构体上生成的方法名，带有 #[sql_crud(prefix = "...")] 指定的前缀
+fn method(parsed: &ParsedStruct, name: &str) -> Ident {
+    format_ident!("{}{}", parsed.options.prefix, name)
+}
+
+/// 写方法是否只能接受连接池：钩子需要在写入后继续使用连接池，重试需要多次执行同一查询
+fn writes_need_pool(parsed: &ParsedStruct) -> bool {
+    parsed.options.hooks || parsed.options.busy_retry.is_some()
+}
+
+/// 写方法的执行器参数：泛型参数、参数类型和约束
+///
+/// 通常接受任意 `sqlx::Executor`（连接池、连接或事务）；启用钩子或重试时为连接池
+fn executor_param(parsed: &ParsedStruct) -> (TokenStream, TokenStream, TokenStream) {
+    let pool_ty = pool_type(parsed);
+    if writes_need_pool(parsed) {
+        return (TokenStream::new(), quote!(&#pool_ty), TokenStream::new());
+    }
+    let db = database_type(parsed);
+    (quote!(<'e, Exec>), quote!(Exec), quote!(where Exec: sqlx::Executor<'e, Database = #db>))
+}
+
+/// 插入是否需要修改记录：启用钩子，或有 #[uuid_generate] 字段
+fn insert_needs_mut(parsed: &ParsedStruct) -> bool {
+    parsed.options.hooks || parsed.fields.iter().any(|f| f.is_uuid_generate)
+}
+
+/// 插入的接收者，需要修改记录时为 `&mut self`
+fn insert_receiver(parsed: &ParsedStruct) -> TokenStream {
+    if insert_needs_mut(parsed) {
+        quote!(&mut self)
+    } else {
+        quote!(&self)
+    }
+}
+
+/// 为值为空（全零）的 #[uuid_generate] 字段生成 v4 UUID
+fn uuid_generation(parsed: &ParsedStruct) -> TokenStream {
+    let assignments = parsed.fields.iter()
+        .filter(|f| f.is_uuid_generate)
+        .map(|f| {
+            let ident = &f.ident;
+            let ty = &f.ty;
+            quote! {
+                if self.#ident.is_nil() {
+                    self.#ident = <#ty>::new_v4();
+                }
+            }
+        });
+    quote!(#(#assignments)*)
+}
+
+/// 钩子 trait 的名称
+fn hooks_trait(parsed: &ParsedStruct) -> Ident {
+    format_ident!("{}Hooks", parsed.name)
+}
+
+/// 写操作的接收者，启用钩子时为 `&mut self` 以便 `before_*` 修改记录
+fn write_receiver(parsed: &ParsedStruct) -> TokenStream {
+    if parsed.options.hooks {
+        quote!(&mut self)
+    } else {
+        quote!(&self)
+    }
+}
+
+/// 生成钩子调用，未启用 #[sql_crud(hooks)] 时为空
+fn hook_call(parsed: &ParsedStruct, hook: &str) -> TokenStream {
+    if !parsed.options.hooks {
+        return TokenStream::new();
+    }
+    let hooks_trait = hooks_trait(parsed);
+    let hook = format_ident!("{}", hook);
+    if hook.to_string().starts_with("before_") {
+        quote!(<Self as #hooks_trait>::#hook(self)?;)
+    } else {
+        quote!(<Self as #hooks_trait>::#hook(self, executor).await?;)
+    }
+}
+
+/// 是否有字段声明了 #[validate] 或 #[max_length]
+fn has_validators(parsed: &ParsedStruct) -> bool {
+    parsed.fields.iter().any(|f| !f.validators.is_empty() || f.max_length.is_some())
+}
+
+/// 生成写操作前的校验，有校验失败时返回 `Validation` 错误，不执行SQL
+fn validation_check(parsed: &ParsedStruct, operation: &str) -> TokenStream {
+    let validate_fn = method(parsed, "validate");
+    let error = match &parsed.options.error {
+        Some(error) if has_validators(parsed) => error,
+        _ => return TokenStream::new(),
+    };
+    let table = &parsed.table_name;
+    quote! {
+        let errors = self.#validate_fn();
+        if !errors.is_empty() {
+            return Err(#error::Validation { operation: #operation, table: #table, errors });
+        }
+    }
+}
+
+/// 泛型结构体查询方法上 `Self` 需要满足的 `FromRow` 约束，非泛型结构体无需额外约束
+fn row_bound(parsed: &ParsedStruct) -> TokenStream {
+    if parsed.generics.params.is_empty() {
+        return TokenStream::new();
+    }
+    let row_ty = row_type(parsed);
+    quote!(where Self: for<'r> sqlx::FromRow<'r, #row_ty> + Send + Unpin)
+}
+
+/// 为用作列类型的泛型参数添加 sqlx 编解码约束后的泛型
+fn bounded_generics(parsed: &ParsedStruct) -> Generics {
+    let db = database_type(parsed);
+    let mut generics = parsed.generics.clone();
+    let column_params: Vec<Ident> = generics.type_params()
+        .filter(|param| parsed.fields.iter().any(|f| type_contains_ident(&f.ty, &param.ident)))
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for param in column_params {
+        where_clause.predicates.push(parse_quote! {
+            #param: for<'q> sqlx::Encode<'q, #db> + for<'de> sqlx::Decode<'de, #db> + sqlx::Type<#db> + Send + Sync
+        });
+    }
+    generics
+}
+
+/// 记录到 span 上的行数
+enum RowCount {
+    /// 写操作影响的行数
+    Affected,
+    /// `fetch_all` 返回的记录数
+    All,
+    /// `fetch_optional` 是否返回了记录
+    Optional,
+    /// `fetch_one` 总是返回一行
+    One,
+}
+
+/// 启用 #[sql_crud(error)] 时把 `sqlx::Error` 包装为带有操作名、表名和当前 `sql` 的错误，否则为空
+fn map_error(parsed: &ParsedStruct, operation: &str) -> TokenStream {
+    let table = &parsed.table_name;
+    match &parsed.options.error {
+        Some(error) => quote! {
+            .map_err(|source| #error::Query {
+                operation: #operation,
+                table: #table,
+                sql: sql.to_string(),
+                source,
+            })
+        },
+        None => TokenStream::new(),
+    }
+}
+
+/// 开始和提交事务前为 `map_error` 记录的 `sql`，只在启用 #[sql_crud(error)] 时需要
+fn transaction_sql(parsed: &ParsedStruct) -> (TokenStream, TokenStream) {
+    if parsed.options.error.is_some() {
+        (quote!(let sql = "BEGIN";), quote!(let sql = "COMMIT";))
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    }
+}
+
+/// 生成执行查询的表达式，`query` 是尚未 `.await` 的查询 future，表达式的值是 `?` 之后的结果
+///
+/// 启用 #[sql_crud(error)] 时查询错误包装为带有操作名、表名和SQL的错误类型
+///
+/// 启用 #[sql_crud(tracing)] 时查询在名为 `sql.<operation>` 的 debug span 中执行，执行前以 debug 级别记录SQL和参数个数，
+/// span 记录表名、SQL、行数和耗时，查询失败时以 warn 级别记录错误；`params` 是求值为绑定参数个数的表达式
+fn run_query(parsed: &ParsedStruct, operation: &str, query: TokenStream, params: TokenStream, rows: RowCount) -> TokenStream {
+    let map_err = map_error(parsed, operation);
+    let is_write = matches!(rows, RowCount::Affected);
+    let query = match &parsed.options.busy_retry {
+        Some(retry) if is_write || retry.reads => {
+            let attempts = retry.attempts;
+            let backoff_ms = retry.backoff_ms;
+            let max_backoff_ms = retry.max_backoff_ms;
+            // SQLite 扩展错误码的低 8 位为主错误码：5 为 SQLITE_BUSY，6 为 SQLITE_LOCKED
+            quote! {
+                async {
+                    let mut attempt = 1u32;
+                    let mut backoff = std::time::Duration::from_millis(#backoff_ms);
+                    loop {
+                        match #query.await {
+                            Err(sqlx::Error::Database(err))
+                                if attempt < #attempts
+                                    && err.code()
+                                        .and_then(|code| code.parse::<i32>().ok())
+                                        .is_some_and(|code| matches!(code & 0xff, 5 | 6)) =>
+                            {
+                                tokio::time::sleep(backoff).await;
+                                backoff = std::cmp::min(backoff * 2, std::time::Duration::from_millis(#max_backoff_ms));
+                                attempt += 1;
+                            }
+                            result => break result,
+                        }
+                    }
+                }
+            }
+        }
+        _ => query,
+    };
+    if !parsed.options.tracing {
+        return quote!(#query.await #map_err ?);
+    }
+    let span_name = format!("sql.{}", operation);
+    let table = &parsed.table_name;
+    let on_ok = match rows {
+        RowCount::Affected => quote!(Ok(value) => { span.record("rows_affected", value.rows_affected()); }),
+        RowCount::All => quote!(Ok(value) => { span.record("row_count", value.len() as u64); }),
+        RowCount::Optional => quote!(Ok(value) => { span.record("row_count", u64::from(value.is_some())); }),
+        RowCount::One => quote!(Ok(_) => { span.record("row_count", 1u64); }),
+    };
+    quote! {{
+        let span = tracing::debug_span!(
+            #span_name,
+            table = #table,
+            sql = %sql,
+            rows_affected = tracing::field::Empty,
+            row_count = tracing::field::Empty,
+            elapsed_ms = tracing::field::Empty,
+        );
+        tracing::debug!(parent: &span, sql = %sql, params = #params, "executing query");
+        let start = std::time::Instant::now();
+        let result = tracing::Instrument::instrument(#query, span.clone()).await;
+        span.record("elapsed_ms", start.elapsed().as_millis() as u64);
+        match &result {
+            #on_ok
+            Err(err) => { tracing::warn!(parent: &span, error = %err, "query failed"); }
+        }
+        result #map_err ?
+    }}
+}
+
+/// `bind` 闭包绑定的参数个数：对 `query` 再调用一次 `bind` 并取出参数，只在启用 tracing 时求值
+fn bind_count(query: TokenStream) -> TokenStream {
+    quote! {
+        sqlx::Execute::take_arguments(&mut bind(#query))
+            .ok()
+            .flatten()
+            .map_or(0, |arguments| sqlx::Arguments::len(&arguments))
+    }
+}
+
+/// 运行时决定表名的SQL模板中表名位置的标记
+const TABLE_MARKER: char = '\u{1}';
+
+/// 在运行时决定表名的方法变体
+#[derive(Clone, Copy)]
+enum RuntimeTable {
+    /// #[sql_crud(runtime_prefix)]：`<method>_with_prefix`，参数 `prefix` 加在表名前
+    Prefix,
+    /// #[sql_crud(dynamic_table)]：`<method>_in_table`，参数 `table` 替换整个表名
+    Table,
+}
+
+impl RuntimeTable {
+    /// 结构体上启用的变体
+    fn enabled(parsed: &ParsedStruct) -> Vec<RuntimeTable> {
+        let mut variants = Vec::new();
+        if parsed.options.runtime_prefix {
+            variants.push(RuntimeTable::Prefix);
+        }
+        if parsed.options.dynamic_table {
+            variants.push(RuntimeTable::Table);
+        }
+        variants
+    }
+
+    /// 变体的方法名，`init_table` 的动态表变体为 `init_table_in`
+    fn method(self, parsed: &ParsedStruct, name: &str) -> Ident {
+        match (self, name) {
+            (RuntimeTable::Prefix, name) => method(parsed, &format!("{}_with_prefix", name)),
+            (RuntimeTable::Table, "init_table") => method(parsed, "init_table_in"),
+            (RuntimeTable::Table, name) => method(parsed, &format!("{}_in_table", name)),
+        }
+    }
+
+    /// 额外的 `&str` 参数名
+    fn param(self) -> Ident {
+        match self {
+            RuntimeTable::Prefix => format_ident!("prefix"),
+            RuntimeTable::Table => format_ident!("table"),
+        }
+    }
+
+    /// 方法文档中对目标表的描述
+    fn target(self) -> &'static str {
+        match self {
+            RuntimeTable::Prefix => "加上前缀的表",
+            RuntimeTable::Table => "指定的表",
+        }
+    }
+
+    /// 表名位置带有标记的解析结果，用它生成的SQL即运行时替换标记的模板
+    fn template(self, parsed: &ParsedStruct) -> ParsedStruct {
+        let mut template = parsed.clone();
+        match self {
+            RuntimeTable::Prefix => {
+                template.read_table.insert(0, TABLE_MARKER);
+                template.write_table.insert(0, TABLE_MARKER);
+            }
+            RuntimeTable::Table => {
+                template.read_table = TABLE_MARKER.to_string();
+                template.write_table = TABLE_MARKER.to_string();
+            }
+        }
+        template
+    }
+
+    /// 生成绑定 `sql` 的语句：校验参数后把模板中的标记替换为它
+    fn sql(self, parsed: &ParsedStruct, operation: &str, template: String) -> TokenStream {
+        let apply_fn = match self {
+            RuntimeTable::Prefix => method(parsed, "apply_table_prefix"),
+            RuntimeTable::Table => method(parsed, "apply_table_name"),
+        };
+        let param = self.param();
+        let map_err = map_error(parsed, operation);
+        quote! {
+            let sql = #template;
+            let sql = Self::#apply_fn(sql, #param) #map_err ?;
+            let sql = sql.as_str();
+        }
+    }
+}
+
+/// 生成 `table_name_with_prefix` 和替换SQL模板中表名标记的私有方法
+pub fn generate_runtime_table_methods(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let table_name = &parsed.table_name;
+    let quote_char = match parsed.options.backend {
+        Backend::MySql => '`',
+        Backend::Sqlite | Backend::Postgres | Backend::Any => '"',
+    };
+    let methods = RuntimeTable::enabled(parsed).into_iter().map(|variant| match variant {
+        RuntimeTable::Prefix => {
+            let table_name_with_prefix_fn = method(parsed, "table_name_with_prefix");
+            let apply_fn = method(parsed, "apply_table_prefix");
+            quote! {
+                /// 获取加上前缀后的表名
+                #vis fn #table_name_with_prefix_fn(prefix: &str) -> String {
+                    format!("{}{}", prefix, #table_name)
+                }
+
+                /// 把SQL模板中的标记替换为 `prefix`，前缀中不能有引号或控制字符
+                fn #apply_fn(template: &str, prefix: &str) -> Result<String, sqlx::Error> {
+                    if prefix.contains(#quote_char) || prefix.chars().any(char::is_control) {
+                        return Err(sqlx::Error::Configuration(
+                            format!("table prefix `{}` must not contain {} or control characters", prefix.escape_default(), #quote_char).into(),
+                        ));
+                    }
+                    Ok(template.replace(#TABLE_MARKER, prefix))
+                }
+            }
+        }
+        RuntimeTable::Table => {
+            let apply_fn = method(parsed, "apply_table_name");
+            quote! {
+                /// 把SQL模板中的标记替换为表名 `table`，表名只能由字母、数字和下划线组成且不以数字开头
+                fn #apply_fn(template: &str, table: &str) -> Result<String, sqlx::Error> {
+                    let valid = table.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                        && table.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                    if !valid {
+                        return Err(sqlx::Error::Configuration(
+                            format!("table name `{}` must match [A-Za-z_][A-Za-z0-9_]*", table.escape_default()).into(),
+                        ));
+                    }
+                    Ok(template.replace(#TABLE_MARKER, table))
+                }
+            }
+        }
+    });
+
+    quote! {
+        #(#methods)*
+    }
+}
+
+/// 生成各语句对应的关联常量，被跳过的方法不生成对应常量
+pub fn generate_sql_consts(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let skip = &parsed.options.skip;
+    let mut consts = Vec::new();
+    if !skip.init_table {
+        if let Some(sql) = generate_create_schema_sql(parsed).filter(|_| parsed.options.create_schema) {
+            consts.push((quote!(CREATE_SCHEMA_SQL), sql));
+        }
+        consts.push((quote!(CREATE_TABLE_SQL), generate_create_table_sql(parsed)));
+    }
+    if !skip.insert {
+        consts.push((quote!(INSERT_SQL), generate_insert_sql(parsed).sql));
+    }
+    if !skip.update {
+        consts.push((quote!(UPDATE_SQL), generate_update_sql(parsed).sql));
+    }
+    if !skip.update && parsed.options.update_returning {
+        consts.push((quote!(UPDATE_RETURNING_SQL), generate_update_returning_sql(parsed)));
+    }
+    if !skip.delete {
+        consts.push((quote!(DELETE_SQL), generate_delete_sql(parsed).sql));
+    }
+    if !skip.find {
+        consts.push((quote!(SELECT_SQL), generate_select_sql(parsed)));
+        consts.push((quote!(SELECT_BY_ID_SQL), generate_select_by_id_sql(parsed).sql));
+        consts.push((quote!(COUNT_SQL), generate_count_sql(parsed)));
+    }
+    let consts = consts.into_iter().map(|(name, sql)| quote! {
+        #[doc = #sql]
+        #vis const #name: &'static str = #sql;
+    });
+    let comment_sqls = generate_comment_sqls(parsed);
+    let comment_consts = if skip.init_table || comment_sqls.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! {
+            /// 建表后设置表和列注释的 `COMMENT ON` 语句
+            #vis const COMMENT_SQLS: &'static [&'static str] = &[#(#comment_sqls),*];
+        }
+    };
+    
+    quote! {
+        #(#consts)*
+        #comment_consts
+    }
+}
+
+/// 生成返回列名和列类型的 `columns`，以及返回主键列名的 `primary_key_columns`
+pub fn generate_columns_methods(parsed: &ParsedStruct) -> TokenStream {
+    let columns_fn = method(parsed, "columns");
+    let primary_key_columns_fn = method(parsed, "primary_key_columns");
+    let vis = parsed.options.vis();
+    let names = parsed.fields.iter().map(|f| &f.name);
+    let sql_types = parsed.fields.iter().map(|f| column_type(&f.sql_type, parsed.options.backend));
+    let primary_keys = parsed.fields.iter()
+        .filter(|f| f.is_primary_key)
+        .map(|f| &f.name);
+    
+    quote! {
+        /// 按字段顺序返回 `(列名, 列类型)`，跳过的字段不在其中
+        #vis fn #columns_fn() -> &'static [(&'static str, &'static str)] {
+            &[#((#names, #sql_types)),*]
+        }
+
+        /// 返回主键列名
+        #vis fn #primary_key_columns_fn() -> &'static [&'static str] {
+            &[#(#primary_keys),*]
+        }
+    }
+}
+
+/// 生成按 #[validate] 校验所有字段的方法
+pub fn generate_validate_method(parsed: &ParsedStruct) -> TokenStream {
+    let validate_fn = method(parsed, "validate");
+    if !has_validators(parsed) {
+        return TokenStream::new();
+    }
+    let vis = parsed.options.vis();
+    let checks = parsed.fields.iter().flat_map(|f| {
+        let ident = &f.ident;
+        let field_name = ident.unraw().to_string();
+        let length_check = f.max_length.map(|max| {
+            let max = max as usize;
+            let value = if is_option(&f.ty) { quote!(self.#ident.as_deref()) } else { quote!(Some(&*self.#ident)) };
+            quote! {
+                if let Some(value) = #value {
+                    let length = value.chars().count();
+                    if length > #max {
+                        errors.push((#field_name, format!("length {} exceeds the maximum of {}", length, #max)));
+                    }
+                }
+            }
+        });
+        length_check.into_iter().chain(f.validators.iter().map(move |validator| quote! {
+            if let Err(message) = #validator(&self.#ident) {
+                errors.push((#field_name, message));
+            }
+        }))
+    });
+    
+    quote! {
+        /// 执行所有字段的校验函数，返回校验失败的字段名和错误信息
+        #vis fn #validate_fn(&self) -> Vec<(&'static str, String)> {
+            let mut errors = Vec::new();
+            #(#checks)*
+            errors
+        }
+    }
+}
+
 /// 生成表初始化方法
 pub fn generate_init_table_method(parsed: &ParsedStruct) -> TokenStream {
-    let create_table_sql = generate_create_table_sql(parsed);
+    let init_table_fn = method(parsed, "init_table");
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let execute = run_query(parsed, "init_table", quote!(sqlx::query(sql).execute(&mut *tx)), quote!(0usize), RowCount::Affected);
+    let map_err = map_error(parsed, "init_table");
+    let (begin_sql, commit_sql) = transaction_sql(parsed);
+    let begin = quote! {
+        #begin_sql
+        let mut tx = pool.begin().await #map_err ?;
+    };
+    let commit = quote! {
+        #commit_sql
+        tx.commit().await #map_err ?;
+    };
+    let comments = if generate_comment_sqls(parsed).is_empty() {
+        TokenStream::new()
+    } else {
+        quote! {
+            for sql in Self::COMMENT_SQLS {
+                #execute;
+            }
+        }
+    };
+    
+    let create_schema = if parsed.options.create_schema {
+        quote! {
+            let sql = Self::CREATE_SCHEMA_SQL;
+            #execute;
+        }
+    } else {
+        TokenStream::new()
+    };
+    let runtime_tables = RuntimeTable::enabled(parsed).into_iter().map(|variant| {
+        let init_table_fn = variant.method(parsed, "init_table");
+        let param = variant.param();
+        let doc = format!("初始化{}", variant.target());
+        let template = variant.template(parsed);
+        let statements = std::iter::once(generate_create_table_sql(&template))
+            .chain(generate_comment_sqls(&template))
+            .map(|sql| variant.sql(parsed, "init_table", sql));
+        quote! {
+            #[doc = #doc]
+            #vis async fn #init_table_fn(pool: &#pool_ty, #param: &str) -> Result<(), #error_ty> {
+                #begin
+                #create_schema
+                #({
+                    #statements
+                    #execute;
+                })*
+                #commit
+                Ok(())
+            }
+        }
+    });
+    
+    quote! {
+        /// 在一个事务中初始化表结构，Postgres 上随后设置表和列的注释，任一语句失败时全部回滚
+        ///
+        /// MySQL 的 DDL 会隐式提交，无法回滚已执行的语句
+        #vis async fn #init_table_fn(pool: &#pool_ty) -> Result<(), #error_ty> {
+            #begin
+            #create_schema
+            let sql = Self::CREATE_TABLE_SQL;
+            #execute;
+            #comments
+            #commit
+            Ok(())
+        }
+
+        #(#runtime_tables)*
+    }
+}
+
+/// 生成建表前设置 PRAGMA 的 `<Name>InitOptions`
+pub fn generate_init_options_type(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let options_name = format_ident!("{}InitOptions", parsed.name);
+
+    quote! {
+        /// `init_table_with` 和 `configure_connection` 在建表前执行的 PRAGMA
+        ///
+        /// PRAGMA 只作用于执行它的连接，连接池中的其他连接需要各自调用 `configure_connection`
+        #[derive(Debug, Clone, Default)]
+        #vis struct #options_name {
+            /// 执行 `PRAGMA foreign_keys = ON`，启用外键约束检查
+            #vis foreign_keys: bool,
+            /// 执行 `PRAGMA journal_mode = ...`，如 `"WAL"`
+            #vis journal_mode: Option<&'static str>,
+            /// 执行 `PRAGMA busy_timeout = ...`，单位为毫秒
+            #vis busy_timeout_ms: Option<u64>,
+        }
+    }
+}
+
+/// 生成 `configure_connection` 和 `init_table_with`：在连接上执行 PRAGMA，并在同一连接上建表
+pub fn generate_init_table_with_method(parsed: &ParsedStruct) -> TokenStream {
+    let configure_fn = method(parsed, "configure_connection");
+    let init_table_with_fn = method(parsed, "init_table_with");
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let options_name = format_ident!("{}InitOptions", parsed.name);
+    let map_err = map_error(parsed, "configure_connection");
+    let pragma = run_query(parsed, "configure_connection", quote!(sqlx::query(sql).execute(&mut *conn)), quote!(0usize), RowCount::Affected);
+    let execute = run_query(parsed, "init_table", quote!(sqlx::query(sql).execute(&mut *tx)), quote!(0usize), RowCount::Affected);
+    let acquire_err = map_error(parsed, "init_table");
+    let (begin_sql, commit_sql) = transaction_sql(parsed);
+    let comments = if generate_comment_sqls(parsed).is_empty() {
+        TokenStream::new()
+    } else {
+        quote! {
+            for sql in Self::COMMENT_SQLS {
+                #execute;
+            }
+        }
+    };
+
+    quote! {
+        /// 在连接上执行 `options` 要求的 PRAGMA
+        ///
+        /// PRAGMA 只作用于当前连接，可在连接池的 `after_connect` 中调用，使每个连接都启用外键检查
+        #vis async fn #configure_fn(conn: &mut sqlx::SqliteConnection, options: &#options_name) -> Result<(), #error_ty> {
+            if options.foreign_keys {
+                let sql = "PRAGMA foreign_keys = ON";
+                #pragma;
+            }
+            if let Some(mode) = options.journal_mode {
+                let sql = format!("PRAGMA journal_mode = {}", mode);
+                let known = ["DELETE", "TRUNCATE", "PERSIST", "MEMORY", "WAL", "OFF"]
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(mode));
+                if !known {
+                    let result: Result<(), sqlx::Error> = Err(sqlx::Error::Configuration(
+                        format!("unknown journal_mode `{}`", mode).into(),
+                    ));
+                    result #map_err ?;
+                }
+                let sql = sql.as_str();
+                #pragma;
+            }
+            if let Some(timeout) = options.busy_timeout_ms {
+                let sql = format!("PRAGMA busy_timeout = {}", timeout);
+                let sql = sql.as_str();
+                #pragma;
+            }
+            Ok(())
+        }
+
+        /// 从连接池取一个连接，执行 `options` 要求的 PRAGMA 后在同一连接上的一个事务中初始化表结构，任一语句失败时全部回滚
+        ///
+        /// PRAGMA 只作用于这一个连接，池中其他连接不受影响；`foreign_keys` 在事务中无法修改，因此 PRAGMA 在事务开始前执行
+        #vis async fn #init_table_with_fn(pool: &#pool_ty, options: #options_name) -> Result<(), #error_ty> {
+            let sql = Self::CREATE_TABLE_SQL;
+            let mut conn = pool.acquire().await #acquire_err ?;
+            Self::#configure_fn(&mut conn, &options).await?;
+            let mut tx = {
+                #begin_sql
+                sqlx::Connection::begin(&mut *conn).await #acquire_err ?
+            };
+            #execute;
+            #comments
+            {
+                #commit_sql
+                tx.commit().await #acquire_err ?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// 生成迁移方法：每列的 `add_column_<field>_sql`，以及为旧表中缺少的列生成 `ALTER TABLE ... ADD COLUMN` 语句的 `migration_from`
+pub fn generate_migration_method(parsed: &ParsedStruct) -> TokenStream {
+    let migration_from_fn = method(parsed, "migration_from");
+    let vis = parsed.options.vis();
+    let columns = parsed.fields.iter().map(|f| &f.name);
+    let statements: Vec<String> = parsed.fields.iter().map(|f| generate_add_column_sql(parsed, f)).collect();
+    let add_column_methods = parsed.fields.iter().zip(&statements).map(|(f, sql)| {
+        let method_name = format_ident!("{}add_column_{}_sql", parsed.options.prefix, f.ident);
+        quote! {
+            /// 为已有表添加该列的语句
+            #vis fn #method_name() -> &'static str {
+                #sql
+            }
+        }
+    });
+    
+    quote! {
+        #(#add_column_methods)*
+
+        /// 根据旧表的 `(列名, 类型)` 列表，生成添加当前结构体中新增列的语句
+        #vis fn #migration_from_fn(old_columns: &[(&str, &str)]) -> Vec<String> {
+            [#((#columns, #statements)),*]
+                .iter()
+                .filter(|(column, _)| !old_columns.iter().any(|(old, _)| old == column))
+                .map(|(_, sql)| sql.to_string())
+                .collect()
+        }
+    }
+}
+
+/// 生成获取表名方法
+pub fn generate_table_name_method(parsed: &ParsedStruct) -> TokenStream {
+    let table_name_fn = method(parsed, "table_name");
+    let vis = parsed.options.vis();
+    let qualified_table_name_fn = method(parsed, "qualified_table_name");
     let table_name = &parsed.table_name;
+    let qualified_table_name = match &parsed.schema {
+        Some(schema) => format!("{}.{}", schema, table_name),
+        None => table_name.clone(),
+    };
+    
+    quote! {
+        /// 获取表名
+        #vis fn #table_name_fn() -> &'static str {
+            #table_name
+        }
+
+        /// 获取带模式名的表名，未指定 #[schema] 时与 `table_name` 相同
+        #vis fn #qualified_table_name_fn() -> &'static str {
+            #qualified_table_name
+        }
+    }
+}
+
+/// 生成插入记录方法
+pub fn generate_insert_method(parsed: &ParsedStruct) -> TokenStream {
+    let insert_fn = method(parsed, "insert");
+    let into_insert_fn = method(parsed, "into_insert");
+    let insert_one_fn = method(parsed, "insert_one");
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let (exec_generics, exec_ty, exec_bound) = executor_param(parsed);
+    
+    let statement = generate_insert_sql(parsed);
+    let by_ref = statement.binds.iter().map(|f| {
+        let value = field_access(f, quote!(self));
+        bind_value(f, quote!(&#value))
+    });
+    let by_value = statement.binds.iter().map(|f| {
+        let value = field_access(f, quote!(self));
+        bind_value(f, quote!(#value))
+    });
+    let params = statement.binds.len();
+    let output = insert_output(parsed);
+    let insert = insert_query(parsed, "insert", quote!(#(.bind(#by_ref))*), quote!(#params));
+    let into_insert = insert_query(parsed, "into_insert", quote!(#(.bind(#by_value))*), quote!(#params));
+    let receiver = insert_receiver(parsed);
+    let generate_uuids = uuid_generation(parsed);
+    let validate_insert = validation_check(parsed, "insert");
+    let validate_into_insert = validation_check(parsed, "into_insert");
+    let before_insert = hook_call(parsed, "before_insert");
+    let after_insert = hook_call(parsed, "after_insert");
+    // 启用钩子时 after_insert 需要完整的记录，重试时需要多次绑定，按值插入都退化为按引用插入
+    let into_insert_body = if writes_need_pool(parsed) {
+        quote! {
+            let mut this = self;
+            this.#insert_fn(executor).await
+        }
+    } else {
+        quote! {
+            #generate_uuids
+            #validate_into_insert
+            let sql = Self::INSERT_SQL;
+            let id = #into_insert;
+            Ok(id)
+        }
+    };
+    let into_receiver = if insert_needs_mut(parsed) { quote!(mut self) } else { quote!(self) };
+    let item_ty = if insert_needs_mut(parsed) { quote!(&mut Self) } else { quote!(&Self) };
+    let runtime_tables = RuntimeTable::enabled(parsed).into_iter().map(|variant| {
+        let insert_fn = variant.method(parsed, "insert");
+        let param = variant.param();
+        let doc = format!("插入记录到{}", variant.target());
+        let sql = variant.sql(parsed, "insert", generate_insert_sql(&variant.template(parsed)).sql);
+        quote! {
+            #[doc = #doc]
+            #vis async fn #insert_fn #exec_generics (#receiver, executor: #exec_ty, #param: &str) -> Result<#output, #error_ty> #exec_bound {
+                #generate_uuids
+                #before_insert
+                #validate_insert
+                #sql
+                let id = #insert;
+                #after_insert
+                Ok(id)
+            }
+        }
+    });
+    
+    quote! {
+        /// 插入记录，有 `#[auto_increment]` 主键时返回数据库生成的主键值
+        #vis async fn #insert_fn #exec_generics (#receiver, executor: #exec_ty) -> Result<#output, #error_ty> #exec_bound {
+            #generate_uuids
+            #before_insert
+            #validate_insert
+            let sql = Self::INSERT_SQL;
+            let id = #insert;
+            #after_insert
+            Ok(id)
+        }
+
+        /// 按值插入记录，绑定字段的所有权而非引用
+        #vis async fn #into_insert_fn #exec_generics (#into_receiver, executor: #exec_ty) -> Result<#output, #error_ty> #exec_bound {
+            #into_insert_body
+        }
+
+        /// 插入记录并返回插入的对象
+        #vis async fn #insert_one_fn #exec_generics (executor: #exec_ty, item: #item_ty) -> Result<#output, #error_ty> #exec_bound {
+            item.#insert_fn(executor).await
+        }
+
+        #(#runtime_tables)*
+    }
+}
+
+/// `insert` 的返回类型：有自增主键时为主键类型，否则为 `()`
+fn insert_output(parsed: &ParsedStruct) -> TokenStream {
+    match parsed.auto_increment_field() {
+        Some(key) => {
+            let ty = &key.ty;
+            quote!(#ty)
+        }
+        None => quote!(()),
+    }
+}
+
+/// 绑定字段值时使用的表达式，#[duration(...)] 字段按声明的单位转换为整数，#[ip_as_text] 和 `char` 字段转换为文本
+fn bind_value(field: &ParsedField, value: TokenStream) -> TokenStream {
+    let encode = |v: TokenStream| match field.codec {
+        Some(ColumnCodec::Duration(unit)) => {
+            let as_int = format_ident!("{}", unit.encoder());
+            quote!(#v.#as_int() as i64)
+        }
+        Some(ColumnCodec::IpText | ColumnCodec::Char) => quote!(#v.to_string()),
+        None => v,
+    };
+    match field.codec {
+        None => value,
+        Some(_) if is_option(&field.ty) => {
+            let encoded = encode(quote!(v));
+            quote!((#value).map(|v| #encoded))
+        }
+        Some(_) => encode(quote!((#value))),
+    }
+}
+
+/// 在 `receiver` 上读取字段值的表达式，#[flatten] 展开的列为 `receiver.<字段>.<子字段>`
+fn field_access(field: &ParsedField, receiver: TokenStream) -> TokenStream {
+    match &field.flattened {
+        Some(flattened) => {
+            let outer = &flattened.field;
+            let member = &flattened.member;
+            quote!(#receiver.#outer.#member)
+        }
+        None => {
+            let ident = &field.ident;
+            quote!(#receiver.#ident)
+        }
+    }
+}
+
+/// 执行插入语句并得到 `insert` 的返回值，`binds` 为依次绑定字段的调用，`params` 为绑定参数个数的表达式
+///
+/// 自增主键在 SQLite 上取 `last_insert_rowid`，MySQL 上取 `last_insert_id`，Postgres 上取 `RETURNING` 的结果
+fn insert_query(parsed: &ParsedStruct, operation: &str, binds: TokenStream, params: TokenStream) -> TokenStream {
+    insert_query_with(parsed, operation, |query| quote!(#query #binds), params)
+}
+
+/// 与 `insert_query` 相同，`bind` 接收构造查询的表达式并返回绑定参数后的查询，用于按条件绑定
+fn insert_query_with(parsed: &ParsedStruct, operation: &str, bind: impl Fn(TokenStream) -> TokenStream, params: TokenStream) -> TokenStream {
+    let key = match parsed.auto_increment_field() {
+        Some(key) => key,
+        None => {
+            let query = bind(quote!(sqlx::query(sql)));
+            let execute = run_query(parsed, operation, quote!(#query.execute(executor)), params.clone(), RowCount::Affected);
+            return quote!({ #execute; })
+        }
+    };
+    let ty = &key.ty;
+    match parsed.options.backend {
+        Backend::Postgres => {
+            let query = bind(quote!(sqlx::query_scalar::<_, #ty>(sql)));
+            run_query(parsed, operation, quote!(#query.fetch_one(executor)), params.clone(), RowCount::One)
+        }
+        backend => {
+            let query = bind(quote!(sqlx::query(sql)));
+            let execute = run_query(parsed, operation, quote!(#query.execute(executor)), params, RowCount::Affected);
+            let last_id = match backend {
+                Backend::MySql => quote!(last_insert_id),
+                _ => quote!(last_insert_rowid),
+            };
+            quote!((#execute).#last_id() as #ty)
+        }
+    }
+}
+
+/// 生成 #[sql_crud(insert_present)] 的 `insert_present`：省略值为 `None` 的 `Option` 字段，由列的默认值填充
+pub fn generate_insert_present_method(parsed: &ParsedStruct) -> TokenStream {
+    let insert_present_fn = method(parsed, "insert_present");
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let (exec_generics, exec_ty, exec_bound) = executor_param(parsed);
+    let statement = generate_insert_present_sql(parsed);
+    let placeholder = placeholder_fn(parsed);
+    // 字段是否写入：`Option` 字段仅在为 `Some` 时写入
+    let present = |f: &ParsedField| {
+        let value = field_access(f, quote!(self));
+        if is_option(&f.ty) { quote!(#value.is_some()) } else { quote!(true) }
+    };
+    let push_columns = statement.columns.iter().map(|(f, column)| {
+        let present = present(f);
+        quote! {
+            if #present {
+                columns.push(#column);
+            }
+        }
+    });
+    let binds: Vec<TokenStream> = statement.columns.iter().map(|(f, _)| {
+        let present = present(f);
+        let value = field_access(f, quote!(self));
+        let value = bind_value(f, quote!(&#value));
+        quote! {
+            if #present {
+                query = query.bind(#value);
+            }
+        }
+    }).collect();
+    let insert = insert_query_with(parsed, "insert_present", |query| quote!({
+        let mut query = #query;
+        #(#binds)*
+        query
+    }), quote!(columns.len()));
+    let output = insert_output(parsed);
+    let receiver = insert_receiver(parsed);
+    let generate_uuids = uuid_generation(parsed);
+    let validate_insert = validation_check(parsed, "insert_present");
+    let before_insert = hook_call(parsed, "before_insert");
+    let after_insert = hook_call(parsed, "after_insert");
+    let (head, values, tail, empty) = (&statement.head, &statement.values, &statement.tail, &statement.empty);
+    
+    quote! {
+        /// 插入记录，省略值为 `None` 的 `Option` 字段，使这些列取数据库的默认值
+        ///
+        /// 列名在运行时按有值的字段拼接，不使用 `INSERT_SQL`
+        #vis async fn #insert_present_fn #exec_generics (#receiver, executor: #exec_ty) -> Result<#output, #error_ty> #exec_bound {
+            #generate_uuids
+            #before_insert
+            #validate_insert
+            let placeholder = #placeholder;
+            let mut columns: Vec<&str> = Vec::new();
+            #(#push_columns)*
+            let sql = if columns.is_empty() {
+                #empty.to_string()
+            } else {
+                let placeholders = (0..columns.len())
+                    .map(placeholder)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{}{}{}{}{}", #head, columns.join(", "), #values, placeholders, #tail)
+            };
+            let sql = sql.as_str();
+            let id = #insert;
+            #after_insert
+            Ok(id)
+        }
+    }
+}
+
+/// 生成 `get_or_insert`：在同一事务中冲突时忽略插入，再按冲突列读取数据库中的记录
+///
+/// 没有可用的冲突列（自增主键且无 #[unique] 字段）时不生成
+pub fn generate_get_or_insert_method(parsed: &ParsedStruct) -> TokenStream {
+    let get_or_insert_fn = method(parsed, "get_or_insert");
+    let key = match parsed.conflict_key() {
+        Some(key) => key,
+        None => return TokenStream::new(),
+    };
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let row_bound = row_bound(parsed);
+    let insert_sql = generate_insert_ignore_sql(parsed, key);
+    let select_sql = generate_select_by_key_sql(parsed, key);
+    let map_err = map_error(parsed, "get_or_insert");
+    let (begin_sql, commit_sql) = transaction_sql(parsed);
+    
+    let values = parsed.insertable_fields().map(|f| {
+        let value = field_access(f, quote!(item));
+        bind_value(f, quote!(&#value))
+    });
+    let insert_params = parsed.insertable_fields().count();
+    let key_ident = &key.ident;
+    let key_value = bind_value(key, quote!(&item.#key_ident));
+    let insert = run_query(parsed, "get_or_insert", quote! {
+        sqlx::query(sql)
+            #(.bind(#values))*
+            .execute(&mut *tx)
+    }, quote!(#insert_params), RowCount::Affected);
+    let select = run_query(parsed, "get_or_insert", quote! {
+        sqlx::query_as::<_, Self>(sql)
+            .bind(#key_value)
+            .fetch_one(&mut *tx)
+    }, quote!(1usize), RowCount::One);
+    
+    quote! {
+        /// 记录不存在时插入，返回数据库中的记录（包括数据库赋值的列）
+        #vis async fn #get_or_insert_fn(pool: &#pool_ty, item: &Self) -> Result<Self, #error_ty> #row_bound {
+            #begin_sql
+            let mut tx = pool.begin().await #map_err ?;
+            let sql = #insert_sql;
+            #insert;
+            let sql = #select_sql;
+            let record = #select;
+            #commit_sql
+            tx.commit().await #map_err ?;
+            Ok(record)
+        }
+    }
+}
+
+/// 生成 `upsert_many`：按冲突列批量插入或更新，每条语句包含多行，超过后端的参数上限时分批在同一事务中执行
+///
+/// 没有可用的冲突列时不生成
+pub fn generate_upsert_many_method(parsed: &ParsedStruct) -> TokenStream {
+    let upsert_many_fn = method(parsed, "upsert_many");
+    let key = match parsed.conflict_key() {
+        Some(key) => key,
+        None => return TokenStream::new(),
+    };
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let (prefix, suffix) = generate_upsert_many_sql(parsed, key);
+    let map_err = map_error(parsed, "upsert_many");
+    let (begin_sql, commit_sql) = transaction_sql(parsed);
+    let max_params = max_params(parsed);
+    let columns = parsed.insertable_fields().count();
+    let placeholder = placeholder_fn(parsed);
+    let values = parsed.insertable_fields().map(|f| {
+        let value = field_access(f, quote!(item));
+        bind_value(f, quote!(&#value))
+    });
+    let validate = match &parsed.options.error {
+        Some(error) if has_validators(parsed) => {
+            let validate_fn = method(parsed, "validate");
+            let table = &parsed.table_name;
+            quote! {
+                for item in items {
+                    let errors = item.#validate_fn();
+                    if !errors.is_empty() {
+                        return Err(#error::Validation { operation: "upsert_many", table: #table, errors });
+                    }
+                }
+            }
+        }
+        _ => TokenStream::new(),
+    };
+    let execute = run_query(parsed, "upsert_many", quote! {{
+        let mut query = sqlx::query(&sql);
+        for item in chunk {
+            query = query #(.bind(#values))*;
+        }
+        query.execute(&mut *tx)
+    }}, quote!(chunk.len() * #columns), RowCount::Affected);
+    
+    quote! {
+        /// 按冲突列批量插入记录，已存在的记录更新除冲突列和主键以外的列，切片为空时不访问数据库
+        ///
+        /// 多行合并为一条语句，超过参数上限时在同一事务中分批执行；不调用钩子、不生成 UUID。
+        /// 同一批中的冲突列取值应互不相同，Postgres 不允许一条语句多次更新同一行
+        #vis async fn #upsert_many_fn(pool: &#pool_ty, items: &[Self]) -> Result<(), #error_ty> {
+            if items.is_empty() {
+                return Ok(());
+            }
+            #validate
+            let placeholder = #placeholder;
+            #begin_sql
+            let mut tx = pool.begin().await #map_err ?;
+            for chunk in items.chunks(#max_params / #columns) {
+                let rows = (0..chunk.len())
+                    .map(|row| {
+                        let row = (0..#columns)
+                            .map(|column| placeholder(row * #columns + column))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        format!("({})", row)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let sql = format!("{}{}{}", #prefix, rows, #suffix);
+                #execute;
+            }
+            #commit_sql
+            tx.commit().await #map_err ?;
+            Ok(())
+        }
+    }
+}
+
+/// 生成在事务中执行闭包的 `with_transaction`
+pub fn generate_transaction_method(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let db = database_type(parsed);
+    let with_transaction_fn = method(parsed, "with_transaction");
+    let map_err = map_error(parsed, "with_transaction");
+    let (begin_sql, commit_sql) = transaction_sql(parsed);
+    
+    quote! {
+        /// 在事务中执行 `f`，返回 `Ok` 时提交；返回 `Err` 或 panic 时回滚
+        ///
+        /// 闭包中把 `&mut **tx` 传给生成的写方法即可在同一事务中执行
+        #vis async fn #with_transaction_fn<TxOutput, TxBody>(pool: &#pool_ty, f: TxBody) -> Result<TxOutput, #error_ty>
+        where
+            TxBody: for<'c> FnOnce(&'c mut sqlx::Transaction<'static, #db>) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<TxOutput, #error_ty>> + Send + 'c>>,
+        {
+            #begin_sql
+            let mut tx = pool.begin().await #map_err ?;
+            match f(&mut tx).await {
+                Ok(value) => {
+                    #commit_sql
+                    tx.commit().await #map_err ?;
+                    Ok(value)
+                }
+                Err(err) => {
+                    // 回滚失败时连接会被丢弃，事务同样不会提交，返回闭包的错误更有意义
+                    let _ = tx.rollback().await;
+                    Err(err)
+                }
+            }
+        }
+    }
+}
+
+/// 生成更新记录方法
+pub fn generate_update_method(parsed: &ParsedStruct) -> TokenStream {
+    let update_fn = method(parsed, "update");
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let (exec_generics, exec_ty, exec_bound) = executor_param(parsed);
+    
+    let statement = generate_update_sql(parsed);
+    let params = statement.binds.len();
+    let values = statement.binds.iter().map(|f| {
+        let value = field_access(f, quote!(self));
+        bind_value(f, quote!(&#value))
+    });
+    let execute = run_query(parsed, "update", quote! {
+        sqlx::query(sql)
+            #(.bind(#values))*
+            .execute(executor)
+    }, quote!(#params), RowCount::Affected);
+    let execute = match &parsed.options.error {
+        Some(error) => {
+            let table = &parsed.table_name;
+            quote! {
+                let result = #execute;
+                if result.rows_affected() == 0 {
+                    return Err(#error::NotFound { operation: "update", table: #table });
+                }
+            }
+        }
+        None => quote!(#execute;),
+    };
+    let receiver = write_receiver(parsed);
+    let before_update = hook_call(parsed, "before_update");
+    let validate_update = validation_check(parsed, "update");
+    let after_update = hook_call(parsed, "after_update");
+    let runtime_tables = RuntimeTable::enabled(parsed).into_iter().map(|variant| {
+        let update_fn = variant.method(parsed, "update");
+        let param = variant.param();
+        let doc = format!("更新{}中的记录", variant.target());
+        let sql = variant.sql(parsed, "update", generate_update_sql(&variant.template(parsed)).sql);
+        quote! {
+            #[doc = #doc]
+            #vis async fn #update_fn #exec_generics (#receiver, executor: #exec_ty, #param: &str) -> Result<(), #error_ty> #exec_bound {
+                #before_update
+                #validate_update
+                #sql
+                #execute
+                #after_update
+                Ok(())
+            }
+        }
+    });
+    
+    quote! {
+        /// 更新记录
+        #vis async fn #update_fn #exec_generics (#receiver, executor: #exec_ty) -> Result<(), #error_ty> #exec_bound {
+            #before_update
+            #validate_update
+            let sql = Self::UPDATE_SQL;
+            #execute
+            #after_update
+            Ok(())
+        }
+
+        #(#runtime_tables)*
+    }
+}
+
+/// 生成 `update_returning`：执行 `UPDATE ... RETURNING`，返回数据库中更新后的记录
+pub fn generate_update_returning_method(parsed: &ParsedStruct) -> TokenStream {
+    let update_returning_fn = method(parsed, "update_returning");
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let (exec_generics, exec_ty, exec_bound) = executor_param(parsed);
+    let bound = match (parsed.generics.params.is_empty(), exec_bound.is_empty()) {
+        (true, _) => exec_bound,
+        (false, true) => row_bound(parsed),
+        (false, false) => {
+            let row_ty = row_type(parsed);
+            quote!(#exec_bound, Self: for<'r> sqlx::FromRow<'r, #row_ty> + Send + Unpin)
+        }
+    };
+    
+    // RETURNING 追加在更新语句之后，绑定与更新语句相同
+    let values: Vec<TokenStream> = generate_update_sql(parsed).binds.iter().map(|f| {
+        let value = field_access(f, quote!(self));
+        bind_value(f, quote!(&#value))
+    }).collect();
+    let params = values.len();
+    // 启用 error 时与 update 一样把没有匹配的记录报告为 NotFound，否则为 sqlx::Error::RowNotFound
+    let fetch = match &parsed.options.error {
+        Some(error) => {
+            let table = &parsed.table_name;
+            let fetch = run_query(parsed, "update_returning", quote! {
+                sqlx::query_as::<_, Self>(sql)
+                    #(.bind(#values))*
+                    .fetch_optional(executor)
+            }, quote!(#params), RowCount::Optional);
+            quote! {
+                let record = match #fetch {
+                    Some(record) => record,
+                    None => return Err(#error::NotFound { operation: "update_returning", table: #table }),
+                };
+            }
+        }
+        None => {
+            let fetch = run_query(parsed, "update_returning", quote! {
+                sqlx::query_as::<_, Self>(sql)
+                    #(.bind(#values))*
+                    .fetch_one(executor)
+            }, quote!(#params), RowCount::One);
+            quote!(let record = #fetch;)
+        }
+    };
+    let receiver = write_receiver(parsed);
+    let before_update = hook_call(parsed, "before_update");
+    let validate_update = validation_check(parsed, "update_returning");
+    let after_update = hook_call(parsed, "after_update");
+    
+    quote! {
+        /// 更新记录并返回数据库中更新后的记录（包括数据库计算的列）
+        #vis async fn #update_returning_fn #exec_generics (#receiver, executor: #exec_ty) -> Result<Self, #error_ty> #bound {
+            #before_update
+            #validate_update
+            let sql = Self::UPDATE_RETURNING_SQL;
+            #fetch
+            #after_update
+            Ok(record)
+        }
+    }
+}
+
+/// 为 #[counter] 字段生成原子自增方法
+pub fn generate_increment_methods(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let (exec_generics, exec_ty, exec_bound) = executor_param(parsed);
+    
+    let pk = parsed.fields.iter()
+        .find(|f| f.is_primary_key)
+        .expect("No primary key defined");
+    let pk_type = &pk.ty;
+    let pk_param = &pk.ident;
+    let pk_value = bind_value(pk, quote!(#pk_param));
+    
+    let methods = parsed.fields.iter()
+        .filter(|f| f.is_counter)
+        .map(|f| {
+            let increment_sql = generate_increment_sql(parsed, f);
+            let method_name = format_ident!("{}increment_{}", parsed.options.prefix, f.ident);
+            let execute = run_query(parsed, &format!("increment_{}", f.ident.unraw()), quote! {
+                sqlx::query(sql)
+                    .bind(delta)
+                    .bind(#pk_value)
+                    .execute(executor)
+            }, quote!(2usize), RowCount::Affected);
+            
+            quote! {
+                /// 在数据库中原子地增加计数，`delta` 为负数时递减，返回影响的行数
+                #vis async fn #method_name #exec_generics (executor: #exec_ty, #pk_param: &#pk_type, delta: i64) -> Result<u64, #error_ty> #exec_bound {
+                    let sql = #increment_sql;
+                    let result = #execute;
+                    Ok(result.rows_affected())
+                }
+            }
+        });
+    
+    quote! {
+        #(#methods)*
+    }
+}
+
+/// 生成删除记录方法
+pub fn generate_delete_method(parsed: &ParsedStruct) -> TokenStream {
+    let delete_fn = method(parsed, "delete");
+    let delete_by_id_fn = method(parsed, "delete_by_id");
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let (exec_generics, exec_ty, exec_bound) = executor_param(parsed);
+    
+    let pk = parsed.fields.iter()
+        .find(|f| f.is_primary_key)
+        .expect("No primary key defined");
+    let pk_type = &pk.ty;
+    let pk_param = &pk.ident;
+    // delete 绑定记录上的主键，delete_by_id 绑定同名的参数
+    let statement = generate_delete_sql(parsed);
+    let params = statement.binds.len();
+    let self_values = statement.binds.iter().map(|f| {
+        let value = field_access(f, quote!(self));
+        bind_value(f, quote!(&#value))
+    });
+    let param_values = statement.binds.iter().map(|f| {
+        let ident = &f.ident;
+        bind_value(f, quote!(#ident))
+    });
+    let delete = run_query(parsed, "delete", quote! {
+        sqlx::query(sql)
+            #(.bind(#self_values))*
+            .execute(executor)
+    }, quote!(#params), RowCount::Affected);
+    let delete_by_id = run_query(parsed, "delete_by_id", quote! {
+        sqlx::query(sql)
+            #(.bind(#param_values))*
+            .execute(executor)
+    }, quote!(#params), RowCount::Affected);
+    let before_delete = hook_call(parsed, "before_delete");
+    let after_delete = hook_call(parsed, "after_delete");
+    let runtime_tables = RuntimeTable::enabled(parsed).into_iter().map(|variant| {
+        let delete_fn = variant.method(parsed, "delete");
+        let delete_by_id_fn = variant.method(parsed, "delete_by_id");
+        let param = variant.param();
+        let delete_doc = format!("从{}中删除记录", variant.target());
+        let delete_by_id_doc = format!("按ID从{}中删除记录", variant.target());
+        let template = generate_delete_sql(&variant.template(parsed)).sql;
+        let sql = variant.sql(parsed, "delete", template.clone());
+        let by_id_sql = variant.sql(parsed, "delete_by_id", template);
+        quote! {
+            #[doc = #delete_doc]
+            #vis async fn #delete_fn #exec_generics (&self, executor: #exec_ty, #param: &str) -> Result<(), #error_ty> #exec_bound {
+                #before_delete
+                #sql
+                #delete;
+                #after_delete
+                Ok(())
+            }
+
+            #[doc = #delete_by_id_doc]
+            #vis async fn #delete_by_id_fn #exec_generics (executor: #exec_ty, #param: &str, #pk_param: &#pk_type) -> Result<(), #error_ty> #exec_bound {
+                #by_id_sql
+                #delete_by_id;
+                Ok(())
+            }
+        }
+    });
+    
+    quote! {
+        /// 删除记录
+        #vis async fn #delete_fn #exec_generics (&self, executor: #exec_ty) -> Result<(), #error_ty> #exec_bound {
+            #before_delete
+            let sql = Self::DELETE_SQL;
+            #delete;
+            #after_delete
+            Ok(())
+        }
+
+        /// 按ID删除记录
+        #vis async fn #delete_by_id_fn #exec_generics (executor: #exec_ty, #pk_param: &#pk_type) -> Result<(), #error_ty> #exec_bound {
+            let sql = Self::DELETE_SQL;
+            #delete_by_id;
+            Ok(())
+        }
+
+        #(#runtime_tables)*
+    }
+}
+
+/// 生成按列删除记录的 `delete_by_<column>`，返回删除的行数
+///
+/// 主键已有 `delete_by_id`，不为它生成
+pub fn generate_delete_by_methods(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let (exec_generics, exec_ty, exec_bound) = executor_param(parsed);
+    
+    let methods = parsed.fields.iter()
+        .filter(|f| f.is_queryable && !f.is_primary_key)
+        .map(|f| {
+            let delete_sql = generate_delete_by_column_sql(parsed, f);
+            let delete_by = format_ident!("{}delete_by_{}", parsed.options.prefix, f.ident);
+            let ty = &f.ty;
+            let value = bind_value(f, quote!(value));
+            let execute = run_query(parsed, &format!("delete_by_{}", f.ident.unraw()), quote! {
+                sqlx::query(sql)
+                    .bind(#value)
+                    .execute(executor)
+            }, quote!(1usize), RowCount::Affected);
+            
+            quote! {
+                /// 按列删除所有匹配的记录，返回删除的行数
+                #vis async fn #delete_by #exec_generics (executor: #exec_ty, value: &#ty) -> Result<u64, #error_ty> #exec_bound {
+                    let sql = #delete_sql;
+                    let result = #execute;
+                    Ok(result.rows_affected())
+                }
+            }
+        });
+    
+    quote! {
+        #(#methods)*
+    }
+}
+
+/// 生成查询记录方法
+pub fn generate_select_methods(parsed: &ParsedStruct) -> TokenStream {
+    let find_all_fn = method(parsed, "find_all");
+    let find_all_stream_fn = method(parsed, "find_all_stream");
+    let find_by_id_fn = method(parsed, "find_by_id");
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let row_bound = row_bound(parsed);
+    let stream_bound = if parsed.generics.params.is_empty() {
+        TokenStream::new()
+    } else {
+        let row_ty = row_type(parsed);
+        quote!(where Self: 'e + for<'r> sqlx::FromRow<'r, #row_ty> + Send + Unpin)
+    };
+    let find_all_stream = if parsed.options.stream {
+        quote! {
+            /// 以流的形式查询所有记录，不会一次性缓冲整个结果集，调用方需依赖 `futures-core`
+            #vis fn #find_all_stream_fn<'e>(pool: &'e #pool_ty) -> impl futures_core::Stream<Item = Result<Self, sqlx::Error>> + Send + 'e #stream_bound {
+                let sql = Self::SELECT_SQL;
+                sqlx::query_as::<_, Self>(sql).fetch(pool)
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+    
+    let pk = parsed.fields.iter()
+        .find(|f| f.is_primary_key)
+        .expect("No primary key defined");
+    let pk_type = &pk.ty;
+    let pk_param = &pk.ident;
+    let select_by_id = generate_select_by_id_sql(parsed);
+    let params = select_by_id.binds.len();
+    let param_values = select_by_id.binds.into_iter().map(|f| {
+        let ident = &f.ident;
+        bind_value(f, quote!(#ident))
+    });
+    let find_all = run_query(parsed, "find_all", quote! {
+        sqlx::query_as::<_, Self>(sql).fetch_all(pool)
+    }, quote!(0usize), RowCount::All);
+    let find_by_id = run_query(parsed, "find_by_id", quote! {
+        sqlx::query_as::<_, Self>(sql)
+            #(.bind(#param_values))*
+            .fetch_optional(pool)
+    }, quote!(#params), RowCount::Optional);
+    let runtime_tables = RuntimeTable::enabled(parsed).into_iter().map(|variant| {
+        let find_all_fn = variant.method(parsed, "find_all");
+        let find_by_id_fn = variant.method(parsed, "find_by_id");
+        let param = variant.param();
+        let find_all_doc = format!("查询{}中的所有记录", variant.target());
+        let find_by_id_doc = format!("按ID查询{}中的记录", variant.target());
+        let template = variant.template(parsed);
+        let find_all_sql = variant.sql(parsed, "find_all", generate_select_sql(&template));
+        let find_by_id_sql = variant.sql(parsed, "find_by_id", generate_select_by_id_sql(&template).sql);
+        quote! {
+            #[doc = #find_all_doc]
+            #vis async fn #find_all_fn(pool: &#pool_ty, #param: &str) -> Result<Vec<Self>, #error_ty> #row_bound {
+                #find_all_sql
+                let records = #find_all;
+                Ok(records)
+            }
+
+            #[doc = #find_by_id_doc]
+            #vis async fn #find_by_id_fn(pool: &#pool_ty, #param: &str, #pk_param: &#pk_type) -> Result<Option<Self>, #error_ty> #row_bound {
+                #find_by_id_sql
+                let record = #find_by_id;
+                Ok(record)
+            }
+        }
+    });
+    
+    quote! {
+        /// 查询所有记录
+        #vis async fn #find_all_fn(pool: &#pool_ty) -> Result<Vec<Self>, #error_ty> #row_bound {
+            let sql = Self::SELECT_SQL;
+            let records = #find_all;
+            Ok(records)
+        }
+
+        #find_all_stream
+
+        /// 按ID查询记录
+        #vis async fn #find_by_id_fn(pool: &#pool_ty, #pk_param: &#pk_type) -> Result<Option<Self>, #error_ty> #row_bound {
+            let sql = Self::SELECT_BY_ID_SQL;
+            let record = #find_by_id;
+            Ok(record)
+        }
+
+        #(#runtime_tables)*
+    }
+}
+
+/// 生成 #[has_many] 声明的子记录加载方法
+///
+/// 宏看不到子结构体的字段，查询语句在运行时由子结构体的 `SELECT_SQL` 加上外键条件拼成，
+/// 子结构体没有派生 SqlCRUD 或跳过了查询方法时编译报错
+pub fn generate_has_many_methods(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    
+    let pk = parsed.fields.iter()
+        .find(|f| f.is_primary_key)
+        .expect("No primary key defined");
+    let pk_field = &pk.ident;
+    let pk_value = bind_value(pk, quote!(&self.#pk_field));
+    
+    let methods = parsed.has_many.iter().map(|relation| {
+        let child = &relation.child;
+        let method_name = method(parsed, &relation.method.to_string());
+        let where_sql = generate_has_many_where_sql(parsed, relation);
+        let fetch_all = run_query(parsed, &relation.method.to_string(), quote! {
+            sqlx::query_as::<_, #child>(sql)
+                .bind(#pk_value)
+                .fetch_all(pool)
+        }, quote!(1usize), RowCount::All);
+        
+        quote! {
+            /// 加载外键指向本记录的子记录
+            #vis async fn #method_name(&self, pool: &#pool_ty) -> Result<Vec<#child>, #error_ty> {
+                let sql = format!("{}{}", <#child>::SELECT_SQL.trim_end_matches(';'), #where_sql);
+                let sql = sql.as_str();
+                let records = #fetch_all;
+                Ok(records)
+            }
+        }
+    });
+    
+    // find_all_with_*：先查询所有父记录，再按主键分批用 IN 查询一次取回子记录，按外键分组后与父记录依次配对
+    let row_ty = row_type(parsed);
+    let row_bound = row_bound(parsed);
+    let pk_ty = &pk.ty;
+    let pk_item = bind_value(pk, quote!(&parent.#pk_field));
+    let find_all = method(parsed, "find_all");
+    let max_params = max_params(parsed);
+    let placeholder = placeholder_fn(parsed);
+    let eager_methods = parsed.has_many.iter().filter(|_| !parsed.options.skip.find).map(|relation| {
+        let child = &relation.child;
+        let operation = format!("find_all_with_{}", relation.method);
+        let method_name = method(parsed, &operation);
+        let in_sql = generate_has_many_in_sql(parsed, relation);
+        let foreign_key = &relation.foreign_key;
+        let map_err = map_error(parsed, &operation);
+        let fetch_children = run_query(parsed, &operation, quote! {{
+            let mut query = sqlx::query(&sql);
+            for parent in chunk {
+                query = query.bind(#pk_item);
+            }
+            query.fetch_all(pool)
+        }}, quote!(chunk.len()), RowCount::All);
+        
+        quote! {
+            /// 查询所有记录及其子记录，子记录按主键分批一次取回，没有子记录的记录对应空列表
+            #vis async fn #method_name(pool: &#pool_ty) -> Result<Vec<(Self, Vec<#child>)>, #error_ty> #row_bound {
+                let parents = Self::#find_all(pool).await?;
+                let mut grouped: std::collections::HashMap<#pk_ty, Vec<#child>> = std::collections::HashMap::new();
+                for chunk in parents.chunks(#max_params) {
+                    let placeholders = (0..chunk.len())
+                        .map(#placeholder)
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let sql = format!("{}{}{});", <#child>::SELECT_SQL.trim_end_matches(';'), #in_sql, placeholders);
+                    let rows = #fetch_children;
+                    for row in &rows {
+                        let key: #pk_ty = sqlx::Row::try_get(row, #foreign_key) #map_err ?;
+                        let record = <#child as sqlx::FromRow<'_, #row_ty>>::from_row(row) #map_err ?;
+                        grouped.entry(key).or_default().push(record);
+                    }
+                }
+                Ok(parents
+                    .into_iter()
+                    .map(|parent| {
+                        let children = grouped.remove(&parent.#pk_field).unwrap_or_default();
+                        (parent, children)
+                    })
+                    .collect())
+            }
+        }
+    });
     
     quote! {
-        /// 初始化表结构
-        pub async fn init_table(pool: &sqlx::Pool<sqlx::Sqlite>) -> Result<(), sqlx::Error> {
-            let sql = #create_table_sql;
-            sqlx::query(sql).execute(pool).await?;
-            Ok(())
+        #(#methods)*
+        #(#eager_methods)*
+    }
+}
+
+/// 生成外键字段上 #[belongs_to] 声明的父记录加载方法
+///
+/// 与 has_many 相同，查询使用父结构体的 `SELECT_BY_ID_SQL`，错误类型为本结构体的错误类型；
+/// 可空外键为 `None` 时不访问数据库
+pub fn generate_belongs_to_methods(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    
+    let methods = parsed.fields.iter().filter_map(|f| f.belongs_to.as_ref().map(|relation| (f, relation))).map(|(f, relation)| {
+        let parent = &relation.parent;
+        let field = &f.ident;
+        let method_name = method(parsed, &relation.method.to_string());
+        let value = bind_value(f, quote!(&self.#field));
+        let null_check = if is_option(&f.ty) {
+            quote! {
+                if self.#field.is_none() {
+                    return Ok(None);
+                }
+            }
+        } else {
+            TokenStream::new()
+        };
+        let fetch_optional = run_query(parsed, &relation.method.to_string(), quote! {
+            sqlx::query_as::<_, #parent>(sql)
+                .bind(#value)
+                .fetch_optional(pool)
+        }, quote!(1usize), RowCount::Optional);
+        
+        quote! {
+            /// 加载本记录外键指向的父记录，父记录不存在时返回 `None`
+            #vis async fn #method_name(&self, pool: &#pool_ty) -> Result<Option<#parent>, #error_ty> {
+                #null_check
+                let sql = <#parent>::SELECT_BY_ID_SQL;
+                let record = #fetch_optional;
+                Ok(record)
+            }
         }
+    });
+    
+    quote! {
+        #(#methods)*
+    }
+}
 
-        /// 获取表名
-        pub fn table_name() -> &'static str {
-            #table_name
+/// 生成查看查询计划的 `explain_*` 方法，仅 SQLite 支持 `EXPLAIN QUERY PLAN`
+///
+/// 未绑定的占位符按 NULL 处理，不影响查询计划，因此统一绑定 `None`
+pub fn generate_explain_methods(parsed: &ParsedStruct) -> TokenStream {
+    if parsed.options.backend != Backend::Sqlite {
+        return TokenStream::new();
+    }
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    
+    let select_by_id = generate_select_by_id_sql(parsed);
+    let mut statements = vec![
+        ("find_all".to_string(), generate_select_sql(parsed), 0),
+        ("find_by_id".to_string(), select_by_id.sql, select_by_id.binds.len()),
+    ];
+    for f in parsed.fields.iter().filter(|f| f.is_queryable) {
+        statements.push((format!("find_by_{}", f.ident.unraw()), generate_select_by_column_sql(parsed, f), 1));
+    }
+    
+    let methods = statements.into_iter().map(|(name, select_sql, params)| {
+        let operation = format!("explain_{}", name);
+        let method_name = method(parsed, &operation);
+        let explain_sql = format!("EXPLAIN QUERY PLAN {}", select_sql);
+        let doc = format!("`{}` 的查询计划，返回每一行的 `detail`", method(parsed, &name));
+        let nulls = (0..params).map(|_| quote!(.bind(None::<i64>)));
+        let fetch = run_query(parsed, &operation, quote! {
+            sqlx::query(sql)
+                #(#nulls)*
+                .fetch_all(pool)
+        }, quote!(#params), RowCount::All);
+        let map_err = map_error(parsed, &operation);
+        
+        quote! {
+            #[doc = #doc]
+            #vis async fn #method_name(pool: &#pool_ty) -> Result<Vec<String>, #error_ty> {
+                let sql = #explain_sql;
+                let rows = #fetch;
+                let details = rows.iter()
+                    .map(|row| sqlx::Row::try_get::<String, _>(row, "detail"))
+                    .collect::<Result<Vec<_>, sqlx::Error>>()
+                    #map_err ?;
+                Ok(details)
+            }
         }
+    });
+    
+    quote! {
+        #(#methods)*
     }
 }
 
-/// 生成插入记录方法
-pub fn generate_insert_method(parsed: &ParsedStruct) -> TokenStream {
-    let insert_sql = generate_insert_sql(parsed);
-    let struct_name = format_ident!("{}", parsed.name);
+/// 生成 #[sql_crud(raw_query)] 的 `find_by_sql`
+pub fn generate_raw_query_method(parsed: &ParsedStruct) -> TokenStream {
+    let find_by_sql_fn = method(parsed, "find_by_sql");
+    if !parsed.options.raw_query {
+        return TokenStream::new();
+    }
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let row_bound = row_bound(parsed);
+    let select_where_sql = generate_select_where_sql(parsed);
+    let fetch = run_query(parsed, "find_by_sql", quote! {
+        sqlx::query_as::<_, Self>(&sql).fetch_all(pool)
+    }, quote!(0usize), RowCount::All);
     
-    let field_names: Vec<Ident> = parsed.fields.iter()
-        .map(|f| format_ident!("{}", f.name))
-        .collect();
+    quote! {
+        /// 按原始 WHERE 子句查询记录
+        ///
+        /// `where_clause` 原样拼接进SQL，不得包含未经处理的外部输入，否则会导致SQL注入
+        #vis async fn #find_by_sql_fn(pool: &#pool_ty, where_clause: &str) -> Result<Vec<Self>, #error_ty> #row_bound {
+            let sql = format!("{}{}", #select_where_sql, where_clause);
+            let records = #fetch;
+            Ok(records)
+        }
+    }
+}
+
+/// 生成按调用方的 WHERE 子句拼接 `sql` 的语句
+///
+/// `head` 以 ` WHERE ` 结尾，子句为空时去掉它，不加 WHERE；`find_where`、`count_where` 等方法共用
+fn where_clause_sql(head: &str, tail: &str) -> TokenStream {
+    let without_where = format!("{}{}", &head[..head.len() - " WHERE ".len()], tail);
+    quote! {
+        let sql = if clause.trim().is_empty() {
+            #without_where.to_string()
+        } else {
+            format!("{}{}{}", #head, clause, #tail)
+        };
+    }
+}
+
+/// 泛型结构体上读取 `Self` 需要的约束，用在已有 where 子句的末尾
+fn self_row_bound(parsed: &ParsedStruct) -> TokenStream {
+    if parsed.generics.params.is_empty() {
+        return TokenStream::new();
+    }
+    let row_ty = row_type(parsed);
+    quote!(Self: for<'r> sqlx::FromRow<'r, #row_ty> + Send + Unpin,)
+}
+
+/// 生成按 WHERE 子句和调用方绑定的参数查询的 `find_where`、`find_one_where`
+pub fn generate_find_where_methods(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let db = database_type(parsed);
+    let find_where_fn = method(parsed, "find_where");
+    let find_one_where_fn = method(parsed, "find_one_where");
+    let sql = where_clause_sql(&generate_select_where_sql(parsed), "");
+    let self_bound = self_row_bound(parsed);
+    let query_ty = quote!(sqlx::query::QueryAs<'q, #db, Self, <#db as sqlx::Database>::Arguments<'q>>);
+    let bind_count = bind_count(quote!(sqlx::query_as::<_, Self>(&sql)));
+    let fetch_all = run_query(parsed, "find_where", quote! {
+        bind(sqlx::query_as::<_, Self>(&sql)).fetch_all(pool)
+    }, bind_count.clone(), RowCount::All);
+    let fetch_optional = run_query(parsed, "find_one_where", quote! {
+        bind(sqlx::query_as::<_, Self>(&sql)).fetch_optional(pool)
+    }, bind_count.clone(), RowCount::Optional);
     
     quote! {
-        /// 插入记录
-        pub async fn insert(&self, pool: &sqlx::Pool<sqlx::Sqlite>) -> Result<(), sqlx::Error> {
-            let sql = #insert_sql;
-            sqlx::query(sql)
-                #(.bind(&self.#field_names))*
-                .execute(pool)
-                .await?;
-            Ok(())
+        /// 按 WHERE 子句查询记录，`bind` 依次绑定子句中的占位符，子句为空时查询全部记录
+        ///
+        /// `clause` 原样拼接进SQL，外部输入应通过 `bind` 绑定；`bind` 在重试或启用 tracing 时可能被多次调用，绑定的值需为拥有所有权的值
+        #vis async fn #find_where_fn<F>(pool: &#pool_ty, clause: &str, bind: F) -> Result<Vec<Self>, #error_ty>
+        where
+            F: for<'q> Fn(#query_ty) -> #query_ty,
+            #self_bound
+        {
+            #sql
+            let records = #fetch_all;
+            Ok(records)
         }
 
-        /// 插入记录并返回插入的对象
-        pub async fn insert_one(pool: &sqlx::Pool<sqlx::Sqlite>, item: &#struct_name) -> Result<(), sqlx::Error> {
-            item.insert(pool).await
+        /// 按 WHERE 子句查询第一条记录，没有匹配的记录时返回 `None`
+        #vis async fn #find_one_where_fn<F>(pool: &#pool_ty, clause: &str, bind: F) -> Result<Option<Self>, #error_ty>
+        where
+            F: for<'q> Fn(#query_ty) -> #query_ty,
+            #self_bound
+        {
+            #sql
+            let record = #fetch_optional;
+            Ok(record)
         }
     }
 }
 
-/// 生成更新记录方法
-pub fn generate_update_method(parsed: &ParsedStruct) -> TokenStream {
-    let update_sql = generate_update_sql(parsed);
+/// 生成按 WHERE 子句和调用方绑定的参数统计的 `count_where`、`exists_where`
+pub fn generate_count_where_methods(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let db = database_type(parsed);
+    let count_where_fn = method(parsed, "count_where");
+    let exists_where_fn = method(parsed, "exists_where");
+    let count_sql = where_clause_sql(&generate_count_where_sql(parsed), "");
+    let (exists_head, exists_tail) = generate_exists_where_sql(parsed);
+    let exists_sql = where_clause_sql(&exists_head, &exists_tail);
+    // EXISTS 在 Postgres 上是布尔值，在 SQLite 和 MySQL 上是整数
+    let (exists_ty, exists_value) = match parsed.options.backend {
+        Backend::Postgres => (quote!(bool), quote!(exists)),
+        Backend::Sqlite | Backend::MySql | Backend::Any => (quote!(i64), quote!(exists != 0)),
+    };
+    let scalar_ty = |ty: &TokenStream| quote!(sqlx::query::QueryScalar<'q, #db, #ty, <#db as sqlx::Database>::Arguments<'q>>);
+    let count_query_ty = scalar_ty(&quote!(i64));
+    let exists_query_ty = scalar_ty(&exists_ty);
+    let count = run_query(parsed, "count_where", quote! {
+        bind(sqlx::query_scalar::<_, i64>(&sql)).fetch_one(pool)
+    }, bind_count(quote!(sqlx::query_scalar::<_, i64>(&sql))), RowCount::One);
+    let exists = run_query(parsed, "exists_where", quote! {
+        bind(sqlx::query_scalar::<_, #exists_ty>(&sql)).fetch_one(pool)
+    }, bind_count(quote!(sqlx::query_scalar::<_, #exists_ty>(&sql))), RowCount::One);
     
-    let non_pk_fields: Vec<Ident> = parsed.fields.iter()
-        .filter(|f| !f.is_primary_key)
-        .map(|f| format_ident!("{}", f.name))
-        .collect();
+    quote! {
+        /// 按 WHERE 子句统计记录数，`bind` 依次绑定子句中的占位符，子句为空时统计全部记录
+        #vis async fn #count_where_fn<F>(pool: &#pool_ty, clause: &str, bind: F) -> Result<i64, #error_ty>
+        where
+            F: for<'q> Fn(#count_query_ty) -> #count_query_ty,
+        {
+            #count_sql
+            let count = #count;
+            Ok(count)
+        }
+
+        /// 按 WHERE 子句判断是否存在匹配的记录，子句为空时判断表是否非空
+        #vis async fn #exists_where_fn<F>(pool: &#pool_ty, clause: &str, bind: F) -> Result<bool, #error_ty>
+        where
+            F: for<'q> Fn(#exists_query_ty) -> #exists_query_ty,
+        {
+            #exists_sql
+            let exists = #exists;
+            Ok(#exists_value)
+        }
+    }
+}
+
+/// 为 #[queryable]、#[searchable]、#[json_indexed] 字段生成按列查询方法
+pub fn generate_find_by_methods(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let row_bound = row_bound(parsed);
+    
+    let methods = parsed.fields.iter()
+        .filter(|f| f.is_queryable)
+        .map(|f| {
+            let select_sql = generate_select_by_column_sql(parsed, f);
+            let select_first_sql = generate_select_first_by_column_sql(parsed, f);
+            let find_by = format_ident!("{}find_by_{}", parsed.options.prefix, f.ident);
+            let find_first_by = format_ident!("{}find_first_by_{}", parsed.options.prefix, f.ident);
+            let find_by_in = format_ident!("{}find_by_{}_in", parsed.options.prefix, f.ident);
+            let (in_prefix, in_suffix) = generate_select_by_column_in_sql(parsed, f);
+            let placeholder = placeholder_fn(parsed);
+            let ty = &f.ty;
+            let value = bind_value(f, quote!(value));
+            let fetch_all = run_query(parsed, &format!("find_by_{}", f.ident.unraw()), quote! {
+                sqlx::query_as::<_, Self>(sql)
+                    .bind(#value)
+                    .fetch_all(pool)
+            }, quote!(1usize), RowCount::All);
+            let fetch_first = run_query(parsed, &format!("find_first_by_{}", f.ident.unraw()), quote! {
+                sqlx::query_as::<_, Self>(sql)
+                    .bind(#value)
+                    .fetch_optional(pool)
+            }, quote!(1usize), RowCount::Optional);
+            let max_params = max_params(parsed);
+            let fetch_in = run_query(parsed, &format!("find_by_{}_in", f.ident.unraw()), quote! {{
+                let mut query = sqlx::query_as::<_, Self>(&sql);
+                for value in chunk {
+                    query = query.bind(#value);
+                }
+                query.fetch_all(pool)
+            }}, quote!(chunk.len()), RowCount::All);
+            
+            quote! {
+                /// 按列查询所有匹配的记录
+                #vis async fn #find_by(pool: &#pool_ty, value: &#ty) -> Result<Vec<Self>, #error_ty> #row_bound {
+                    let sql = #select_sql;
+                    let records = #fetch_all;
+                    Ok(records)
+                }
+
+                /// 按列查询第一条匹配的记录
+                #vis async fn #find_first_by(pool: &#pool_ty, value: &#ty) -> Result<Option<Self>, #error_ty> #row_bound {
+                    let sql = #select_first_sql;
+                    let record = #fetch_first;
+                    Ok(record)
+                }
+
+                /// 按列查询值在给定列表中的所有记录，列表为空时直接返回空结果，值超过单条语句的参数上限时分批查询
+                #vis async fn #find_by_in(pool: &#pool_ty, values: &[#ty]) -> Result<Vec<Self>, #error_ty> #row_bound {
+                    if values.is_empty() {
+                        return Ok(Vec::new());
+                    }
+                    let mut records = Vec::new();
+                    for chunk in values.chunks(#max_params) {
+                        let placeholders = (0..chunk.len())
+                            .map(#placeholder)
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let sql = format!("{}{}{}", #in_prefix, placeholders, #in_suffix);
+                        records.extend(#fetch_in);
+                    }
+                    Ok(records)
+                }
+            }
+        });
+    
+    let search_methods = parsed.fields.iter()
+        .filter(|f| f.is_searchable)
+        .map(|f| {
+            let search_sql = generate_search_by_column_sql(parsed, f);
+            let search = format_ident!("{}search_{}", parsed.options.prefix, f.ident);
+            let fetch_all = run_query(parsed, &format!("search_{}", f.ident.unraw()), quote! {
+                sqlx::query_as::<_, Self>(sql)
+                    .bind(pattern)
+                    .fetch_all(pool)
+            }, quote!(1usize), RowCount::All);
+            
+            quote! {
+                /// 按 LIKE 模式查询所有匹配的记录，`pattern` 作为参数绑定，通配符 `%`、`_` 由调用方添加
+                ///
+                /// SQLite 的 LIKE 只对 ASCII 字母不区分大小写，MySQL 取决于列的排序规则，Postgres 使用 ILIKE
+                #vis async fn #search(pool: &#pool_ty, pattern: &str) -> Result<Vec<Self>, #error_ty> #row_bound {
+                    let sql = #search_sql;
+                    let records = #fetch_all;
+                    Ok(records)
+                }
+            }
+        });
+    
+    let json_methods = parsed.fields.iter()
+        .filter(|f| f.is_json_indexed)
+        .map(|f| {
+            let select_sql = generate_select_by_json_path_sql(parsed, f);
+            let find_by_json_path = format_ident!("{}find_by_{}_json_path", parsed.options.prefix, f.ident);
+            let fetch_all = run_query(parsed, &format!("find_by_{}_json_path", f.ident.unraw()), quote! {
+                sqlx::query_as::<_, Self>(sql)
+                    .bind(path)
+                    .bind(value)
+                    .fetch_all(pool)
+            }, quote!(2usize), RowCount::All);
+            
+            quote! {
+                /// 查询 JSON 列在路径 `path`（如 `$.a.b`）处的值按文本等于 `value` 的所有记录
+                #vis async fn #find_by_json_path(pool: &#pool_ty, path: &str, value: &str) -> Result<Vec<Self>, #error_ty> #row_bound {
+                    let sql = #select_sql;
+                    let records = #fetch_all;
+                    Ok(records)
+                }
+            }
+        });
+    
+    quote! {
+        #(#methods)*
+        #(#search_methods)*
+        #(#json_methods)*
+    }
+}
+
+/// 生成统计记录数方法，以及 #[queryable] 字段的按列统计方法
+pub fn generate_count_methods(parsed: &ParsedStruct) -> TokenStream {
+    let count_fn = method(parsed, "count");
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let count = run_query(parsed, "count", quote! {
+        sqlx::query_scalar::<_, i64>(sql).fetch_one(pool)
+    }, quote!(0usize), RowCount::One);
+    
+    let count_by_methods = parsed.fields.iter()
+        .filter(|f| f.is_queryable)
+        .map(|f| {
+            let count_by_sql = generate_count_by_sql(parsed, f);
+            let method_name = format_ident!("{}count_by_{}", parsed.options.prefix, f.ident);
+            let ty = &f.ty;
+            let value = bind_value(f, quote!(value));
+            let fetch_count = run_query(parsed, &format!("count_by_{}", f.ident.unraw()), quote! {
+                sqlx::query_scalar::<_, i64>(sql)
+                    .bind(#value)
+                    .fetch_one(pool)
+            }, quote!(1usize), RowCount::One);
+            
+            quote! {
+                /// 按列统计记录数
+                #vis async fn #method_name(pool: &#pool_ty, value: &#ty) -> Result<i64, #error_ty> {
+                    let sql = #count_by_sql;
+                    let count = #fetch_count;
+                    Ok(count)
+                }
+            }
+        });
+    let runtime_tables = RuntimeTable::enabled(parsed).into_iter().map(|variant| {
+        let count_fn = variant.method(parsed, "count");
+        let param = variant.param();
+        let doc = format!("统计{}中的记录数", variant.target());
+        let sql = variant.sql(parsed, "count", generate_count_sql(&variant.template(parsed)));
+        quote! {
+            #[doc = #doc]
+            #vis async fn #count_fn(pool: &#pool_ty, #param: &str) -> Result<i64, #error_ty> {
+                #sql
+                let count = #count;
+                Ok(count)
+            }
+        }
+    });
+    
+    quote! {
+        /// 统计记录数
+        #vis async fn #count_fn(pool: &#pool_ty) -> Result<i64, #error_ty> {
+            let sql = Self::COUNT_SQL;
+            let count = #count;
+            Ok(count)
+        }
+
+        #(#runtime_tables)*
+
+        #(#count_by_methods)*
+    }
+}
+
+/// 生成同时返回一页记录和总数的 `find_page`，两条查询在同一事务中执行
+pub fn generate_find_page_method(parsed: &ParsedStruct) -> TokenStream {
+    let find_page_fn = method(parsed, "find_page");
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let row_bound = row_bound(parsed);
+    let select_page_sql = generate_select_page_sql(parsed);
+    let map_err = map_error(parsed, "find_page");
+    let (begin_sql, commit_sql) = transaction_sql(parsed);
+    let fetch_page = run_query(parsed, "find_page", quote! {
+        sqlx::query_as::<_, Self>(sql)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&mut *tx)
+    }, quote!(2usize), RowCount::All);
+    let count = run_query(parsed, "find_page", quote! {
+        sqlx::query_scalar::<_, i64>(sql).fetch_one(&mut *tx)
+    }, quote!(0usize), RowCount::One);
+    
+    quote! {
+        /// 按主键顺序查询一页记录，同时返回记录总数；两条查询在同一事务中执行
+        #vis async fn #find_page_fn(pool: &#pool_ty, limit: i64, offset: i64) -> Result<(Vec<Self>, i64), #error_ty> #row_bound {
+            #begin_sql
+            let mut tx = pool.begin().await #map_err ?;
+            let sql = #select_page_sql;
+            let records = #fetch_page;
+            let sql = Self::COUNT_SQL;
+            let total = #count;
+            #commit_sql
+            tx.commit().await #map_err ?;
+            Ok((records, total))
+        }
+    }
+}
+
+/// 生成 #[sql_crud(blocking)] 的同步包装方法，在调用方传入的 tokio 运行时上阻塞等待对应的异步方法
+///
+/// 取 `Runtime` 而不是 `Handle`：current_thread 运行时只有 `Runtime::block_on` 才会驱动 IO 和定时器
+pub fn generate_blocking_methods(parsed: &ParsedStruct) -> TokenStream {
+    if !parsed.options.blocking {
+        return TokenStream::new();
+    }
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let row_bound = row_bound(parsed);
+    let receiver = write_receiver(parsed);
+    let skip = &parsed.options.skip;
     
-    let pk_field = format_ident!("{}", parsed.fields.iter()
+    let pk = parsed.fields.iter()
         .find(|f| f.is_primary_key)
-        .expect("No primary key defined")
-        .name);
+        .expect("No primary key defined");
+    let pk_type = &pk.ty;
+    let pk_param = &pk.ident;
+    
+    let block_on = |name: &str, params: TokenStream, output: TokenStream, bound: &TokenStream, call: TokenStream| {
+        let async_fn = method(parsed, name);
+        let blocking_fn = method(parsed, &format!("{}_blocking", name));
+        let doc = format!("阻塞版本的 `{}`，在 `runtime` 上执行，不能在异步上下文中调用", async_fn);
+        quote! {
+            #[doc = #doc]
+            #vis fn #blocking_fn(#params, runtime: &tokio::runtime::Runtime) -> Result<#output, #error_ty> #bound {
+                runtime.block_on(#call)
+            }
+        }
+    };
+    let no_bound = TokenStream::new();
+    
+    let mut methods = Vec::new();
+    if !skip.init_table {
+        let init_table_fn = method(parsed, "init_table");
+        methods.push(block_on("init_table", quote!(pool: &#pool_ty), quote!(()), &no_bound, quote!(Self::#init_table_fn(pool))));
+    }
+    if !skip.insert {
+        let insert_fn = method(parsed, "insert");
+        let insert_receiver = insert_receiver(parsed);
+        let output = insert_output(parsed);
+        methods.push(block_on("insert", quote!(#insert_receiver, pool: &#pool_ty), output, &no_bound, quote!(self.#insert_fn(pool))));
+    }
+    if !skip.update {
+        let update_fn = method(parsed, "update");
+        methods.push(block_on("update", quote!(#receiver, pool: &#pool_ty), quote!(()), &no_bound, quote!(self.#update_fn(pool))));
+    }
+    if !skip.delete {
+        let delete_fn = method(parsed, "delete");
+        let delete_by_id_fn = method(parsed, "delete_by_id");
+        methods.push(block_on("delete", quote!(&self, pool: &#pool_ty), quote!(()), &no_bound, quote!(self.#delete_fn(pool))));
+        methods.push(block_on("delete_by_id", quote!(pool: &#pool_ty, #pk_param: &#pk_type), quote!(()), &no_bound, quote!(Self::#delete_by_id_fn(pool, #pk_param))));
+    }
+    if !skip.find {
+        let find_all_fn = method(parsed, "find_all");
+        let find_by_id_fn = method(parsed, "find_by_id");
+        let count_fn = method(parsed, "count");
+        methods.push(block_on("find_all", quote!(pool: &#pool_ty), quote!(Vec<Self>), &row_bound, quote!(Self::#find_all_fn(pool))));
+        methods.push(block_on("find_by_id", quote!(pool: &#pool_ty, #pk_param: &#pk_type), quote!(Option<Self>), &row_bound, quote!(Self::#find_by_id_fn(pool, #pk_param))));
+        methods.push(block_on("count", quote!(pool: &#pool_ty), quote!(i64), &no_bound, quote!(Self::#count_fn(pool))));
+    }
     
     quote! {
-        /// 更新记录
-        pub async fn update(&self, pool: &sqlx::Pool<sqlx::Sqlite>) -> Result<(), sqlx::Error> {
-            let sql = #update_sql;
-            sqlx::query(sql)
-                #(.bind(&self.#non_pk_fields))*
-                .bind(&self.#pk_field)
-                .execute(pool)
-                .await?;
-            Ok(())
+        #(#methods)*
+    }
+}
+
+/// 生成 #[sql_crud(hooks)] 使用的 `<Name>Hooks` trait，所有钩子默认什么都不做
+pub fn generate_hooks_trait(parsed: &ParsedStruct) -> TokenStream {
+    if !parsed.options.hooks {
+        return TokenStream::new();
+    }
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let hooks_trait = hooks_trait(parsed);
+    
+    quote! {
+        /// 写操作前后调用的钩子，`before_*` 返回错误时不会执行SQL
+        #vis trait #hooks_trait {
+            /// 插入前调用，可修改记录
+            fn before_insert(&mut self) -> Result<(), #error_ty> {
+                Ok(())
+            }
+
+            /// 插入成功后调用
+            fn after_insert(&self, pool: &#pool_ty) -> impl std::future::Future<Output = Result<(), #error_ty>> + Send {
+                let _ = pool;
+                async { Ok(()) }
+            }
+
+            /// 更新前调用，可修改记录
+            fn before_update(&mut self) -> Result<(), #error_ty> {
+                Ok(())
+            }
+
+            /// 更新成功后调用
+            fn after_update(&self, pool: &#pool_ty) -> impl std::future::Future<Output = Result<(), #error_ty>> + Send {
+                let _ = pool;
+                async { Ok(()) }
+            }
+
+            /// 删除前调用
+            fn before_delete(&self) -> Result<(), #error_ty> {
+                Ok(())
+            }
+
+            /// 删除成功后调用
+            fn after_delete(&self, pool: &#pool_ty) -> impl std::future::Future<Output = Result<(), #error_ty>> + Send {
+                let _ = pool;
+                async { Ok(()) }
+            }
         }
     }
 }
 
-/// 生成删除记录方法
-pub fn generate_delete_method(parsed: &ParsedStruct) -> TokenStream {
-    let delete_sql = generate_delete_sql(parsed);
+/// 生成 #[sql_crud(error)] 使用的错误类型
+pub fn generate_error_type(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let error = match &parsed.options.error {
+        Some(error) => error,
+        None => return TokenStream::new(),
+    };
+    
+    quote! {
+        /// 生成方法返回的错误，带有出错的操作、表名和SQL
+        #[derive(Debug)]
+        #vis enum #error {
+            /// 执行查询失败
+            Query {
+                operation: &'static str,
+                table: &'static str,
+                sql: String,
+                source: sqlx::Error,
+            },
+            /// 按主键更新时没有匹配的记录
+            NotFound {
+                operation: &'static str,
+                table: &'static str,
+            },
+            /// 字段校验失败，包含失败的字段名和错误信息
+            Validation {
+                operation: &'static str,
+                table: &'static str,
+                errors: Vec<(&'static str, String)>,
+            },
+        }
+
+        impl #error {
+            /// 出错的操作名
+            #vis fn operation(&self) -> &'static str {
+                match self {
+                    #error::Query { operation, .. }
+                    | #error::NotFound { operation, .. }
+                    | #error::Validation { operation, .. } => operation,
+                }
+            }
+
+            /// 出错的表名
+            #vis fn table(&self) -> &'static str {
+                match self {
+                    #error::Query { table, .. }
+                    | #error::NotFound { table, .. }
+                    | #error::Validation { table, .. } => table,
+                }
+            }
+        }
+
+        impl std::fmt::Display for #error {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    #error::Query { operation, table, source, .. } => {
+                        write!(f, "{} on table `{}` failed: {}", operation, table, source)
+                    }
+                    #error::NotFound { operation, table } => {
+                        write!(f, "{} on table `{}` matched no rows", operation, table)
+                    }
+                    #error::Validation { operation, table, errors } => {
+                        write!(f, "{} on table `{}` failed validation: ", operation, table)?;
+                        for (i, (field, message)) in errors.iter().enumerate() {
+                            if i > 0 {
+                                write!(f, "; ")?;
+                            }
+                            write!(f, "{}: {}", field, message)?;
+                        }
+                        Ok(())
+                    }
+                }
+            }
+        }
+
+        impl std::error::Error for #error {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                match self {
+                    #error::Query { source, .. } => Some(source),
+                    #error::NotFound { .. } | #error::Validation { .. } => None,
+                }
+            }
+        }
+    }
+}
+
+/// 生成 `sqlx::FromRow` 实现，按列名读取每个字段
+pub fn generate_from_row_impl(parsed: &ParsedStruct) -> TokenStream {
     let struct_name = format_ident!("{}", parsed.name);
+    let row_ty = row_type(parsed);
     
-    let pk_field = format_ident!("{}", parsed.fields.iter()
-        .find(|f| f.is_primary_key)
-        .expect("No primary key defined")
-        .name);
+    let mut generics = bounded_generics(parsed);
+    generics.params.insert(0, parse_quote!('r));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = parsed.generics.split_for_impl();
     
-    let pk_type = &parsed.fields.iter()
-        .find(|f| f.is_primary_key)
-        .expect("No primary key defined")
-        .ty;
+    // #[flatten] 展开的相邻列合并为一个嵌套结构体的值
+    let mut field_names: Vec<Ident> = Vec::new();
+    let mut values: Vec<TokenStream> = Vec::new();
+    let mut members: Vec<TokenStream> = Vec::new();
+    for (i, f) in parsed.fields.iter().enumerate() {
+        let value = read_column(f);
+        let flattened = match &f.flattened {
+            Some(flattened) => flattened,
+            None => {
+                field_names.push(f.ident.clone());
+                values.push(value);
+                continue;
+            }
+        };
+        let member = &flattened.member;
+        members.push(quote!(#member: #value));
+        let group_ends = parsed.fields.get(i + 1)
+            .and_then(|next| next.flattened.as_ref())
+            .is_none_or(|next| next.field != flattened.field);
+        if group_ends {
+            let ty = &flattened.ty;
+            field_names.push(flattened.field.clone());
+            values.push(quote!(#ty { #(#members,)* }));
+            members.clear();
+        }
+    }
+    let skipped_fields = &parsed.skipped_fields;
     
     quote! {
-        /// 删除记录
-        pub async fn delete(&self, pool: &sqlx::Pool<sqlx::Sqlite>) -> Result<(), sqlx::Error> {
-            let sql = #delete_sql;
-            sqlx::query(sql)
-                .bind(&self.#pk_field)
-                .execute(pool)
-                .await?;
-            Ok(())
+        impl #impl_generics sqlx::FromRow<'r, #row_ty> for #struct_name #ty_generics #where_clause {
+            fn from_row(row: &'r #row_ty) -> Result<Self, sqlx::Error> {
+                use sqlx::Row;
+                Ok(Self {
+                    #(#field_names: #values,)*
+                    #(#skipped_fields: ::std::default::Default::default(),)*
+                })
+            }
         }
+    }
+}
 
-        /// 按ID删除记录
-        pub async fn delete_by_id(pool: &sqlx::Pool<sqlx::Sqlite>, id: &#pk_type) -> Result<(), sqlx::Error> {
-            let sql = #delete_sql;
-            sqlx::query(sql)
-                .bind(id)
-                .execute(pool)
-                .await?;
-            Ok(())
+/// `from_row` 中读取一列的表达式
+///
+/// #[duration(...)] 列存储的负数、#[ip_as_text] 列中无法解析的文本、`char` 列中不是单个字符的文本作为解码错误返回
+fn read_column(field: &ParsedField) -> TokenStream {
+    let column = &field.name;
+    let (stored, decode) = match field.codec {
+        Some(ColumnCodec::Duration(unit)) => {
+            let from_int = format_ident!("{}", unit.decoder());
+            (quote!(i64), quote! {
+                |value: i64| u64::try_from(value)
+                    .map(::std::time::Duration::#from_int)
+                    .map_err(|_| sqlx::Error::ColumnDecode {
+                        index: #column.to_string(),
+                        source: format!("negative duration `{}`", value).into(),
+                    })
+            })
+        }
+        Some(ColumnCodec::IpText) => {
+            let ty = option_inner(&field.ty);
+            (quote!(String), quote! {
+                |value: String| value.parse::<#ty>()
+                    .map_err(|err| sqlx::Error::ColumnDecode {
+                        index: #column.to_string(),
+                        source: Box::new(err),
+                    })
+            })
         }
+        Some(ColumnCodec::Char) => (quote!(String), quote! {
+            |value: String| {
+                let mut chars = value.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(sqlx::Error::ColumnDecode {
+                        index: #column.to_string(),
+                        source: format!("expected a single character, found `{}`", value).into(),
+                    }),
+                }
+            }
+        }),
+        None => return quote!(row.try_get(#column)?),
+    };
+    if is_option(&field.ty) {
+        quote!(row.try_get::<Option<#stored>, _>(#column)?.map(#decode).transpose()?)
+    } else {
+        quote!((#decode)(row.try_get::<#stored, _>(#column)?)?)
     }
 }
 
-/// 生成查询记录方法
-pub fn generate_select_methods(parsed: &ParsedStruct) -> TokenStream {
-    let select_sql = generate_select_sql(parsed);
-    let select_by_id_sql = generate_select_by_id_sql(parsed);
+/// 生成持有连接池的 `<Name>Repo` 仓储类型，方法委托给结构体上生成的静态方法
+pub fn generate_repository(parsed: &ParsedStruct) -> TokenStream {
+    let init_table_fn = method(parsed, "init_table");
+    let insert_fn = method(parsed, "insert");
+    let update_fn = method(parsed, "update");
+    let delete_fn = method(parsed, "delete");
+    let delete_by_id_fn = method(parsed, "delete_by_id");
+    let find_all_fn = method(parsed, "find_all");
+    let find_by_id_fn = method(parsed, "find_by_id");
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
     let struct_name = format_ident!("{}", parsed.name);
+    let repo_name = format_ident!("{}Repo", parsed.name);
+    let item_ty = if parsed.options.hooks { quote!(&mut #struct_name) } else { quote!(&#struct_name) };
+    let insert_item_ty = if insert_needs_mut(parsed) { quote!(&mut #struct_name) } else { quote!(&#struct_name) };
+    let insert_output = insert_output(parsed);
+    let skip = &parsed.options.skip;
     
-    let pk_field = format_ident!("{}", parsed.fields.iter()
+    let pk = parsed.fields.iter()
         .find(|f| f.is_primary_key)
-        .expect("No primary key defined")
-        .name);
+        .expect("No primary key defined");
+    let pk_type = &pk.ty;
+    let pk_param = &pk.ident;
+    
+    let init_table = if skip.init_table { TokenStream::new() } else {
+        quote! {
+            /// 初始化表结构
+            #vis async fn init_table(&self) -> Result<(), #error_ty> {
+                #struct_name::#init_table_fn(&self.pool).await
+            }
+        }
+    };
+    
+    let init_table_with = if !parsed.options.init_options { TokenStream::new() } else {
+        let init_table_with_fn = method(parsed, "init_table_with");
+        let options_name = format_ident!("{}InitOptions", parsed.name);
+        quote! {
+            /// 执行 PRAGMA 后在同一连接上的事务中初始化表结构
+            #vis async fn init_table_with(&self, options: #options_name) -> Result<(), #error_ty> {
+                #struct_name::#init_table_with_fn(&self.pool, options).await
+            }
+        }
+    };
+    
+    let insert = if skip.insert { TokenStream::new() } else {
+        quote! {
+            /// 插入记录
+            #vis async fn insert(&self, item: #insert_item_ty) -> Result<#insert_output, #error_ty> {
+                item.#insert_fn(&self.pool).await
+            }
+        }
+    };
+    
+    let update = if skip.update { TokenStream::new() } else {
+        quote! {
+            /// 更新记录
+            #vis async fn update(&self, item: #item_ty) -> Result<(), #error_ty> {
+                item.#update_fn(&self.pool).await
+            }
+        }
+    };
+    
+    let delete = if skip.delete { TokenStream::new() } else {
+        quote! {
+            /// 删除记录
+            #vis async fn delete(&self, item: &#struct_name) -> Result<(), #error_ty> {
+                item.#delete_fn(&self.pool).await
+            }
+
+            /// 按ID删除记录
+            #vis async fn delete_by_id(&self, #pk_param: &#pk_type) -> Result<(), #error_ty> {
+                #struct_name::#delete_by_id_fn(&self.pool, #pk_param).await
+            }
+        }
+    };
+    
+    let find = if skip.find { TokenStream::new() } else {
+        quote! {
+            /// 查询所有记录
+            #vis async fn find_all(&self) -> Result<Vec<#struct_name>, #error_ty> {
+                #struct_name::#find_all_fn(&self.pool).await
+            }
+
+            /// 按ID查询记录
+            #vis async fn find_by_id(&self, #pk_param: &#pk_type) -> Result<Option<#struct_name>, #error_ty> {
+                #struct_name::#find_by_id_fn(&self.pool, #pk_param).await
+            }
+        }
+    };
+    
+    quote! {
+        /// 持有连接池的仓储类型，克隆开销与克隆连接池相同
+        #[derive(Clone)]
+        #vis struct #repo_name {
+            pool: #pool_ty,
+        }
+
+        impl #repo_name {
+            /// 使用连接池创建仓储
+            #vis fn new(pool: #pool_ty) -> Self {
+                Self { pool }
+            }
+
+            /// 获取内部连接池
+            #vis fn pool(&self) -> &#pool_ty {
+                &self.pool
+            }
+
+            #init_table
+            #init_table_with
+            #insert
+            #update
+            #delete
+            #find
+        }
+    }
+}
+
+
+/// 校验语句的占位符数量与其绑定列表的长度一致
+///
+/// 生成方法按绑定列表逐个 `.bind`，不一致说明语句的生成逻辑有误，在展开时报错而不是留到运行时
+fn check_bind_counts(parsed: &ParsedStruct) -> syn::Result<()> {
+    let backend = parsed.options.backend;
+    let statements = [
+        ("insert", generate_insert_sql(parsed)),
+        ("update", generate_update_sql(parsed)),
+        ("delete", generate_delete_sql(parsed)),
+        ("select_by_id", generate_select_by_id_sql(parsed)),
+    ];
+    for (operation, Statement { sql, binds }) in statements {
+        let placeholders = count_placeholders(backend, &sql);
+        let binds = binds.len();
+        if placeholders != binds {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                format!("internal error: generated {} statement has {} placeholder(s) but binds {} value(s): {}", operation, placeholders, binds, sql),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 生成 #[sql_crud(self_check)] 的 `#[cfg(test)]` 模块，在运行时再次校验公开常量中的占位符数量与绑定数量一致
+pub fn generate_self_check(parsed: &ParsedStruct) -> TokenStream {
+    if !parsed.options.self_check {
+        return TokenStream::new();
+    }
+    let struct_name = format_ident!("{}", parsed.name);
+    let module = format_ident!("__sql_crud_self_check_{}", crate::utils::camel_to_snake(&parsed.name));
+    let skip = &parsed.options.skip;
+    let mut statements = Vec::new();
+    if !skip.insert {
+        statements.push(("INSERT_SQL", generate_insert_sql(parsed).binds.len()));
+    }
+    if !skip.update {
+        statements.push(("UPDATE_SQL", generate_update_sql(parsed).binds.len()));
+        if parsed.options.update_returning {
+            statements.push(("UPDATE_RETURNING_SQL", generate_update_sql(parsed).binds.len()));
+        }
+    }
+    if !skip.delete {
+        statements.push(("DELETE_SQL", generate_delete_sql(parsed).binds.len()));
+    }
+    if !skip.find {
+        statements.push(("SELECT_BY_ID_SQL", generate_select_by_id_sql(parsed).binds.len()));
+    }
+    let names = statements.iter().map(|(name, _)| *name);
+    let consts = statements.iter().map(|(name, _)| format_ident!("{}", name));
+    let binds = statements.iter().map(|(_, binds)| binds);
+    let dollar = matches!(parsed.options.backend, Backend::Sqlite | Backend::Postgres);
+    
+    quote! {
+        #[cfg(test)]
+        mod #module {
+            /// 统计引号以外的占位符，`$N` 按不同的序号计数，`?` 按出现次数计数
+            fn count_placeholders(sql: &str) -> usize {
+                let mut indices = ::std::collections::HashSet::new();
+                let mut count = 0;
+                let mut quote = None;
+                let mut chars = sql.chars().peekable();
+                while let Some(c) = chars.next() {
+                    match quote {
+                        Some(q) if c == q => quote = None,
+                        Some(_) => {}
+                        None if matches!(c, '\'' | '"' | '`') => quote = Some(c),
+                        None if #dollar && c == '$' => {
+                            let mut index = String::new();
+                            while let Some(d) = chars.next_if(|d| d.is_ascii_digit()) {
+                                index.push(d);
+                            }
+                            if !index.is_empty() {
+                                indices.insert(index);
+                            }
+                        }
+                        None if !#dollar && c == '?' => count += 1,
+                        None => {}
+                    }
+                }
+                count + indices.len()
+            }
+
+            #[test]
+            fn placeholders_match_binds() {
+                #(
+                    let sql = super::#struct_name::#consts;
+                    assert_eq!(count_placeholders(sql), #binds, "{} should bind {} value(s): {}", #names, #binds, sql);
+                )*
+            }
+        }
+    }
+}
+
+/// 生成 `<Name>Builder`：逐个设置非自增字段后构造记录，或直接插入
+///
+/// 未设置的 `Option<T>` 字段为 `None`，#[uuid_generate] 字段为空值并在插入时生成，
+/// 自增字段和跳过的字段为 `Default::default()`，其余字段必须设置
+pub fn generate_builder(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let struct_name = format_ident!("{}", parsed.name);
+    let builder_name = format_ident!("{}Builder", parsed.name);
+    let builder_fn = method(parsed, "builder");
+    let insert_fn = method(parsed, "insert");
+    let error_ty = error_type(parsed);
+    let (exec_generics, exec_ty, exec_bound) = executor_param(parsed);
+    let generics = &parsed.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let bounded = bounded_generics(parsed);
+    let (bounded_impl_generics, _, bounded_where_clause) = bounded.split_for_impl();
+    
+    let fields: Vec<_> = parsed.insertable_fields().collect();
+    let field_names: Vec<&Ident> = fields.iter().map(|f| &f.ident).collect();
+    let field_types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+    let setter_docs: Vec<String> = fields.iter().map(|f| format!("设置 `{}`", f.ident.unraw())).collect();
+    let (optional, required): (Vec<&ParsedField>, Vec<&ParsedField>) = fields.iter().copied().partition(|f| is_option(&f.ty) || f.is_uuid_generate);
+    let optional: Vec<&Ident> = optional.iter().map(|f| &f.ident).collect();
+    let required: Vec<&Ident> = required.iter().map(|f| &f.ident).collect();
+    let required_names: Vec<String> = required.iter().map(|ident| ident.unraw().to_string()).collect();
+    let defaulted: Vec<&Ident> = parsed.fields.iter()
+        .filter(|f| f.is_auto_increment)
+        .map(|f| &f.ident)
+        .chain(&parsed.skipped_fields)
+        .collect();
+    
+    let missing_error = match &parsed.options.error {
+        Some(error) => {
+            let table = &parsed.table_name;
+            quote! {
+                #error::Validation {
+                    operation: "insert",
+                    table: #table,
+                    errors: missing.into_iter().map(|field| (field, "value not set".to_string())).collect(),
+                }
+            }
+        }
+        None => quote!(sqlx::Error::ColumnNotFound(missing.join(", "))),
+    };
+    // 自增主键插入后写回构造出的记录
+    let (item, store_id) = match parsed.auto_increment_field() {
+        Some(key) => {
+            let ident = &key.ident;
+            (quote!(mut item), quote!(item.#ident =))
+        }
+        None if insert_needs_mut(parsed) => (quote!(mut item), TokenStream::new()),
+        None => (quote!(item), TokenStream::new()),
+    };
+    let insert = if parsed.options.skip.insert { TokenStream::new() } else {
+        quote! {
+            impl #bounded_impl_generics #builder_name #ty_generics #bounded_where_clause {
+                /// 构造记录并插入，返回插入的记录；有未设置的字段时不执行SQL
+                #vis async fn insert #exec_generics (self, executor: #exec_ty) -> Result<#struct_name #ty_generics, #error_ty> #exec_bound {
+                    let #item = self.build().map_err(|missing| #missing_error)?;
+                    #store_id item.#insert_fn(executor).await?;
+                    Ok(item)
+                }
+            }
+        }
+    };
+    
+    quote! {
+        /// 逐字段构造记录的构建器
+        #vis struct #builder_name #generics #where_clause {
+            #( #field_names: Option<#field_types>, )*
+            _marker: std::marker::PhantomData<fn() -> #struct_name #ty_generics>,
+        }
+
+        impl #impl_generics Default for #builder_name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #( #field_names: None, )*
+                    _marker: std::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #(
+                #[doc = #setter_docs]
+                #vis fn #field_names(mut self, value: #field_types) -> Self {
+                    self.#field_names = Some(value);
+                    self
+                }
+            )*
+
+            /// 构造记录，有未设置的必填字段时返回这些字段名
+            #vis fn build(self) -> Result<#struct_name #ty_generics, Vec<&'static str>> {
+                let mut missing = Vec::new();
+                #( if self.#required.is_none() { missing.push(#required_names); } )*
+                if !missing.is_empty() {
+                    return Err(missing);
+                }
+                Ok(#struct_name {
+                    #( #required: self.#required.unwrap(), )*
+                    #( #optional: self.#optional.unwrap_or_default(), )*
+                    #( #defaulted: Default::default(), )*
+                })
+            }
+        }
+
+        #insert
+
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// 创建逐字段构造记录的构建器
+            #vis fn #builder_fn() -> #builder_name #ty_generics {
+                Default::default()
+            }
+        }
+    }
+}
+
+/// 生成只含可插入字段的 `New<Name>`，插入后按主键读回数据库中的完整记录
+pub fn generate_insert_model(parsed: &ParsedStruct) -> TokenStream {
+    let derives = match &parsed.options.insert_model {
+        Some(derives) => derives,
+        None => return TokenStream::new(),
+    };
+    let vis = parsed.options.vis();
+    let struct_name = format_ident!("{}", parsed.name);
+    let model_name = format_ident!("New{}", parsed.name);
+    let insert_fn = method(parsed, "insert");
+    let find_by_id_fn = method(parsed, "find_by_id");
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
     
-    let pk_type = &parsed.fields.iter()
+    let field_names: Vec<&Ident> = parsed.insertable_fields().map(|f| &f.ident).collect();
+    let field_types = parsed.insertable_fields().map(|f| &f.ty);
+    let defaulted: Vec<&Ident> = parsed.fields.iter()
+        .filter(|f| f.is_auto_increment)
+        .map(|f| &f.ident)
+        .chain(&parsed.skipped_fields)
+        .collect();
+    let pk = parsed.fields.iter()
         .find(|f| f.is_primary_key)
-        .expect("No primary key defined")
-        .ty;
+        .expect("No primary key defined");
+    let pk_field = &pk.ident;
+    let (record, store_id) = match parsed.auto_increment_field() {
+        Some(key) => {
+            let ident = &key.ident;
+            (quote!(mut record), quote!(record.#ident =))
+        }
+        None if insert_needs_mut(parsed) => (quote!(mut record), TokenStream::new()),
+        None => (quote!(record), TokenStream::new()),
+    };
+    // 插入后读不到记录（如被并发删除）时与 update 一样报告为 NotFound，未启用 error 时为 sqlx::Error::RowNotFound
+    let not_found = match &parsed.options.error {
+        Some(error) => {
+            let table = &parsed.table_name;
+            quote!(#error::NotFound { operation: "insert", table: #table })
+        }
+        None => quote!(sqlx::Error::RowNotFound),
+    };
     
     quote! {
-        /// 查询所有记录
-        pub async fn find_all(pool: &sqlx::Pool<sqlx::Sqlite>) -> Result<Vec<#struct_name>, sqlx::Error> {
-            let sql = #select_sql;
-            let records = sqlx::query_as::<_, #struct_name>(sql)
-                .fetch_all(pool)
-                .await?;
-            Ok(records)
+        /// 插入新记录时使用的结构体，不含由数据库赋值的自增主键
+        #[derive(#(#derives),*)]
+        #vis struct #model_name {
+            #( #vis #field_names: #field_types, )*
         }
 
-        /// 按ID查询记录
-        pub async fn find_by_id(pool: &sqlx::Pool<sqlx::Sqlite>, id: &#pk_type) -> Result<Option<#struct_name>, sqlx::Error> {
-            let sql = #select_by_id_sql;
-            let record = sqlx::query_as::<_, #struct_name>(sql)
-                .bind(id)
+        impl #model_name {
+            /// 插入记录，返回按主键读回的完整记录，包括自增主键和由数据库默认值填充的列
+            #vis async fn insert(self, pool: &#pool_ty) -> Result<#struct_name, #error_ty> {
+                let #record = #struct_name {
+                    #( #field_names: self.#field_names, )*
+                    #( #defaulted: Default::default(), )*
+                };
+                #store_id record.#insert_fn(pool).await?;
+                match #struct_name::#find_by_id_fn(pool, &record.#pk_field).await? {
+                    Some(record) => Ok(record),
+                    None => Err(#not_found),
+                }
+            }
+        }
+    }
+}
+
+/// 生成 #[projection] 声明的结构体，以及只读取其字段对应列的 `find_all`、`find_by_id`
+pub fn generate_projections(parsed: &ParsedStruct) -> TokenStream {
+    let vis = parsed.options.vis();
+    let error_ty = error_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let row_ty = row_type(parsed);
+    let pk = parsed.fields.iter()
+        .find(|f| f.is_primary_key)
+        .expect("No primary key defined");
+    let pk_param = &pk.ident;
+    let pk_type = &pk.ty;
+    let pk_value = bind_value(pk, quote!(#pk_param));
+    
+    let projections = parsed.projections.iter().map(|projection| {
+        let name = &projection.name;
+        let doc = format!("`{}` 中部分列的投影", parsed.name);
+        let field_names: Vec<&Ident> = projection.fields.iter().map(|f| &f.ident).collect();
+        let field_types = projection.fields.iter().map(|f| &f.ty);
+        let values = projection.fields.iter().map(read_column);
+        let (select_sql, select_by_id_sql) = generate_projection_select_sql(parsed, projection);
+        let find_all = run_query(parsed, "find_all", quote! {
+            sqlx::query_as::<_, Self>(sql).fetch_all(pool)
+        }, quote!(0usize), RowCount::All);
+        let find_by_id = run_query(parsed, "find_by_id", quote! {
+            sqlx::query_as::<_, Self>(sql)
+                .bind(#pk_value)
                 .fetch_optional(pool)
-                .await?;
-            Ok(record)
+        }, quote!(1usize), RowCount::Optional);
+        
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone)]
+            #vis struct #name {
+                #( #vis #field_names: #field_types, )*
+            }
+
+            impl<'r> sqlx::FromRow<'r, #row_ty> for #name {
+                fn from_row(row: &'r #row_ty) -> Result<Self, sqlx::Error> {
+                    use sqlx::Row;
+                    Ok(Self {
+                        #(#field_names: #values,)*
+                    })
+                }
+            }
+
+            impl #name {
+                /// 查询所有记录的语句，只选取投影中的列
+                #vis const SELECT_SQL: &'static str = #select_sql;
+                /// 按主键查询记录的语句，只选取投影中的列
+                #vis const SELECT_BY_ID_SQL: &'static str = #select_by_id_sql;
+
+                /// 查询所有记录的投影
+                #vis async fn find_all(pool: &#pool_ty) -> Result<Vec<Self>, #error_ty> {
+                    let sql = Self::SELECT_SQL;
+                    let records = #find_all;
+                    Ok(records)
+                }
+
+                /// 按ID查询记录的投影
+                #vis async fn find_by_id(pool: &#pool_ty, #pk_param: &#pk_type) -> Result<Option<Self>, #error_ty> {
+                    let sql = Self::SELECT_BY_ID_SQL;
+                    let record = #find_by_id;
+                    Ok(record)
+                }
+            }
         }
+    });
+    
+    quote! {
+        #(#projections)*
     }
 }
 
 /// 生成所有CRUD方法
 pub fn generate_impl_block(parsed: &ParsedStruct) -> TokenStream {
+    if let Err(err) = check_bind_counts(parsed) {
+        return err.to_compile_error();
+    }
     let struct_name = format_ident!("{}", parsed.name);
     
-    let init_table_method = generate_init_table_method(parsed);
-    let insert_method = generate_insert_method(parsed);
-    let update_method = generate_update_method(parsed);
-    let delete_method = generate_delete_method(parsed);
-    let select_methods = generate_select_methods(parsed);
+    let skip = &parsed.options.skip;
+    
+    let sql_consts = generate_sql_consts(parsed);
+    let table_name_method = generate_table_name_method(parsed);
+    let runtime_table_methods = generate_runtime_table_methods(parsed);
+    let columns_methods = generate_columns_methods(parsed);
+    let validate_method = generate_validate_method(parsed);
+    let init_table_method = if skip.init_table { TokenStream::new() } else { generate_init_table_method(parsed) };
+    let init_table_with_method = if parsed.options.init_options { generate_init_table_with_method(parsed) } else { TokenStream::new() };
+    let migration_method = if skip.init_table { TokenStream::new() } else { generate_migration_method(parsed) };
+    let insert_method = if skip.insert { TokenStream::new() } else { generate_insert_method(parsed) };
+    let insert_present_method = if parsed.options.insert_present { generate_insert_present_method(parsed) } else { TokenStream::new() };
+    let get_or_insert_method = if skip.insert || skip.find { TokenStream::new() } else { generate_get_or_insert_method(parsed) };
+    let upsert_many_method = if skip.insert || skip.update { TokenStream::new() } else { generate_upsert_many_method(parsed) };
+    let transaction_method = generate_transaction_method(parsed);
+    let blocking_methods = generate_blocking_methods(parsed);
+    let update_method = if skip.update { TokenStream::new() } else { generate_update_method(parsed) };
+    let update_returning_method = if skip.update || !parsed.options.update_returning { TokenStream::new() } else { generate_update_returning_method(parsed) };
+    let increment_methods = if skip.update { TokenStream::new() } else { generate_increment_methods(parsed) };
+    let delete_method = if skip.delete { TokenStream::new() } else { generate_delete_method(parsed) };
+    let delete_by_methods = if skip.delete { TokenStream::new() } else { generate_delete_by_methods(parsed) };
+    let select_methods = if skip.find { TokenStream::new() } else { generate_select_methods(parsed) };
+    let find_by_methods = if skip.find { TokenStream::new() } else { generate_find_by_methods(parsed) };
+    let raw_query_method = if skip.find { TokenStream::new() } else { generate_raw_query_method(parsed) };
+    let find_where_methods = if skip.find { TokenStream::new() } else { generate_find_where_methods(parsed) };
+    let explain_methods = if skip.find { TokenStream::new() } else { generate_explain_methods(parsed) };
+    let count_methods = if skip.find { TokenStream::new() } else { generate_count_methods(parsed) };
+    let count_where_methods = if skip.find { TokenStream::new() } else { generate_count_where_methods(parsed) };
+    let find_page_method = if skip.find { TokenStream::new() } else { generate_find_page_method(parsed) };
+    let has_many_methods = generate_has_many_methods(parsed);
+    let belongs_to_methods = generate_belongs_to_methods(parsed);
+    let insert_model = generate_insert_model(parsed);
+    let projections = generate_projections(parsed);
+    let self_check = generate_self_check(parsed);
+    let filter = generate_filter(parsed);
+    
+    let from_row = if parsed.options.from_row {
+        generate_from_row_impl(parsed)
+    } else {
+        TokenStream::new()
+    };
+    
+    let error_type = generate_error_type(parsed);
+    let hooks_trait = generate_hooks_trait(parsed);
+    
+    let init_options = if parsed.options.init_options {
+        generate_init_options_type(parsed)
+    } else {
+        TokenStream::new()
+    };
+    
+    let repository = if parsed.options.repository {
+        generate_repository(parsed)
+    } else {
+        TokenStream::new()
+    };
+    
+    let builder = if parsed.options.builder {
+        generate_builder(parsed)
+    } else {
+        TokenStream::new()
+    };
+    
+    let generics = bounded_generics(parsed);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     
     quote! {
-        impl #struct_name {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            #sql_consts
+            #table_name_method
+            #runtime_table_methods
+            #columns_methods
+            #validate_method
             #init_table_method
+            #init_table_with_method
+            #migration_method
             #insert_method
+            #insert_present_method
+            #get_or_insert_method
+            #upsert_many_method
+            #transaction_method
             #update_method
+            #update_returning_method
+            #increment_methods
             #delete_method
+            #delete_by_methods
             #select_methods
+            #find_by_methods
+            #raw_query_method
+            #find_where_methods
+            #explain_methods
+            #blocking_methods
+            #count_methods
+            #count_where_methods
+            #find_page_method
+            #has_many_methods
+            #belongs_to_methods
+        }
+
+        #error_type
+        #hooks_trait
+        #from_row
+        #init_options
+        #repository
+        #builder
+        #insert_model
+        #projections
+        #self_check
+        #filter
+    }
+}
+
+/// 生成 #[sql_crud(filter)] 的 `<Name>Filter` 和创建它的静态方法 `filter`
+///
+/// 条件的列名和运算符在宏展开时生成，值只通过参数绑定，调用方无法拼接任意SQL
+pub fn generate_filter(parsed: &ParsedStruct) -> TokenStream {
+    if !parsed.options.filter {
+        return TokenStream::new();
+    }
+    let vis = parsed.options.vis();
+    let struct_name = format_ident!("{}", parsed.name);
+    let filter_name = format_ident!("{}Filter", parsed.name);
+    let filter_fn = method(parsed, "filter");
+    let db = database_type(parsed);
+    let pool_ty = pool_type(parsed);
+    let error_ty = error_type(parsed);
+    let sql = generate_filter_sql(parsed);
+    let placeholder = placeholder_fn(parsed);
+    
+    let methods = parsed.fields.iter().filter(|f| f.flattened.is_none()).map(|f| {
+        let name = f.ident.unraw().to_string();
+        let inner = option_inner(&f.ty);
+        let is_string = is_string_type(inner);
+        let param_ty = if is_string { quote!(&str) } else { quote!(&#inner) };
+        let element_ty = if is_string { quote!(&str) } else { quote!(#inner) };
+        // 条件只比较非空值，按 `Option` 的内层类型绑定
+        let mut inner_field = f.clone();
+        inner_field.ty = inner.clone();
+        let bind = if f.codec.is_some() { bind_value(&inner_field, quote!(value)) } else { quote!(value.to_owned()) };
+        let element_bind = if is_string { quote!((*value).to_owned()) } else { bind.clone() };
+        let comparison = |suffix: &str, operator: &str, doc: String| {
+            let method_name = format_ident!("{}_{}", name, suffix);
+            let condition = generate_filter_condition_sql(parsed, f, operator);
+            quote! {
+                #[doc = #doc]
+                #vis fn #method_name(mut self, value: #param_ty) -> Self {
+                    let placeholder = self.push_bind(#bind);
+                    self.conditions.push(format!("{}{}", #condition, placeholder));
+                    self
+                }
+            }
+        };
+        let mut methods = vec![
+            comparison("eq", "= ", format!("`{}` 等于 `value`", name)),
+            comparison("ne", "<> ", format!("`{}` 不等于 `value`", name)),
+        ];
+        let in_fn = format_ident!("{}_in", name);
+        let in_condition = generate_filter_condition_sql(parsed, f, "IN (");
+        let never = &sql.never;
+        let in_doc = format!("`{}` 等于 `values` 中的任一值，`values` 为空时不匹配任何记录", name);
+        methods.push(quote! {
+            #[doc = #in_doc]
+            #vis fn #in_fn(mut self, values: &[#element_ty]) -> Self {
+                if values.is_empty() {
+                    self.conditions.push(#never.to_string());
+                    return self;
+                }
+                let placeholders = values.iter()
+                    .map(|value| self.push_bind(#element_bind))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.conditions.push(format!("{}{})", #in_condition, placeholders));
+                self
+            }
+        });
+        if is_string {
+            methods.push(comparison("like", "LIKE ", format!("`{}` 匹配 LIKE 模式 `pattern`", name)));
+        }
+        if is_datetime_type(inner) {
+            methods.push(comparison("before", "< ", format!("`{}` 早于 `value`", name)));
+            methods.push(comparison("after", "> ", format!("`{}` 晚于 `value`", name)));
+        }
+        if is_option(&f.ty) {
+            let is_null_fn = format_ident!("{}_is_null", name);
+            let is_null_condition = generate_filter_condition_sql(parsed, f, "IS NULL");
+            let is_null_doc = format!("`{}` 为 NULL", name);
+            methods.push(quote! {
+                #[doc = #is_null_doc]
+                #vis fn #is_null_fn(mut self) -> Self {
+                    self.conditions.push(#is_null_condition.to_string());
+                    self
+                }
+            });
+        }
+        methods
+    }).collect::<Vec<_>>().into_iter().flatten();
+    
+    // 启用重试时查询可能执行多次，每次使用参数的副本
+    let arguments = if parsed.options.busy_retry.is_some() { quote!(self.arguments.clone()) } else { quote!(self.arguments) };
+    let terminal = |operation: &str, head: &str, doc: &str, output: TokenStream, query: TokenStream, rows: RowCount, value: TokenStream| {
+        let method_name = format_ident!("{}", operation);
+        let map_err = map_error(parsed, &format!("filter_{}", operation));
+        let run = run_query(parsed, &format!("filter_{}", operation), query, quote!(sqlx::Arguments::len(&self.arguments)), rows);
+        quote! {
+            #[doc = #doc]
+            #vis async fn #method_name(self, pool: &#pool_ty) -> Result<#output, #error_ty> {
+                let sql = self.statement(#head);
+                if let Some(err) = self.error {
+                    return Err(sqlx::Error::Encode(err)) #map_err;
+                }
+                let result = #run;
+                Ok(#value)
+            }
+        }
+    };
+    let skip = &parsed.options.skip;
+    let fetch = if skip.find { TokenStream::new() } else {
+        terminal("fetch", &sql.select, "查询满足所有条件的记录", quote!(Vec<#struct_name>), quote! {
+            sqlx::query_as_with::<_, #struct_name, _>(&sql, #arguments).fetch_all(pool)
+        }, RowCount::All, quote!(result))
+    };
+    let count = if skip.find { TokenStream::new() } else {
+        terminal("count", &sql.count, "统计满足所有条件的记录数", quote!(i64), quote! {
+            sqlx::query_scalar_with::<_, i64, _>(&sql, #arguments).fetch_one(pool)
+        }, RowCount::One, quote!(result))
+    };
+    let delete = if skip.delete { TokenStream::new() } else {
+        terminal("delete", &sql.delete, "删除满足所有条件的记录，返回删除的行数；没有条件时删除所有记录", quote!(u64), quote! {
+            sqlx::query_with(&sql, #arguments).execute(pool)
+        }, RowCount::Affected, quote!(result.rows_affected()))
+    };
+    let (where_keyword, and) = (&sql.where_keyword, &sql.and);
+    let select = &sql.select;
+    let filter_doc = format!("按字段组合条件查询、统计或删除 `{}` 的过滤器，由 `{}::{}()` 创建，条件以 AND 组合，值只通过参数绑定", parsed.name, parsed.name, filter_fn);
+    
+    quote! {
+        #[doc = #filter_doc]
+        #[derive(Default)]
+        #vis struct #filter_name {
+            conditions: Vec<String>,
+            arguments: <#db as sqlx::Database>::Arguments<'static>,
+            error: Option<sqlx::error::BoxDynError>,
+        }
+
+        impl #filter_name {
+            #(#methods)*
+
+            /// 返回按当前条件查询记录的SQL
+            #vis fn sql(&self) -> String {
+                self.statement(#select)
+            }
+
+            #fetch
+            #count
+            #delete
+
+            /// 绑定一个值并返回它的占位符，编码失败时记录第一个错误，在执行时返回
+            fn push_bind<T>(&mut self, value: T) -> String
+            where
+                T: 'static + sqlx::Encode<'static, #db> + sqlx::Type<#db>,
+            {
+                let index = sqlx::Arguments::len(&self.arguments);
+                if let Err(err) = sqlx::Arguments::add(&mut self.arguments, value) {
+                    self.error.get_or_insert(err);
+                }
+                (#placeholder)(index)
+            }
+
+            /// 在 `head` 之后拼接所有条件
+            fn statement(&self, head: &str) -> String {
+                if self.conditions.is_empty() {
+                    format!("{};", head)
+                } else {
+                    format!("{}{}{};", head, #where_keyword, self.conditions.join(#and))
+                }
+            }
+        }
+
+        impl #struct_name {
+            /// 创建没有任何条件的过滤器
+            #vis fn #filter_fn() -> #filter_name {
+                #filter_name::default()
+            }
         }
     }
-}
\ No newline at end of file
+}
@@ -7,7 +7,7 @@
 //! ```rust
 //! use macros::SqlCRUD;
 //! 
-//! #[derive(SqlCRUD)]
+//! #[derive(SqlCRUD, sqlx::FromRow)]
 //! struct User {
 //!     #[primary_key]
 //!     id: i32,
@@ -32,29 +32,124 @@ use syn::{parse_macro_input, DeriveInput};
 ///
 /// # 属性
 ///
-/// - `#[primary_key]`: 标记主键字段
-/// - `#[comment = "..."]`: 为字段或表添加注释
+/// - `#[primary_key]`: 标记主键字段，`#[primary_key(generate = "uuid_v4")]` 同时启用 `#[uuid_generate]`
+/// - `#[auto_increment]`: 标记整数主键为自增列，插入时不绑定该列
+/// - `#[queryable]`: 为字段生成按列查询的方法
+/// - `#[searchable]`: 为字符串字段生成按 LIKE 模式查询的方法
+/// - `#[unique]`: 为列添加唯一约束，并作为 `get_or_insert`、`upsert_many` 的冲突列
+/// - `#[counter]`: 为整数字段生成原子自增方法
+/// - `#[skip]`: 字段不对应任何列
+/// - `#[json_indexed]`: 为 JSON 列生成按路径查询的方法，仅支持 SQLite 和 MySQL
+/// - `#[uuid_generate]`: 插入时为空的 `Uuid` 字段生成 v4 UUID
+/// - `#[uuid_storage = "text"]`: SQLite 上 `Uuid` 列的类型，可选 `text`、`blob`
+/// - `#[validate(with = "path::to::fn")]`: 字段校验函数，可重复指定
+/// - `#[column = "..."]`: 自定义列名
+/// - `#[comment = "..."]`: 为字段或表添加注释（SQLite 忽略注释）
 /// - `#[table_name = "..."]`: 自定义表名
-/// - `#[sql_type = "..."]`: 自定义SQL类型
+/// - `#[table_prefix = "..."]`: 表名前缀，同样加在 `read_table`、`write_table` 上，未指定时读取环境变量 `SQL_CRUD_TABLE_PREFIX`，变化后需重新编译
+/// - `#[schema = "..."]`: 表所在的模式
+/// - `#[read_table = "..."]`、`#[write_table = "..."]`: 查询和写入使用的表或视图
+/// - `#[table_naming = "..."]`: 由结构体名推导表名的规则，可选 `snake`（默认）、`lower`、`preserve`、`snake_plural`
+/// - `#[sql_type = "..."]`: 自定义SQL类型，无法由字段类型推导时必须指定
+/// - `#[max_length = 255]`: 字符串列使用 `VARCHAR(255)`，`validate` 同时检查长度
+/// - `#[text]`: 字符串列使用 `TEXT`
+/// - `#[type_map("Money" = "NUMERIC")]`、`#[sql_type_map(Money = "NUMERIC")]`: 结构体级的类型映射
+/// - `#[sql_transparent = "i64"]`: 新类型字段按内部类型推导列类型
+/// - `#[decimal(precision = 19, scale = 4)]`: 数值列使用 `NUMERIC(19,4)`
+/// - `#[sql_enum(text)]`、`#[sql_enum(int)]`: 枚举字段的存储方式，`values(...)` 添加 CHECK 约束
+/// - `#[duration(millis)]`: `Duration` 字段按给定单位存为 `BIGINT`
+/// - `#[ip_as_text]`: IP 地址字段存为文本
+/// - `#[flatten(prefix = "...", fields(...))]`: 把嵌入结构体的字段展开为带前缀的列
+/// - `#[upsert_coalesce]`: `upsert_many` 冲突时新值为 `NULL` 则保留原值
+/// - `#[unique(a, b)]`: 结构体上的联合唯一约束，可出现多次
+/// - `#[has_many(Post, foreign_key = "user_id")]`: 生成加载子记录的方法，可出现多次
+/// - `#[belongs_to(User)]`: 在外键字段上生成加载父记录的方法
+/// - `#[projection(UserSummary: id, name)]`: 生成只含部分列的投影结构体
+/// - `#[sql_override(insert = "...")]`: 按原样替换生成的语句
+/// - `#[sql_case = "lower"]`: 生成的SQL关键字使用小写
+/// - `#[sql_crud(repository)]`: 额外生成持有连接池的 `<Name>Repo`
+/// - `#[sql_crud(backend = "...")]`: 数据库后端，可选 `sqlite`（默认）、`postgres`、`mysql`、`any`
+/// - `#[sql_crud(from_row)]`: 生成 `sqlx::FromRow` 实现，`char`、`#[duration]`、`#[ip_as_text]`、`#[flatten]` 字段需要它
+/// - `#[sql_crud(vis = "...")]`: 生成项的可见性，默认 `pub`
+/// - `#[sql_crud(tracing)]`: 在 `sql.<方法名>` span 中执行查询并记录SQL和参数个数，调用方需依赖 `tracing`
+/// - `#[sql_crud(error)]`: 生成 `<Name>Error` 并作为方法的错误类型
+/// - `#[sql_crud(hooks)]`: 生成 `<Name>Hooks`，写操作前后调用其中的钩子
+/// - `#[sql_crud(raw_query)]`: 生成 `find_by_sql`，子句原样拼接，不得包含外部输入
+/// - `#[sql_crud(prefix = "...")]`: 为生成的方法名添加前缀
+/// - `#[sql_crud(blocking)]`: 生成同步的 `<method>_blocking`，最后一个参数为执行它的 `tokio::runtime::Runtime`
+/// - `#[sql_crud(busy_retry(attempts = 5, backoff_ms = 50, max_backoff_ms = 1000, reads))]`: SQLite 忙时按指数退避重试，`reads` 时查询也重试，调用方需依赖启用 `time` 特性的 `tokio`
+/// - `#[sql_crud(strict_create)]`: 建表语句不带 `IF NOT EXISTS`
+/// - `#[sql_crud(strict)]`、`#[sql_crud(without_rowid)]`: SQLite 的 STRICT、WITHOUT ROWID 表
+/// - `#[sql_crud(runtime_prefix)]`: 生成在运行时为表名加前缀的 `<method>_with_prefix`
+/// - `#[sql_crud(dynamic_table)]`: 生成在运行时指定表名的 `<method>_in_table`
+/// - `#[sql_crud(create_schema)]`: `init_table` 先创建 `#[schema]` 指定的模式，仅 Postgres
+/// - `#[sql_crud(init_options)]`: 生成执行 PRAGMA 后建表的 `init_table_with`，仅 SQLite
+/// - `#[sql_crud(update_returning)]`: 生成 `update_returning`
+/// - `#[sql_crud(builder)]`: 生成 `<Name>Builder`
+/// - `#[sql_crud(insert_model)]`: 生成只含可插入字段的 `New<Name>`
+/// - `#[sql_crud(insert_present)]`: 生成省略 `None` 字段的 `insert_present`
+/// - `#[sql_crud(filter)]`: 生成按字段组合条件的 `<Name>Filter`
+/// - `#[sql_crud(stream)]`: 生成 `find_all_stream`，调用方需依赖 `futures-core`
+/// - `#[sql_crud(self_check)]`: 生成校验占位符与绑定数量一致的测试
+/// - `#[sql_crud(skip(...))]`: 不生成指定的方法，可选 `insert`、`update`、`delete`、`find`、`init_table`
 ///
 /// # 生成的方法
 ///
-/// - `init_table`: 初始化表结构
+/// - `init_table`: 在一个事务中初始化表结构
+/// - `migration_from`: 生成新增列的 `ALTER TABLE` 语句（静态方法）
+/// - `add_column_<field>_sql`: 添加该列的 `ALTER TABLE` 语句（静态方法）
 /// - `table_name`: 获取表名
-/// - `insert`: 插入记录
+/// - `columns`: 返回 `(列名, 列类型)`（静态方法）
+/// - `primary_key_columns`: 返回主键列名（静态方法）
+/// - `validate`: 运行字段校验
+/// - `insert`: 插入记录，有自增主键时返回生成的主键值
 /// - `insert_one`: 插入记录（静态方法）
+/// - `into_insert`: 按值插入记录
+/// - `get_or_insert`: 记录不存在时插入，返回数据库中的记录（静态方法）
+/// - `upsert_many`: 批量插入或更新记录（静态方法）
+/// - `with_transaction`: 在事务中执行闭包（静态方法）
 /// - `update`: 更新记录
+/// - `update_returning`: 更新记录并返回更新后的记录
+/// - `increment_<field>`: 原子地增加 `#[counter]` 列（静态方法）
 /// - `delete`: 删除记录
 /// - `delete_by_id`: 按ID删除记录（静态方法）
+/// - `delete_by_<column>`: 按 `#[queryable]` 列删除记录（静态方法）
 /// - `find_all`: 查询所有记录（静态方法）
+/// - `find_all_with_<relation>`: 查询所有记录及其子记录（静态方法）
+/// - `find_all_stream`: 以流的形式查询所有记录（静态方法）
 /// - `find_by_id`: 按ID查询记录（静态方法）
-#[proc_macro_derive(SqlCRUD, attributes(primary_key, comment, table_name, sql_type))]
+/// - `find_by_<column>`: 按 `#[queryable]` 列查询所有匹配记录（静态方法）
+/// - `find_first_by_<column>`: 按 `#[queryable]` 列查询第一条匹配记录（静态方法）
+/// - `search_<column>`: 按 `#[searchable]` 列的 LIKE 模式查询记录（静态方法）
+/// - `find_by_<column>_in`: 按 `#[queryable]` 列查询值在给定切片中的记录（静态方法）
+/// - `find_by_<field>_json_path`: 按 `#[json_indexed]` 列中 JSON 路径处的值查询记录（静态方法）
+/// - `find_by_sql`: 按原始 WHERE 子句查询记录（静态方法）
+/// - `find_where`、`find_one_where`: 按带绑定参数的 WHERE 子句查询记录（静态方法）
+/// - `count_where`、`exists_where`: 按带绑定参数的 WHERE 子句统计或判断是否存在记录（静态方法）
+/// - `explain_<method>`: 查询方法的 `EXPLAIN QUERY PLAN` 输出，仅 SQLite（静态方法）
+/// - `count`: 统计记录数（静态方法）
+/// - `find_page`: 分页查询记录并返回总数（静态方法）
+/// - `count_by_<column>`: 按 `#[queryable]` 列统计记录数（静态方法）
+///
+/// 生成的语句同时以 `INSERT_SQL`、`SELECT_SQL` 等关联常量的形式公开。
+/// 写方法接受任意 `sqlx::Executor`，可在 `with_transaction` 中传入 `&mut **tx`。
+/// 生成项与结构体位于同一模块，可以读写私有字段；支持泛型结构体，`PhantomData` 字段不对应任何列。
+#[proc_macro_derive(SqlCRUD, attributes(
+    primary_key, auto_increment, queryable, searchable, unique, counter, skip, json_indexed,
+    uuid_generate, uuid_storage, sql_transparent, validate, column, comment,
+    table_name, table_prefix, read_table, write_table, schema, table_naming,
+    sql_type, max_length, text, decimal, sql_enum, duration, ip_as_text, upsert_coalesce,
+    sql_case, type_map, sql_type_map, sql_crud, sql_override, has_many, belongs_to, projection, flatten
+))]
 pub fn derive_sql_crud(input: TokenStream) -> TokenStream {
     // 解析输入的Rust代码
     let input = parse_macro_input!(input as DeriveInput);
     
     // 解析结构体定义
-    let parsed = parser::parse_struct(&input);
+    let parsed = match parser::parse_struct(&input) {
+        Ok(parsed) => parsed,
+        Err(err) => return err.to_compile_error().into(),
+    };
     
     // 生成实现代码
     let output = code_generator::generate_impl_block(&parsed);
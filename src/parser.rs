@@ -1,77 +1,1522 @@
-use syn::{Attribute, Data, DeriveInput, Field, Fields, Lit, Meta, Type};
-use crate::utils::{extract_comment, get_table_name};
+use std::collections::HashSet;
+use quote::format_ident;
+use syn::ext::IdentExt;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, LitStr, Token, Attribute, Data, DeriveInput, Field, Fields, Generics, Ident, Lit, Meta, MetaList, NestedMeta, Path, Type, Visibility};
+use crate::utils::{camel_to_snake, count_placeholders, did_you_mean, extract_comment, get_sql_case, get_string_attr, get_table_name, is_char_type, is_decimal_type, is_duration_type, is_integer_type, is_ip_addr_type, is_option, is_phantom_data, is_string_type, is_uuid_type, map_transparent_type, normalize_type, option_inner, pluralize, quote_ident, STRICT_TYPE_MAP};
 
 /// 表示一个字段的解析结果
+#[derive(Clone)]
 pub struct ParsedField {
+    /// Rust 字段名
+    pub ident: Ident,
+    /// 列名，默认与字段名相同，可通过 #[column = "..."] 重命名
     pub name: String,
     pub ty: Type,
-    pub sql_type: String,
+    /// 列的逻辑类型，建表时按后端解析为具体的 SQL 类型
+    pub sql_type: SqlType,
     pub is_primary_key: bool,
+    /// 是否为自增列（#[auto_increment]），插入时由数据库赋值
+    pub is_auto_increment: bool,
+    /// 是否为该列生成按列查询的方法（#[queryable]）
+    pub is_queryable: bool,
+    /// 是否为文本列生成按 LIKE 模式查询的方法（#[searchable]）
+    pub is_searchable: bool,
+    /// 是否带有唯一约束（#[unique]）
+    pub is_unique: bool,
+    /// 是否为计数列（#[counter]），生成原子自增方法
+    pub is_counter: bool,
+    /// 是否为 JSON 列生成按路径查询的方法（#[json_indexed]）
+    pub is_json_indexed: bool,
+    /// 插入时是否为空值的 UUID 生成 v4（#[uuid_generate]）
+    pub is_uuid_generate: bool,
+    /// #[sql_enum(..., values(...))] 列出的允许值，非空时建表语句带 CHECK 约束
+    pub allowed_values: Vec<AllowedValue>,
+    /// 字符串列的最大长度（#[max_length = N]），建表为 `VARCHAR(N)`，`validate` 校验字符数
+    pub max_length: Option<u32>,
+    /// 批量 upsert 冲突时是否保留已有的非空值（#[upsert_coalesce]）
+    pub is_upsert_coalesce: bool,
+    /// 写入和读取列值时的转换（#[duration(...)]、#[ip_as_text]、`char`）
+    pub codec: Option<ColumnCodec>,
     pub comment: Option<String>,
+    /// 插入和更新前调用的校验函数（#[validate(with = "...")]），按声明顺序执行
+    pub validators: Vec<Path>,
+    /// #[belongs_to(Parent)] 声明的父记录加载方法
+    pub belongs_to: Option<BelongsTo>,
+    /// 由 #[flatten(...)] 字段展开的列所属的字段，`ident` 为 `<字段>_<子字段>`
+    pub flattened: Option<Flattened>,
+}
+
+/// #[flatten(...)] 字段中的一个子字段
+#[derive(Clone)]
+pub struct Flattened {
+    /// 结构体中的字段
+    pub field: Ident,
+    /// 字段的类型，`from_row` 用它构造嵌套的值
+    pub ty: Type,
+    /// 子字段名
+    pub member: Ident,
+}
+
+/// #[belongs_to(Parent, method = "...")] 声明的多对一关系，所在字段为引用父表主键的外键
+#[derive(Clone)]
+pub struct BelongsTo {
+    /// 父表对应的结构体，需要同样派生 SqlCRUD
+    pub parent: Path,
+    /// 生成的加载方法名，默认为父结构体名的蛇形形式
+    pub method: Ident,
+}
+
+/// #[sql_enum(...)] 中列出的允许值
+#[derive(Clone)]
+pub enum AllowedValue {
+    Text(String),
+    Int(i64),
+}
+
+/// 以其他类型存储的字段在绑定和 `from_row` 中的转换
+#[derive(Clone, Copy)]
+pub enum ColumnCodec {
+    /// `Duration` 按给定单位存为整数（#[duration(...)]）
+    Duration(DurationUnit),
+    /// IP 地址存为文本（#[ip_as_text]）
+    IpText,
+    /// `char` 存为单字符文本
+    Char,
+}
+
+impl ColumnCodec {
+    /// 声明该转换的属性，用于错误信息
+    pub fn attr(self) -> &'static str {
+        match self {
+            ColumnCodec::Duration(_) => "#[duration(...)]",
+            ColumnCodec::IpText => "#[ip_as_text]",
+            ColumnCodec::Char => "a `char` field",
+        }
+    }
+}
+
+/// #[duration(...)] 中 `Duration` 列的整数单位
+#[derive(Clone, Copy)]
+pub enum DurationUnit {
+    Seconds,
+    Millis,
+    Micros,
+}
+
+impl DurationUnit {
+    /// 写入时使用的 `Duration::as_*` 方法名
+    pub fn encoder(self) -> &'static str {
+        match self {
+            DurationUnit::Seconds => "as_secs",
+            DurationUnit::Millis => "as_millis",
+            DurationUnit::Micros => "as_micros",
+        }
+    }
+
+    /// 读取时使用的 `Duration::from_*` 构造函数名
+    pub fn decoder(self) -> &'static str {
+        match self {
+            DurationUnit::Seconds => "from_secs",
+            DurationUnit::Millis => "from_millis",
+            DurationUnit::Micros => "from_micros",
+        }
+    }
+}
+
+/// 通过 #[sql_crud(skip(...))] 跳过生成的方法
+#[derive(Clone, Default)]
+pub struct SkipFlags {
+    pub insert: bool,
+    pub update: bool,
+    pub delete: bool,
+    pub find: bool,
+    pub init_table: bool,
+}
+
+/// 生成代码所针对的数据库后端
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    #[default]
+    Sqlite,
+    Postgres,
+    MySql,
+    /// 运行时才确定具体数据库的 `sqlx::Any`
+    Any,
+}
+
+impl Backend {
+    /// 生成第 `index` 个（从1开始）参数占位符
+    pub fn placeholder(self, index: usize) -> String {
+        match self {
+            Backend::Sqlite | Backend::Postgres => format!("${}", index),
+            Backend::MySql | Backend::Any => "?".into(),
+        }
+    }
+}
+
+/// 列的逻辑类型，由 `sql_generator::column_type` 按后端解析为建表语句中的类型
+#[derive(Clone, Debug)]
+pub enum SqlType {
+    TinyInt,
+    SmallInt,
+    Int,
+    BigInt,
+    /// 无符号整数，MySQL 上为 `UNSIGNED`，其余后端使用能容纳其取值范围的有符号类型
+    UnsignedTinyInt,
+    UnsignedSmallInt,
+    UnsignedInt,
+    UnsignedBigInt,
+    /// 字符串，`len` 为 `None` 时不限长度
+    Text { len: Option<u32> },
+    /// 单个字符
+    Char,
+    Bool,
+    Float,
+    Double,
+    /// 定点小数，`precision` 为 `(精度, 小数位数)`
+    Decimal { precision: Option<(u32, u32)> },
+    /// 不带时区的日期时间
+    DateTime,
+    /// 带时区的日期时间
+    DateTimeTz,
+    Date,
+    Time,
+    Blob,
+    Uuid,
+    Json,
+    /// Postgres 的数组列
+    Array(Box<SqlType>),
+    /// `#[sql_type]`、`#[type_map]` 等给出的类型，原样写入建表语句
+    Custom(String),
+}
+
+/// #[sql_crud(busy_retry(...))] 的重试配置
+#[derive(Clone)]
+pub struct BusyRetry {
+    /// 最多尝试的次数，包括第一次
+    pub attempts: u32,
+    /// 第一次重试前的等待时间，之后每次翻倍
+    pub backoff_ms: u64,
+    /// 单次等待时间的上限
+    pub max_backoff_ms: u64,
+    /// 查询方法是否也重试，默认只重试写方法
+    pub reads: bool,
+}
+
+impl Default for BusyRetry {
+    fn default() -> Self {
+        BusyRetry { attempts: 5, backoff_ms: 50, max_backoff_ms: 1000, reads: false }
+    }
+}
+
+/// 结构体级别的 #[sql_crud(...)] 选项
+#[derive(Clone, Default)]
+pub struct CrudOptions {
+    /// 是否生成 `<Name>Repo` 仓储类型
+    pub repository: bool,
+    /// 不需要生成的方法
+    pub skip: SkipFlags,
+    /// 是否生成 `sqlx::FromRow` 实现
+    pub from_row: bool,
+    /// 生成项的可见性，未指定时为 `pub`
+    pub vis: Option<Visibility>,
+    /// 数据库后端，默认 SQLite
+    pub backend: Backend,
+    /// 是否为每次查询生成 `tracing` span
+    pub tracing: bool,
+    /// 生成方法返回的错误类型，未指定时直接返回 `sqlx::Error`
+    pub error: Option<Ident>,
+    /// 是否在写操作前后调用 `<Name>Hooks` 中的钩子
+    pub hooks: bool,
+    /// 是否生成拼接原始 WHERE 子句的 `find_by_sql`
+    pub raw_query: bool,
+    /// 结构体上生成的方法名前缀，默认为空
+    pub prefix: String,
+    /// 是否生成阻塞调用的 `<method>_blocking` 包装方法
+    pub blocking: bool,
+    /// 遇到 SQLITE_BUSY/SQLITE_LOCKED 时的重试配置
+    pub busy_retry: Option<BusyRetry>,
+    /// 是否生成 `UPDATE ... RETURNING` 并返回更新后记录的 `update_returning`
+    pub update_returning: bool,
+    /// 是否生成逐字段构造记录的 `<Name>Builder`
+    pub builder: bool,
+    /// 是否建为 SQLite 的 STRICT 表
+    pub strict: bool,
+    /// 是否建为 SQLite 的 WITHOUT ROWID 表
+    pub without_rowid: bool,
+    /// 建表语句是否去掉 `IF NOT EXISTS`，表已存在时 `init_table` 报错
+    pub strict_create: bool,
+    /// 是否生成在建表前设置 PRAGMA 的 `<Name>InitOptions`、`configure_connection` 和 `init_table_with`
+    pub init_options: bool,
+    /// 是否生成在运行时为表名加前缀的 `table_name_with_prefix` 和 `<method>_with_prefix`
+    pub runtime_prefix: bool,
+    /// 是否生成在运行时指定整个表名的 `init_table_in` 和 `<method>_in_table`
+    pub dynamic_table: bool,
+    /// 是否在 `init_table` 建表前执行 `CREATE SCHEMA IF NOT EXISTS`
+    pub create_schema: bool,
+    /// 生成只含可插入字段的 `New<Name>` 时为其派生的 trait，未启用 `insert_model` 时为 `None`
+    pub insert_model: Option<Vec<Path>>,
+    /// 是否生成断言每条语句占位符数量与绑定数量一致的 `#[cfg(test)]` 测试
+    pub self_check: bool,
+    /// 是否生成省略值为 `None` 的 `Option` 字段、在运行时拼接列名的 `insert_present`
+    pub insert_present: bool,
+    /// 是否生成按字段组合条件的 `<Name>Filter`
+    pub filter: bool,
+    /// 是否生成返回 `futures_core::Stream` 的 `find_all_stream`
+    pub stream: bool,
+}
+
+impl CrudOptions {
+    /// 获取生成项的可见性
+    pub fn vis(&self) -> Visibility {
+        self.vis.clone().unwrap_or_else(|| parse_quote!(pub))
+    }
+}
+
+/// 生成SQL中关键字的大小写（#[sql_case = "..."]）
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum SqlCase {
+    #[default]
+    Upper,
+    Lower,
+}
+
+/// #[sql_override(...)] 中替换默认SQL的语句，按原样使用
+#[derive(Clone, Default)]
+pub struct SqlOverrides {
+    pub insert: Option<String>,
+    pub update: Option<String>,
+    pub delete: Option<String>,
+    pub select_all: Option<String>,
+    pub select_by_id: Option<String>,
+}
+
+/// #[projection(UserSummary: id, name)] 声明的投影结构体
+#[derive(Clone)]
+pub struct Projection {
+    /// 生成的结构体名
+    pub name: Ident,
+    /// 包含的字段，按声明顺序
+    pub fields: Vec<ParsedField>,
+}
+
+/// #[has_many(Child, foreign_key = "...")] 声明的一对多关系
+#[derive(Clone)]
+pub struct HasMany {
+    /// 子表对应的结构体，需要同样派生 SqlCRUD
+    pub child: Path,
+    /// 子表中引用本表主键的列
+    pub foreign_key: String,
+    /// 生成的加载方法名，默认为子结构体名的蛇形复数
+    pub method: Ident,
 }
 
 /// 表示一个结构体的解析结果
+#[derive(Clone)]
 pub struct ParsedStruct {
     pub name: String,
     pub table_name: String,
+    /// 查询使用的表或视图，默认与 `table_name` 相同
+    pub read_table: String,
+    /// 建表和写操作使用的表，默认与 `table_name` 相同
+    pub write_table: String,
+    /// 表所在的模式（#[schema = "..."]），生成的SQL中以 `"schema"."table"` 引用表
+    pub schema: Option<String>,
     pub fields: Vec<ParsedField>,
     pub comment: Option<String>,
+    pub options: CrudOptions,
+    pub sql_case: SqlCase,
+    pub generics: Generics,
+    /// 不对应任何列的字段（`#[skip]` 或 `PhantomData`），读取时使用 `Default::default()`
+    pub skipped_fields: Vec<Ident>,
+    pub overrides: SqlOverrides,
+    pub has_many: Vec<HasMany>,
+    /// #[projection(Name: field, ...)] 声明的只读取部分列的结构体
+    pub projections: Vec<Projection>,
+    /// 结构体上 #[unique(a, b)] 声明的联合唯一约束，每组为列名
+    pub unique_groups: Vec<Vec<String>>,
+}
+
+impl ParsedStruct {
+    /// 插入时需要绑定的字段，自增列由数据库赋值
+    pub fn insertable_fields(&self) -> impl Iterator<Item = &ParsedField> {
+        self.fields.iter().filter(|f| !f.is_auto_increment)
+    }
+    
+    /// 由数据库赋值的自增主键
+    pub fn auto_increment_field(&self) -> Option<&ParsedField> {
+        self.fields.iter().find(|f| f.is_auto_increment)
+    }
+    
+    /// 更新时 SET 子句中的字段，顺序即绑定顺序，主键在它们之后绑定
+    pub fn updatable_fields(&self) -> impl Iterator<Item = &ParsedField> {
+        self.fields.iter().filter(|f| !f.is_primary_key)
+    }
+    
+    /// `get_or_insert` 的冲突列：优先第一个 #[unique] 字段，否则为主键；自增主键无法作为冲突列
+    pub fn conflict_key(&self) -> Option<&ParsedField> {
+        self.fields.iter()
+            .find(|f| f.is_unique)
+            .or_else(|| self.fields.iter().find(|f| f.is_primary_key))
+            .filter(|f| !f.is_auto_increment)
+    }
+}
+
+/// 字段上以 #[name] 形式使用的属性
+const FIELD_FLAG_ATTRS: &[&str] = &["primary_key", "auto_increment", "queryable", "searchable", "unique", "counter", "skip", "json_indexed", "uuid_generate", "ip_as_text", "upsert_coalesce", "text"];
+
+/// 字段上以 #[name = "..."] 形式使用的属性
+const FIELD_VALUE_ATTRS: &[&str] = &["column", "comment", "sql_type", "uuid_storage", "sql_transparent", "max_length"];
+
+/// 字段上以 #[name(...)] 形式使用的属性
+const FIELD_LIST_ATTRS: &[&str] = &["validate", "decimal", "sql_enum", "duration", "belongs_to", "flatten"];
+
+/// 结构体上以 #[name = "..."] 形式使用的属性
+const STRUCT_VALUE_ATTRS: &[&str] = &["comment", "table_name", "table_naming", "sql_case", "read_table", "write_table", "schema", "table_prefix"];
+
+/// 结构体上的列表属性
+const STRUCT_LIST_ATTRS: &[&str] = &["sql_crud", "sql_override", "type_map", "sql_type_map", "has_many", "projection", "unique"];
+
+/// #[sql_override(...)] 中可替换的语句
+const SQL_OVERRIDES: &[&str] = &["insert", "update", "delete", "select_all", "select_by_id"];
+
+/// #[sql_crud(...)] 中可用的选项
+const CRUD_OPTIONS: &[&str] = &["repository", "from_row", "skip", "vis", "backend", "tracing", "error", "hooks", "raw_query", "prefix", "blocking", "busy_retry", "builder", "update_returning", "strict", "without_rowid", "strict_create", "init_options", "runtime_prefix", "dynamic_table", "create_schema", "insert_model", "self_check", "insert_present", "filter", "stream"];
+
+/// 校验已注册属性的形式，并拒绝用错位置的属性
+fn validate_attrs(attrs: &[Attribute], flag_attrs: &[&str], value_attrs: &[&str], list_attrs: &[&str], target: &str) -> syn::Result<()> {
+    for attr in attrs {
+        let name = match attr.path.get_ident() {
+            Some(ident) => ident.to_string(),
+            None => continue,
+        };
+        let name = name.as_str();
+        if flag_attrs.contains(&name) {
+            // primary_key 可带生成策略，由 parse_primary_key_generate 校验
+            if !attr.tokens.is_empty() && name != "primary_key" {
+                return Err(syn::Error::new_spanned(attr, format!("expected `#[{}]` without arguments", name)));
+            }
+        } else if name == "max_length" {
+            // max_length 的值是整数，由 parse_field 校验取值
+            match attr.parse_meta() {
+                Ok(Meta::NameValue(meta)) if matches!(meta.lit, Lit::Int(_)) => {}
+                _ => return Err(syn::Error::new_spanned(attr, "expected `#[max_length = N]`")),
+            }
+        } else if value_attrs.contains(&name) {
+            match attr.parse_meta() {
+                Ok(Meta::NameValue(meta)) if matches!(meta.lit, Lit::Str(_)) => {}
+                _ => return Err(syn::Error::new_spanned(attr, format!("expected `#[{} = \"...\"]`", name))),
+            }
+        } else if list_attrs.contains(&name) {
+            // type_map 的键可以是字符串、projection 和 flatten 带有 `name:`，不符合 Meta 语法，分别由 parse_type_map、parse_projections、parse_flatten 校验
+            if !["type_map", "sql_type_map", "projection", "flatten"].contains(&name) && !matches!(attr.parse_meta(), Ok(Meta::List(_))) {
+                return Err(syn::Error::new_spanned(attr, format!("expected `#[{}(...)]`", name)));
+            }
+        } else if FIELD_FLAG_ATTRS.contains(&name) || FIELD_VALUE_ATTRS.contains(&name) || FIELD_LIST_ATTRS.contains(&name)
+            || STRUCT_VALUE_ATTRS.contains(&name) || STRUCT_LIST_ATTRS.contains(&name) {
+            return Err(syn::Error::new_spanned(attr, format!("`#[{}]` cannot be used on {}", name, target)));
+        }
+    }
+    Ok(())
+}
+
+/// 生成 #[sql_crud(...)] 中无法识别的选项的错误，附带拼写建议
+fn unknown_option_error(nested: &NestedMeta) -> syn::Error {
+    let path = match nested {
+        NestedMeta::Meta(meta) => meta.path(),
+        NestedMeta::Lit(_) => return syn::Error::new_spanned(nested, "expected a sql_crud option"),
+    };
+    let name = path.get_ident().map(|i| i.to_string()).unwrap_or_default();
+    if CRUD_OPTIONS.contains(&name.as_str()) {
+        return syn::Error::new_spanned(nested, format!("invalid form of sql_crud option `{}`", name));
+    }
+    let message = match did_you_mean(&name, CRUD_OPTIONS) {
+        Some(suggestion) => format!("unknown sql_crud option `{}`, did you mean `{}`?", name, suggestion),
+        None => format!("unknown sql_crud option `{}`, expected one of: {}", name, CRUD_OPTIONS.join(", ")),
+    };
+    syn::Error::new_spanned(path, message)
+}
+
+/// 解析 #[type_map("Foo" = "BAR", ...)] 和 #[sql_type_map(Foo = "BAR", ...)]，
+/// 类型名可写成字符串或标识符，可出现多次，同一类型名重复时报错
+fn parse_type_map(attrs: &[Attribute]) -> syn::Result<Vec<(String, String)>> {
+    fn parse_entry(input: ParseStream) -> syn::Result<(LitStr, LitStr)> {
+        let rust_type = if input.peek(LitStr) {
+            input.parse()?
+        } else {
+            let ident: Ident = input.parse()?;
+            LitStr::new(&ident.to_string(), ident.span())
+        };
+        input.parse::<Token![=]>()?;
+        Ok((rust_type, input.parse()?))
+    }
+    
+    let mut type_map: Vec<(String, String)> = Vec::new();
+    for attr in attrs {
+        let name = match attr.path.get_ident() {
+            Some(ident) if ident == "type_map" || ident == "sql_type_map" => ident.to_string(),
+            _ => continue,
+        };
+        let entries = attr
+            .parse_args_with(|input: ParseStream| Punctuated::<_, Token![,]>::parse_terminated_with(input, parse_entry))
+            .map_err(|_| syn::Error::new_spanned(attr, format!("expected `#[{}(RustType = \"SQL TYPE\", ...)]`", name)))?;
+        for (rust_type, sql_type) in entries {
+            if type_map.iter().any(|(name, _)| *name == rust_type.value()) {
+                return Err(syn::Error::new_spanned(&rust_type, format!("duplicate type_map entry for `{}`", rust_type.value())));
+            }
+            type_map.push((rust_type.value(), sql_type.value()));
+        }
+    }
+    Ok(type_map)
+}
+
+/// 解析 #[has_many(Post, foreign_key = "user_id")]，可选 `name = "..."` 指定方法名，可出现多次
+fn parse_has_many(attrs: &[Attribute], backend: Backend) -> syn::Result<Vec<HasMany>> {
+    let mut relations: Vec<HasMany> = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("has_many")) {
+        let form_error = || syn::Error::new_spanned(attr, "expected `#[has_many(Child, foreign_key = \"...\")]`");
+        let (child, options) = attr
+            .parse_args_with(|input: ParseStream| {
+                let child: Path = input.parse()?;
+                let options = if input.is_empty() {
+                    Punctuated::new()
+                } else {
+                    input.parse::<Token![,]>()?;
+                    Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated(input)?
+                };
+                Ok((child, options))
+            })
+            .map_err(|_| form_error())?;
+        let mut foreign_key = None;
+        let mut method = None;
+        for option in options {
+            let value = match &option.lit {
+                Lit::Str(lit_str) => lit_str,
+                lit => return Err(syn::Error::new_spanned(lit, "expected a string literal")),
+            };
+            if option.path.is_ident("foreign_key") {
+                quote_ident(backend, &value.value()).map_err(|msg| syn::Error::new_spanned(value, msg))?;
+                foreign_key = Some(value.value());
+            } else if option.path.is_ident("name") {
+                method = Some(value.parse::<Ident>()?);
+            } else {
+                return Err(syn::Error::new_spanned(&option.path, "unknown has_many option, expected `foreign_key` or `name`"));
+            }
+        }
+        let foreign_key = foreign_key.ok_or_else(form_error)?;
+        let method = match method {
+            Some(method) => method,
+            None => {
+                let child_name = child.segments.last().map(|segment| segment.ident.to_string()).unwrap_or_default();
+                format_ident!("{}", pluralize(&camel_to_snake(&child_name)))
+            }
+        };
+        if relations.iter().any(|relation| relation.method == method) {
+            return Err(syn::Error::new_spanned(attr, format!("duplicate has_many method `{}`, use `name = \"...\"` to rename one", method)));
+        }
+        relations.push(HasMany { child, foreign_key, method });
+    }
+    Ok(relations)
+}
+
+/// 解析 #[projection(UserSummary: id, name)]，可出现多次，字段必须是结构体中对应列的字段
+fn parse_projections(attrs: &[Attribute], fields: &[ParsedField]) -> syn::Result<Vec<Projection>> {
+    let mut projections: Vec<Projection> = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("projection")) {
+        let (name, idents) = attr
+            .parse_args_with(|input: ParseStream| {
+                let name: Ident = input.parse()?;
+                input.parse::<Token![:]>()?;
+                let idents = Punctuated::<Ident, Token![,]>::parse_terminated_with(input, Ident::parse_any)?;
+                Ok((name, idents))
+            })
+            .map_err(|_| syn::Error::new_spanned(attr, "expected `#[projection(Name: field, ...)]`"))?;
+        if idents.is_empty() {
+            return Err(syn::Error::new_spanned(attr, "a projection needs at least one field"));
+        }
+        if projections.iter().any(|projection| projection.name == name) {
+            return Err(syn::Error::new_spanned(&name, format!("duplicate projection `{}`", name)));
+        }
+        let mut projected: Vec<ParsedField> = Vec::new();
+        for ident in idents {
+            let field = fields.iter()
+                .find(|f| f.flattened.is_none() && f.ident.unraw() == ident.unraw())
+                .ok_or_else(|| syn::Error::new_spanned(&ident, format!("`{}` is not a column field of this struct", ident)))?;
+            if projected.iter().any(|f| f.ident == field.ident) {
+                return Err(syn::Error::new_spanned(&ident, format!("duplicate projection field `{}`", ident)));
+            }
+            projected.push(field.clone());
+        }
+        projections.push(Projection { name, fields: projected });
+    }
+    Ok(projections)
+}
+
+/// 解析结构体上的 #[unique(a, b, ...)]，将字段名解析为列名
+fn parse_unique_groups(attrs: &[Attribute], fields: &[ParsedField]) -> syn::Result<Vec<Vec<String>>> {
+    let mut groups: Vec<Vec<String>> = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("unique")) {
+        let idents = attr
+            .parse_args_with(|input: ParseStream| Punctuated::<Ident, Token![,]>::parse_terminated_with(input, Ident::parse_any))
+            .map_err(|_| syn::Error::new_spanned(attr, "expected `#[unique(field, ...)]`"))?;
+        if idents.is_empty() {
+            return Err(syn::Error::new_spanned(attr, "a unique constraint needs at least one field"));
+        }
+        let mut columns: Vec<String> = Vec::new();
+        for ident in idents {
+            let field = fields.iter()
+                .find(|f| f.flattened.is_none() && f.ident.unraw() == ident.unraw())
+                .ok_or_else(|| syn::Error::new_spanned(&ident, format!("`{}` is not a column field of this struct", ident)))?;
+            if columns.contains(&field.name) {
+                return Err(syn::Error::new_spanned(&ident, format!("duplicate unique field `{}`", ident)));
+            }
+            columns.push(field.name.clone());
+        }
+        if groups.contains(&columns) {
+            return Err(syn::Error::new_spanned(attr, "duplicate unique constraint"));
+        }
+        groups.push(columns);
+    }
+    Ok(groups)
+}
+
+/// 解析 #[sql_override(...)]，并校验每条语句的占位符数量与默认语句一致
+fn parse_sql_overrides(attrs: &[Attribute], backend: Backend, fields: &[ParsedField]) -> syn::Result<SqlOverrides> {
+    let mut overrides = SqlOverrides::default();
+    for attr in attrs {
+        if !attr.path.is_ident("sql_override") {
+            continue;
+        }
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            _ => return Err(syn::Error::new_spanned(attr, "expected `#[sql_override(insert = \"...\")]`")),
+        };
+        for nested in list.nested {
+            let meta = match nested {
+                NestedMeta::Meta(Meta::NameValue(meta)) => meta,
+                other => return Err(syn::Error::new_spanned(other, "expected `statement = \"...\"`")),
+            };
+            let key = meta.path.get_ident().map(|i| i.to_string()).unwrap_or_default();
+            let lit = match meta.lit {
+                Lit::Str(lit) => lit,
+                other => return Err(syn::Error::new_spanned(other, "expected a string literal")),
+            };
+            let (slot, expected) = match key.as_str() {
+                "insert" => (&mut overrides.insert, fields.iter().filter(|f| !f.is_auto_increment).count()),
+                "update" => (&mut overrides.update, fields.len()),
+                "delete" => (&mut overrides.delete, 1),
+                "select_all" => (&mut overrides.select_all, 0),
+                "select_by_id" => (&mut overrides.select_by_id, 1),
+                _ => {
+                    let message = match did_you_mean(&key, SQL_OVERRIDES) {
+                        Some(suggestion) => format!("unknown sql_override statement `{}`, did you mean `{}`?", key, suggestion),
+                        None => format!("unknown sql_override statement `{}`, expected one of: {}", key, SQL_OVERRIDES.join(", ")),
+                    };
+                    return Err(syn::Error::new_spanned(&meta.path, message));
+                }
+            };
+            let sql = lit.value();
+            let found = count_placeholders(backend, &sql);
+            if found != expected {
+                return Err(syn::Error::new_spanned(
+                    &lit,
+                    format!("`{}` override has {} placeholder(s), but the generated statement binds {}", key, found, expected),
+                ));
+            }
+            if slot.replace(sql).is_some() {
+                return Err(syn::Error::new_spanned(&meta.path, format!("duplicate sql_override statement `{}`", key)));
+            }
+        }
+    }
+    Ok(overrides)
 }
 
 /// 解析结构体字段
-pub fn parse_field(field: &Field) -> ParsedField {
-    let name = field.ident.as_ref().unwrap().to_string();
-    let ty = field.ty.clone();
+pub fn parse_field(field: &Field, backend: Backend, type_map: &[(String, String)]) -> syn::Result<ParsedField> {
+    let ident = field.ident.clone().unwrap();
+    let ty = normalize_type(&field.ty);
     
-    // 检查是否有自定义SQL类型
+    // 检查是否有自定义SQL类型和列名
     let mut sql_type = None;
+    let mut column = None;
     for attr in &field.attrs {
         if attr.path.is_ident("sql_type") {
             if let Ok(Meta::NameValue(meta)) = attr.parse_meta() {
                 if let Lit::Str(lit_str) = meta.lit {
-                    sql_type = Some(lit_str.value());
+                    sql_type = Some(SqlType::Custom(lit_str.value()));
+                }
+            }
+        }
+        if attr.path.is_ident("column") {
+            if let Ok(Meta::NameValue(meta)) = attr.parse_meta() {
+                if let Lit::Str(lit_str) = meta.lit {
+                    column = Some(lit_str.value());
                 }
             }
         }
     }
+    // 原始标识符（如 r#type）的列名去掉 r# 前缀，绑定时仍使用原始标识符
+    let name = column.unwrap_or_else(|| ident.unraw().to_string());
     
     // 检查是否是主键
     let is_primary_key = field.attrs.iter().any(|attr| attr.path.is_ident("primary_key"));
     
+    // 检查是否是自增列
+    let is_auto_increment = field.attrs.iter().any(|attr| attr.path.is_ident("auto_increment"));
+    
+    // 检查是否需要生成按列查询的方法
+    let is_queryable = field.attrs.iter().any(|attr| attr.path.is_ident("queryable"));
+    
+    // 检查是否需要生成按 LIKE 模式查询的方法，只对文本列有意义
+    let is_searchable = match field.attrs.iter().find(|attr| attr.path.is_ident("searchable")) {
+        Some(attr) if !is_string_type(option_inner(&ty)) => {
+            return Err(syn::Error::new_spanned(attr, "#[searchable] can only be used on string fields"));
+        }
+        Some(_) => true,
+        None => false,
+    };
+    
+    // 检查是否有唯一约束
+    let is_unique = field.attrs.iter().any(|attr| attr.path.is_ident("unique"));
+    
+    // 检查是否是计数列
+    let is_counter = field.attrs.iter().any(|attr| attr.path.is_ident("counter"));
+    
+    // 检查是否需要生成按 JSON 路径查询的方法
+    let is_json_indexed = field.attrs.iter().any(|attr| attr.path.is_ident("json_indexed"));
+    
+    // 检查批量 upsert 时是否保留已有的非空值，只对可为空的列有意义
+    let is_upsert_coalesce = match field.attrs.iter().find(|attr| attr.path.is_ident("upsert_coalesce")) {
+        Some(attr) if !is_option(&ty) => {
+            return Err(syn::Error::new_spanned(attr, "#[upsert_coalesce] requires an `Option<T>` field"));
+        }
+        Some(_) => true,
+        None => false,
+    };
+    
     // 提取注释
     let comment = extract_comment(&field.attrs);
     
-    ParsedField {
+    // 收集校验函数，可重复指定
+    let validators = field.attrs.iter()
+        .filter(|attr| attr.path.is_ident("validate"))
+        .map(parse_validator)
+        .collect::<syn::Result<Vec<_>>>()?;
+    
+    let belongs_to = match field.attrs.iter().find(|attr| attr.path.is_ident("belongs_to")) {
+        Some(attr) => Some(parse_belongs_to(attr)?),
+        None => None,
+    };
+    
+    // #[decimal(...)] 细化 NUMERIC 的精度，只能用于小数字段
+    let decimal = match field.attrs.iter().find(|attr| attr.path.is_ident("decimal")) {
+        Some(attr) if sql_type.is_some() => {
+            return Err(syn::Error::new_spanned(attr, "#[decimal(...)] cannot be combined with #[sql_type]"));
+        }
+        Some(attr) if !is_decimal_type(&ty) => {
+            return Err(syn::Error::new_spanned(attr, "#[decimal(...)] can only be used on `Decimal`, `f32` or `f64` fields"));
+        }
+        Some(attr) => Some(parse_decimal(attr)?),
+        None => None,
+    };
+    
+    // #[sql_transparent = "i64"] 按声明的内部类型推导新类型字段的列类型
+    let transparent = match field.attrs.iter().find(|attr| attr.path.is_ident("sql_transparent")) {
+        Some(attr) if sql_type.is_some() => {
+            return Err(syn::Error::new_spanned(attr, "#[sql_transparent] cannot be combined with #[sql_type]"));
+        }
+        Some(attr) => {
+            let inner = get_string_attr(std::slice::from_ref(attr), "sql_transparent").unwrap_or_default();
+            Some(map_transparent_type(&inner, backend, type_map).map_err(|msg| syn::Error::new_spanned(attr, msg))?)
+        }
+        None => None,
+    };
+    let sql_type = sql_type.or(transparent);
+    
+    // 检查是否在插入时生成 UUID，#[primary_key(generate = "uuid_v4")] 与 #[uuid_generate] 等价
+    let mut uuid_generate_attr = field.attrs.iter().find(|attr| attr.path.is_ident("uuid_generate"));
+    for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("primary_key")) {
+        if parse_primary_key_generate(attr)?.is_some() {
+            uuid_generate_attr = Some(attr);
+        }
+    }
+    let is_uuid_generate = match uuid_generate_attr {
+        Some(attr) if !is_uuid_type(&ty) => {
+            return Err(syn::Error::new_spanned(attr, "UUID generation requires a `Uuid` field"));
+        }
+        Some(_) => true,
+        None => false,
+    };
+    
+    // #[uuid_storage = "..."] 选择 SQLite 上 UUID 列的存储类型
+    let uuid_storage = match field.attrs.iter().find(|attr| attr.path.is_ident("uuid_storage")) {
+        Some(attr) if backend != Backend::Sqlite || !is_uuid_type(option_inner(&ty)) => {
+            return Err(syn::Error::new_spanned(attr, "#[uuid_storage] is only supported on `Uuid` fields with the sqlite backend"));
+        }
+        Some(attr) if sql_type.is_some() => {
+            return Err(syn::Error::new_spanned(attr, "#[uuid_storage] cannot be combined with #[sql_type]"));
+        }
+        Some(attr) => match get_string_attr(std::slice::from_ref(attr), "uuid_storage").as_deref() {
+            Some("text") => Some(SqlType::Text { len: None }),
+            Some("blob") => Some(SqlType::Blob),
+            _ => return Err(syn::Error::new_spanned(attr, "expected `#[uuid_storage = \"text\"]` or `#[uuid_storage = \"blob\"]`")),
+        },
+        None => None,
+    };
+    let sql_type = sql_type.or(uuid_storage);
+    
+    // #[sql_enum(...)] 决定枚举列的存储类型和允许值
+    let (sql_enum_type, allowed_values) = match field.attrs.iter().find(|attr| attr.path.is_ident("sql_enum")) {
+        Some(attr) if sql_type.is_some() || decimal.is_some() => {
+            return Err(syn::Error::new_spanned(attr, "#[sql_enum(...)] cannot be combined with #[sql_type] or #[decimal]"));
+        }
+        Some(attr) => {
+            let (enum_type, values) = parse_sql_enum(attr)?;
+            // MySQL 的 TEXT 列不能直接作为主键或唯一键，键列改用 VARCHAR
+            let enum_type = match enum_type {
+                SqlType::Text { len: None } if backend == Backend::MySql && (is_primary_key || is_unique) => SqlType::Text { len: Some(255) },
+                enum_type => enum_type,
+            };
+            (Some(enum_type), values)
+        }
+        None => (None, Vec::new()),
+    };
+    let sql_type = sql_type.or(sql_enum_type);
+    
+    // #[duration(millis)] 把 `Duration` 存为整数，列类型与 i64 相同
+    let duration = match field.attrs.iter().find(|attr| attr.path.is_ident("duration")) {
+        Some(attr) if !is_duration_type(option_inner(&ty)) => {
+            return Err(syn::Error::new_spanned(attr, "#[duration(...)] can only be used on `Duration` or `Option<Duration>` fields"));
+        }
+        Some(attr) if sql_type.is_some() => {
+            return Err(syn::Error::new_spanned(attr, "#[duration(...)] cannot be combined with #[sql_type]"));
+        }
+        Some(attr) => Some(parse_duration(attr)?),
+        None => None,
+    };
+    
+    // #[ip_as_text] 把 IP 地址存为文本，最长的 IPv6 文本形式为 45 个字符
+    let ip_as_text = match field.attrs.iter().find(|attr| attr.path.is_ident("ip_as_text")) {
+        Some(attr) if !is_ip_addr_type(option_inner(&ty)) => {
+            return Err(syn::Error::new_spanned(attr, "#[ip_as_text] can only be used on `IpAddr`, `Ipv4Addr` or `Ipv6Addr` fields"));
+        }
+        Some(attr) if sql_type.is_some() => {
+            return Err(syn::Error::new_spanned(attr, "#[ip_as_text] cannot be combined with #[sql_type]"));
+        }
+        Some(_) => true,
+        None => false,
+    };
+    
+    // #[max_length = N] 和 #[text] 调整字符串列的类型，默认为 VARCHAR(255)
+    let max_length_attr = field.attrs.iter().find(|attr| attr.path.is_ident("max_length"));
+    let text_attr = field.attrs.iter().find(|attr| attr.path.is_ident("text"));
+    if let Some(attr) = max_length_attr.or(text_attr) {
+        if !is_string_type(option_inner(&ty)) {
+            return Err(syn::Error::new_spanned(attr, "#[max_length] and #[text] can only be used on string fields"));
+        }
+        if sql_type.is_some() {
+            return Err(syn::Error::new_spanned(attr, "#[max_length] and #[text] cannot be combined with #[sql_type]"));
+        }
+        if let (Some(_), Some(text)) = (max_length_attr, text_attr) {
+            return Err(syn::Error::new_spanned(text, "#[text] cannot be combined with #[max_length]"));
+        }
+    }
+    let max_length = match max_length_attr.map(|attr| attr.parse_meta()) {
+        Some(Ok(Meta::NameValue(meta))) => match &meta.lit {
+            Lit::Int(lit_int) => match lit_int.base10_parse::<u32>()? {
+                0 => return Err(syn::Error::new_spanned(lit_int, "#[max_length] must be positive")),
+                n => Some(n),
+            },
+            _ => None,
+        },
+        _ => None,
+    };
+    let sql_type = match (max_length, text_attr) {
+        (Some(n), _) => Some(SqlType::Text { len: Some(n) }),
+        (None, Some(_)) => Some(SqlType::Text { len: None }),
+        (None, None) => sql_type,
+    };
+    
+    let (sql_type, codec) = match duration {
+        Some(unit) => (Some(map_transparent_type("i64", backend, type_map).map_err(|msg| syn::Error::new_spanned(&field.ty, msg))?), Some(ColumnCodec::Duration(unit))),
+        None if ip_as_text => (Some(SqlType::Text { len: Some(45) }), Some(ColumnCodec::IpText)),
+        None if is_char_type(option_inner(&ty)) => (sql_type, Some(ColumnCodec::Char)),
+        None => (sql_type, None),
+    };
+    
+    // 未指定 #[sql_type] 时按Rust类型推导，无法映射的类型报错
+    let sql_type = match (sql_type, decimal) {
+        (Some(sql_type), _) => sql_type,
+        (None, Some(precision)) => SqlType::Decimal { precision: Some(precision) },
+        (None, None) => crate::utils::map_type_to_sql(&ty, backend, type_map)
+            .map_err(|msg| syn::Error::new_spanned(&field.ty, format!("field `{}`: {}", ident, msg)))?,
+    };
+    
+    Ok(ParsedField {
+        ident,
         name,
         ty: ty.clone(),
-        sql_type: sql_type.unwrap_or_else(|| crate::utils::map_type_to_sql(&ty)),
+        sql_type,
         is_primary_key,
+        is_auto_increment,
+        is_queryable,
+        is_searchable,
+        is_unique,
+        is_counter,
+        is_json_indexed,
+        is_uuid_generate,
+        allowed_values,
+        max_length,
+        is_upsert_coalesce,
+        codec,
         comment,
+        validators,
+        belongs_to,
+        flattened: None,
+    })
+}
+
+/// 解析 #[flatten(prefix = "address_", fields(street: "VARCHAR(255)", ...))]，每个子字段展开为一列
+///
+/// 宏看不到嵌入结构体的定义，子字段及其列类型需要逐一列出；`prefix` 默认为 `<字段名>_`
+fn parse_flatten(field: &Field, attr: &Attribute) -> syn::Result<Vec<ParsedField>> {
+    let ident = field.ident.clone().unwrap();
+    let form_error = || syn::Error::new_spanned(attr, "expected `#[flatten(prefix = \"...\", fields(name: \"SQL TYPE\", ...))]`");
+    let (prefix, members) = attr
+        .parse_args_with(|input: ParseStream| {
+            let mut prefix = None;
+            let mut members = None;
+            while !input.is_empty() {
+                let key: Ident = input.parse()?;
+                if key == "prefix" {
+                    input.parse::<Token![=]>()?;
+                    prefix = Some(input.parse::<LitStr>()?.value());
+                } else if key == "fields" {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let parsed = Punctuated::<(Ident, LitStr), Token![,]>::parse_terminated_with(&content, |input: ParseStream| {
+                        let member = input.call(Ident::parse_any)?;
+                        input.parse::<Token![:]>()?;
+                        Ok((member, input.parse::<LitStr>()?))
+                    })?;
+                    members = Some(parsed.into_iter().collect::<Vec<_>>());
+                } else {
+                    return Err(syn::Error::new_spanned(&key, "unknown flatten option"));
+                }
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+            }
+            Ok((prefix, members))
+        })
+        .map_err(|_| form_error())?;
+    let members = members.filter(|members| !members.is_empty()).ok_or_else(form_error)?;
+    let prefix = prefix.unwrap_or_else(|| format!("{}_", ident.unraw()));
+    let comment = extract_comment(&field.attrs);
+    Ok(members.into_iter().map(|(member, sql_type)| ParsedField {
+        ident: format_ident!("{}_{}", ident.unraw(), member.unraw()),
+        name: format!("{}{}", prefix, member.unraw()),
+        ty: parse_quote!(_),
+        sql_type: SqlType::Custom(sql_type.value()),
+        is_primary_key: false,
+        is_auto_increment: false,
+        is_queryable: false,
+        is_searchable: false,
+        is_unique: false,
+        is_counter: false,
+        is_json_indexed: false,
+        is_uuid_generate: false,
+        allowed_values: Vec::new(),
+        max_length: None,
+        is_upsert_coalesce: false,
+        codec: None,
+        comment: comment.clone(),
+        validators: Vec::new(),
+        belongs_to: None,
+        flattened: Some(Flattened { field: ident.clone(), ty: field.ty.clone(), member }),
+    }).collect())
+}
+
+/// 解析 #[belongs_to(User)]，可选 `method = "..."` 指定方法名
+fn parse_belongs_to(attr: &Attribute) -> syn::Result<BelongsTo> {
+    let form_error = || syn::Error::new_spanned(attr, "expected `#[belongs_to(Parent)]` or `#[belongs_to(Parent, method = \"...\")]`");
+    let (parent, options) = attr
+        .parse_args_with(|input: ParseStream| {
+            let parent: Path = input.parse()?;
+            let options = if input.is_empty() {
+                Punctuated::new()
+            } else {
+                input.parse::<Token![,]>()?;
+                Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated(input)?
+            };
+            Ok((parent, options))
+        })
+        .map_err(|_| form_error())?;
+    let mut method = None;
+    for option in options {
+        match (&option.lit, option.path.is_ident("method")) {
+            (Lit::Str(lit_str), true) => method = Some(lit_str.parse::<Ident>()?),
+            (_, true) => return Err(syn::Error::new_spanned(&option.lit, "expected a string literal")),
+            _ => return Err(syn::Error::new_spanned(&option.path, "unknown belongs_to option, expected `method`")),
+        }
+    }
+    let method = match method {
+        Some(method) => method,
+        None => {
+            let parent_name = parent.segments.last().map(|segment| segment.ident.to_string()).unwrap_or_default();
+            format_ident!("{}", camel_to_snake(&parent_name))
+        }
+    };
+    Ok(BelongsTo { parent, method })
+}
+
+/// 解析 #[validate(with = "path::to::fn")]
+fn parse_validator(attr: &Attribute) -> syn::Result<Path> {
+    if let Meta::List(list) = attr.parse_meta()? {
+        if let [NestedMeta::Meta(Meta::NameValue(meta))] = list.nested.iter().collect::<Vec<_>>().as_slice() {
+            if let (true, Lit::Str(lit_str)) = (meta.path.is_ident("with"), &meta.lit) {
+                return lit_str.parse();
+            }
+        }
+    }
+    Err(syn::Error::new_spanned(attr, "expected `#[validate(with = \"path::to::fn\")]`"))
+}
+
+/// 解析 #[duration(seconds)]、#[duration(millis)] 或 #[duration(micros)]
+fn parse_duration(attr: &Attribute) -> syn::Result<DurationUnit> {
+    if let Meta::List(list) = attr.parse_meta()? {
+        if let [NestedMeta::Meta(Meta::Path(path))] = list.nested.iter().collect::<Vec<_>>().as_slice() {
+            match path.get_ident().map(|i| i.to_string()).as_deref() {
+                Some("seconds") => return Ok(DurationUnit::Seconds),
+                Some("millis") => return Ok(DurationUnit::Millis),
+                Some("micros") => return Ok(DurationUnit::Micros),
+                _ => {}
+            }
+        }
+    }
+    Err(syn::Error::new_spanned(attr, "expected `#[duration(seconds)]`, `#[duration(millis)]` or `#[duration(micros)]`"))
+}
+
+/// 解析 #[primary_key(generate = "uuid_v4")] 中的生成策略，不带参数时为 None
+fn parse_primary_key_generate(attr: &Attribute) -> syn::Result<Option<String>> {
+    if attr.tokens.is_empty() {
+        return Ok(None);
+    }
+    if let Ok(Meta::List(list)) = attr.parse_meta() {
+        if let [NestedMeta::Meta(Meta::NameValue(meta))] = list.nested.iter().collect::<Vec<_>>().as_slice() {
+            if let (true, Lit::Str(lit_str)) = (meta.path.is_ident("generate"), &meta.lit) {
+                return match lit_str.value().as_str() {
+                    "uuid_v4" => Ok(Some(lit_str.value())),
+                    other => Err(syn::Error::new_spanned(lit_str, format!("unknown generate strategy `{}`, expected `uuid_v4`", other))),
+                };
+            }
+        }
+    }
+    Err(syn::Error::new_spanned(attr, "expected `#[primary_key]` or `#[primary_key(generate = \"uuid_v4\")]`"))
+}
+
+/// 解析 #[decimal(precision = 19, scale = 4)]，`scale` 可省略，默认为 0
+fn parse_decimal(attr: &Attribute) -> syn::Result<(u32, u32)> {
+    let form_error = || syn::Error::new_spanned(attr, "expected `#[decimal(precision = 19, scale = 4)]`");
+    let list = match attr.parse_meta()? {
+        Meta::List(list) => list,
+        _ => return Err(form_error()),
+    };
+    let mut precision = None;
+    let mut scale = None;
+    for nested in &list.nested {
+        match nested {
+            NestedMeta::Meta(Meta::NameValue(meta)) => {
+                let value = match &meta.lit {
+                    Lit::Int(lit_int) => lit_int.base10_parse::<u32>()?,
+                    _ => return Err(syn::Error::new_spanned(&meta.lit, "expected an integer")),
+                };
+                let slot = match meta.path.get_ident().map(|i| i.to_string()).as_deref() {
+                    Some("precision") => &mut precision,
+                    Some("scale") => &mut scale,
+                    _ => return Err(syn::Error::new_spanned(&meta.path, "unknown decimal option, expected `precision` or `scale`")),
+                };
+                if slot.replace(value).is_some() {
+                    return Err(syn::Error::new_spanned(&meta.path, "duplicate decimal option"));
+                }
+            }
+            _ => return Err(form_error()),
+        }
     }
+    let precision = precision.ok_or_else(form_error)?;
+    let scale = scale.unwrap_or(0);
+    if precision == 0 || scale > precision {
+        return Err(syn::Error::new_spanned(attr, "decimal precision must be positive and not less than scale"));
+    }
+    Ok((precision, scale))
+}
+
+/// 解析 #[sql_enum(text)] 或 #[sql_enum(int, values(0, 1))]，返回列类型和允许值
+fn parse_sql_enum(attr: &Attribute) -> syn::Result<(SqlType, Vec<AllowedValue>)> {
+    let form_error = || syn::Error::new_spanned(attr, "expected `#[sql_enum(text)]` or `#[sql_enum(int)]`, optionally followed by `values(...)`");
+    let list = match attr.parse_meta()? {
+        Meta::List(list) => list,
+        _ => return Err(form_error()),
+    };
+    let mut nested = list.nested.iter();
+    let text = match nested.next() {
+        Some(NestedMeta::Meta(Meta::Path(path))) if path.is_ident("text") => true,
+        Some(NestedMeta::Meta(Meta::Path(path))) if path.is_ident("int") => false,
+        _ => return Err(form_error()),
+    };
+    let mut values = Vec::new();
+    match nested.next() {
+        Some(NestedMeta::Meta(Meta::List(list))) if list.path.is_ident("values") => {
+            for value in &list.nested {
+                values.push(match (text, value) {
+                    (true, NestedMeta::Lit(Lit::Str(lit_str))) => AllowedValue::Text(lit_str.value()),
+                    (false, NestedMeta::Lit(Lit::Int(lit_int))) => AllowedValue::Int(lit_int.base10_parse()?),
+                    (true, _) => return Err(syn::Error::new_spanned(value, "expected a string literal")),
+                    (false, _) => return Err(syn::Error::new_spanned(value, "expected an integer literal")),
+                });
+            }
+            if values.is_empty() {
+                return Err(syn::Error::new_spanned(list, "values(...) must list at least one value"));
+            }
+        }
+        Some(_) => return Err(form_error()),
+        None => {}
+    }
+    if nested.next().is_some() {
+        return Err(form_error());
+    }
+    Ok((if text { SqlType::Text { len: None } } else { SqlType::Custom("INTEGER".into()) }, values))
+}
+
+/// 解析 skip(...) 列表
+fn parse_skip_list(list: &MetaList, skip: &mut SkipFlags) -> syn::Result<()> {
+    for nested in &list.nested {
+        let flag = match nested {
+            NestedMeta::Meta(Meta::Path(path)) => match path.get_ident().map(|i| i.to_string()).as_deref() {
+                Some("insert") => &mut skip.insert,
+                Some("update") => &mut skip.update,
+                Some("delete") => &mut skip.delete,
+                Some("find") => &mut skip.find,
+                Some("init_table") => &mut skip.init_table,
+                _ => return Err(syn::Error::new_spanned(
+                    path,
+                    "unknown method in skip(...), expected one of: insert, update, delete, find, init_table",
+                )),
+            },
+            _ => return Err(syn::Error::new_spanned(nested, "expected a method name")),
+        };
+        *flag = true;
+    }
+    Ok(())
+}
+
+/// 解析 insert_model(derive(...))，返回为 `New<Name>` 派生的 trait
+fn parse_insert_model(list: &MetaList) -> syn::Result<Vec<Path>> {
+    let form_error = || syn::Error::new_spanned(list, "expected `insert_model` or `insert_model(derive(Debug, Clone, ...))`");
+    match list.nested.iter().collect::<Vec<_>>().as_slice() {
+        [NestedMeta::Meta(Meta::List(derive))] if derive.path.is_ident("derive") => derive.nested.iter()
+            .map(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => Ok(path.clone()),
+                _ => Err(form_error()),
+            })
+            .collect(),
+        _ => Err(form_error()),
+    }
+}
+
+/// 解析 busy_retry(...) 列表
+fn parse_busy_retry(list: &MetaList) -> syn::Result<BusyRetry> {
+    let mut retry = BusyRetry::default();
+    for nested in &list.nested {
+        match nested {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("reads") => retry.reads = true,
+            NestedMeta::Meta(Meta::NameValue(meta)) => {
+                let value = match &meta.lit {
+                    Lit::Int(lit_int) => lit_int.base10_parse::<u64>()?,
+                    lit => return Err(syn::Error::new_spanned(lit, "expected an integer")),
+                };
+                match meta.path.get_ident().map(|i| i.to_string()).as_deref() {
+                    Some("attempts") if value > 0 && value <= u32::MAX as u64 => retry.attempts = value as u32,
+                    Some("attempts") => return Err(syn::Error::new_spanned(&meta.lit, "attempts must be at least 1")),
+                    Some("backoff_ms") => retry.backoff_ms = value,
+                    Some("max_backoff_ms") => retry.max_backoff_ms = value,
+                    _ => return Err(syn::Error::new_spanned(
+                        &meta.path,
+                        "unknown busy_retry option, expected one of: attempts, backoff_ms, max_backoff_ms, reads",
+                    )),
+                }
+            }
+            _ => return Err(syn::Error::new_spanned(nested, "expected `name = value` or `reads`")),
+        }
+    }
+    Ok(retry)
+}
+
+/// 解析结构体上的 #[sql_crud(...)] 属性
+pub fn parse_crud_options(attrs: &[Attribute], struct_name: &Ident) -> syn::Result<CrudOptions> {
+    let mut options = CrudOptions::default();
+    for attr in attrs {
+        if !attr.path.is_ident("sql_crud") {
+            continue;
+        }
+        if let Meta::List(list) = attr.parse_meta()? {
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("repository") => {
+                        options.repository = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("from_row") => {
+                        options.from_row = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("tracing") => {
+                        options.tracing = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("hooks") => {
+                        options.hooks = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("raw_query") => {
+                        options.raw_query = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("blocking") => {
+                        options.blocking = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("builder") => {
+                        options.builder = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("update_returning") => {
+                        options.update_returning = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("strict") => {
+                        options.strict = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("without_rowid") => {
+                        options.without_rowid = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("strict_create") => {
+                        options.strict_create = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("init_options") => {
+                        options.init_options = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("runtime_prefix") => {
+                        options.runtime_prefix = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("dynamic_table") => {
+                        options.dynamic_table = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("create_schema") => {
+                        options.create_schema = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("self_check") => {
+                        options.self_check = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("insert_present") => {
+                        options.insert_present = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("filter") => {
+                        options.filter = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("stream") => {
+                        options.stream = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("insert_model") => {
+                        options.insert_model = Some(vec![parse_quote!(Debug), parse_quote!(Clone)]);
+                    }
+                    NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("insert_model") => {
+                        options.insert_model = Some(parse_insert_model(&list)?);
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("error") => {
+                        options.error = Some(format_ident!("{}Error", struct_name));
+                    }
+                    NestedMeta::Meta(Meta::NameValue(meta)) if meta.path.is_ident("error") => {
+                        match &meta.lit {
+                            Lit::Str(lit_str) => options.error = Some(lit_str.parse()?),
+                            lit => return Err(syn::Error::new_spanned(lit, "expected a type name like \"CrudError\"")),
+                        }
+                    }
+                    NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("skip") => {
+                        parse_skip_list(&list, &mut options.skip)?;
+                    }
+                    NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("busy_retry") => {
+                        options.busy_retry = Some(parse_busy_retry(&list)?);
+                    }
+                    NestedMeta::Meta(Meta::NameValue(meta)) if meta.path.is_ident("backend") => {
+                        options.backend = match &meta.lit {
+                            Lit::Str(lit_str) => match lit_str.value().as_str() {
+                                "sqlite" => Backend::Sqlite,
+                                "postgres" => Backend::Postgres,
+                                "mysql" => Backend::MySql,
+                                "any" => Backend::Any,
+                                _ => return Err(syn::Error::new_spanned(
+                                    lit_str,
+                                    "unknown backend, expected one of: sqlite, postgres, mysql, any",
+                                )),
+                            },
+                            lit => return Err(syn::Error::new_spanned(lit, "expected a string like \"sqlite\"")),
+                        };
+                    }
+                    NestedMeta::Meta(Meta::NameValue(meta)) if meta.path.is_ident("prefix") => {
+                        match &meta.lit {
+                            Lit::Str(lit_str) => {
+                                let prefix = lit_str.value();
+                                if !prefix.is_empty() && syn::parse_str::<Ident>(&format!("{}insert", prefix)).is_err() {
+                                    return Err(syn::Error::new_spanned(lit_str, "method prefix must form valid identifiers"));
+                                }
+                                options.prefix = prefix;
+                            }
+                            lit => return Err(syn::Error::new_spanned(lit, "expected a string like \"db_\"")),
+                        }
+                    }
+                    NestedMeta::Meta(Meta::NameValue(meta)) if meta.path.is_ident("vis") => {
+                        match &meta.lit {
+                            Lit::Str(lit_str) => options.vis = Some(lit_str.parse()?),
+                            lit => return Err(syn::Error::new_spanned(lit, "expected a string like \"pub(crate)\"")),
+                        }
+                    }
+                    other => return Err(unknown_option_error(&other)),
+                }
+            }
+        }
+    }
+    Ok(options)
+}
+
+/// 校验 STRICT 表的列类型是 SQLite 允许的类型之一
+fn validate_strict_type(field: &Field, parsed_field: &ParsedField) -> syn::Result<()> {
+    const STRICT_TYPES: &[&str] = &["INT", "INTEGER", "REAL", "TEXT", "BLOB", "ANY"];
+    let column_type = crate::sql_generator::column_type(&parsed_field.sql_type, Backend::Sqlite);
+    let sql_type = column_type.to_uppercase();
+    if STRICT_TYPES.contains(&sql_type.as_str()) {
+        return Ok(());
+    }
+    let hint = if sql_type.starts_with("VARCHAR") || sql_type.starts_with("CHAR") {
+        "use #[text] instead of a length limit"
+    } else {
+        "use #[sql_type = \"...\"] with one of INT, INTEGER, REAL, TEXT, BLOB or ANY"
+    };
+    Err(syn::Error::new_spanned(field, format!(
+        "column type `{}` is not allowed in a STRICT table; {}", column_type, hint)))
+}
+
+/// 校验 #[auto_increment] 只用于唯一的整数主键
+fn validate_auto_increment(field: &Field, parsed_field: &ParsedField, backend: Backend, seen: bool) -> syn::Result<()> {
+    if seen {
+        return Err(syn::Error::new_spanned(field, "only one #[auto_increment] field is allowed"));
+    }
+    if !parsed_field.is_primary_key {
+        return Err(syn::Error::new_spanned(field, "#[auto_increment] can only be used on the #[primary_key] field"));
+    }
+    if !is_integer_type(&parsed_field.ty) {
+        return Err(syn::Error::new_spanned(&field.ty, "#[auto_increment] requires an integer field type"));
+    }
+    if backend == Backend::Any {
+        return Err(syn::Error::new_spanned(field, "#[auto_increment] is not supported with backend = \"any\""));
+    }
+    Ok(())
 }
 
+/// 未指定 #[table_prefix] 时作为表名前缀的环境变量，在宏展开时读取
+const TABLE_PREFIX_ENV: &str = "SQL_CRUD_TABLE_PREFIX";
+
 /// 解析结构体定义
-pub fn parse_struct(input: &DeriveInput) -> ParsedStruct {
+pub fn parse_struct(input: &DeriveInput) -> syn::Result<ParsedStruct> {
+    validate_attrs(&input.attrs, &[], STRUCT_VALUE_ATTRS, STRUCT_LIST_ATTRS, "a struct")?;
+    
     let name = input.ident.to_string();
-    let table_name = get_table_name(&input.attrs, &name);
+    // #[table_prefix] 优先，包括用空字符串取消项目级的默认前缀
+    let table_prefix = get_string_attr(&input.attrs, "table_prefix")
+        .or_else(|| std::env::var(TABLE_PREFIX_ENV).ok())
+        .unwrap_or_default();
+    let table_name = format!("{}{}", table_prefix, get_table_name(&input.attrs, &name)?);
     let comment = extract_comment(&input.attrs);
+    let sql_case = get_sql_case(&input.attrs)?;
+    let mut options = parse_crud_options(&input.attrs, &input.ident)?;
+    let mut type_map = parse_type_map(&input.attrs)?;
+    // 前缀同样加在显式的读写表名上
+    let read_table = get_string_attr(&input.attrs, "read_table")
+        .map_or_else(|| table_name.clone(), |table| format!("{}{}", table_prefix, table));
+    let write_table = get_string_attr(&input.attrs, "write_table")
+        .map_or_else(|| table_name.clone(), |table| format!("{}{}", table_prefix, table));
+    let schema = get_string_attr(&input.attrs, "schema");
+    for table in [&table_name, &read_table, &write_table].into_iter().chain(&schema) {
+        quote_ident(options.backend, table)
+            .map_err(|msg| syn::Error::new_spanned(&input.ident, msg))?;
+    }
+    if options.create_schema && (schema.is_none() || options.backend != Backend::Postgres) {
+        return Err(syn::Error::new_spanned(&input.ident, "#[sql_crud(create_schema)] requires #[schema = \"...\"] and the postgres backend"));
+    }
+    
+    if options.busy_retry.is_some() && options.backend != Backend::Sqlite {
+        return Err(syn::Error::new_spanned(&input.ident, "#[sql_crud(busy_retry(...))] is only supported with the sqlite backend"));
+    }
     
+    if (options.strict || options.without_rowid) && options.backend != Backend::Sqlite {
+        return Err(syn::Error::new_spanned(&input.ident, "#[sql_crud(strict)] and #[sql_crud(without_rowid)] are only supported with the sqlite backend"));
+    }
+    if options.init_options && options.backend != Backend::Sqlite {
+        return Err(syn::Error::new_spanned(&input.ident, "#[sql_crud(init_options)] sets SQLite pragmas and is only supported with the sqlite backend"));
+    }
+    if options.init_options && options.skip.init_table {
+        return Err(syn::Error::new_spanned(&input.ident, "#[sql_crud(init_options)] cannot be combined with skip(init_table)"));
+    }
+    // STRICT 表只接受少数几种列类型，推导时改用对应的类型，#[type_map] 中的条目仍然优先
+    if options.strict {
+        for (rust_type, sql_type) in STRICT_TYPE_MAP {
+            if !type_map.iter().any(|(name, _)| name == rust_type) {
+                type_map.push((rust_type.to_string(), sql_type.to_string()));
+            }
+        }
+    }
+    
+    if options.update_returning && options.backend == Backend::MySql {
+        return Err(syn::Error::new_spanned(&input.ident, "#[sql_crud(update_returning)] requires `RETURNING`, which the mysql backend does not support"));
+    }
+    
+    if options.insert_model.is_some() && (options.skip.insert || options.skip.find) {
+        return Err(syn::Error::new_spanned(&input.ident, "#[sql_crud(insert_model)] inserts and reads back the record and cannot be combined with skip(insert) or skip(find)"));
+    }
+    if options.insert_present && options.skip.insert {
+        return Err(syn::Error::new_spanned(&input.ident, "#[sql_crud(insert_present)] cannot be combined with skip(insert)"));
+    }
+    if options.self_check && !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "#[sql_crud(self_check)] is not supported on generic structs"));
+    }
+    if options.filter && !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "#[sql_crud(filter)] is not supported on generic structs"));
+    }
+    if options.insert_model.is_some() && !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "#[sql_crud(insert_model)] is not supported on generic structs"));
+    }
+    
+    if options.repository && !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "#[sql_crud(repository)] is not supported on generic structs"));
+    }
+    
+    let mut skipped_fields = Vec::new();
     let fields = match &input.data {
         Data::Struct(data) => {
             match &data.fields {
                 Fields::Named(fields) => {
-                    fields.named.iter().map(parse_field).collect()
+                    let mut parsed_fields = Vec::new();
+                    let mut columns = HashSet::new();
+                    let mut has_auto_increment = false;
+                    for field in &fields.named {
+                        validate_attrs(&field.attrs, FIELD_FLAG_ATTRS, FIELD_VALUE_ATTRS, FIELD_LIST_ATTRS, "a field")?;
+                        if is_phantom_data(&field.ty) || field.attrs.iter().any(|attr| attr.path.is_ident("skip")) {
+                            skipped_fields.push(field.ident.clone().unwrap());
+                            continue;
+                        }
+                        if let Some(attr) = field.attrs.iter().find(|attr| attr.path.is_ident("flatten")) {
+                            if let Some(other) = field.attrs.iter().find(|attr| ["primary_key", "auto_increment", "queryable", "searchable", "unique", "column", "sql_type"].iter().any(|name| attr.path.is_ident(name))) {
+                                return Err(syn::Error::new_spanned(other, "#[flatten] cannot be combined with column attributes; its columns are listed in `fields(...)`"));
+                            }
+                            if !options.from_row {
+                                return Err(syn::Error::new_spanned(attr, "#[flatten] requires `#[sql_crud(from_row)]` to construct the nested value"));
+                            }
+                            if options.builder || options.insert_model.is_some() {
+                                return Err(syn::Error::new_spanned(attr, "#[flatten] cannot be combined with #[sql_crud(builder)] or #[sql_crud(insert_model)]"));
+                            }
+                            for flattened in parse_flatten(field, attr)? {
+                                quote_ident(options.backend, &flattened.name)
+                                    .map_err(|msg| syn::Error::new_spanned(attr, msg))?;
+                                if !columns.insert(flattened.name.clone()) {
+                                    return Err(syn::Error::new_spanned(attr, format!("duplicate column name `{}`", flattened.name)));
+                                }
+                                parsed_fields.push(flattened);
+                            }
+                            continue;
+                        }
+                        let parsed_field = parse_field(field, options.backend, &type_map)?;
+                        if !parsed_field.validators.is_empty() && options.error.is_none() {
+                            return Err(syn::Error::new_spanned(
+                                field,
+                                "#[validate] requires #[sql_crud(error)] to report validation failures",
+                            ));
+                        }
+                        quote_ident(options.backend, &parsed_field.name)
+                            .map_err(|msg| syn::Error::new_spanned(field, msg))?;
+                        if !columns.insert(parsed_field.name.clone()) {
+                            return Err(syn::Error::new_spanned(
+                                field,
+                                format!("duplicate column name `{}`", parsed_field.name),
+                            ));
+                        }
+                        if parsed_field.is_counter && (parsed_field.is_primary_key || !is_integer_type(&parsed_field.ty)) {
+                            return Err(syn::Error::new_spanned(field, "#[counter] requires an integer field that is not the primary key"));
+                        }
+                        if options.builder && !parsed_field.is_auto_increment && ["build", "insert"].contains(&parsed_field.ident.unraw().to_string().as_str()) {
+                            return Err(syn::Error::new_spanned(field, format!(
+                                "field `{}` conflicts with a method of the generated builder", parsed_field.ident.unraw())));
+                        }
+                        if let (Some(codec), false) = (parsed_field.codec, options.from_row) {
+                            return Err(syn::Error::new_spanned(field, format!("{} requires `#[sql_crud(from_row)]` to decode the stored value", codec.attr())));
+                        }
+                        if parsed_field.is_json_indexed && !matches!(options.backend, Backend::Sqlite | Backend::MySql) {
+                            return Err(syn::Error::new_spanned(field, "#[json_indexed] is only supported with the sqlite and mysql backends"));
+                        }
+                        if parsed_field.is_auto_increment && options.without_rowid {
+                            return Err(syn::Error::new_spanned(field, "#[auto_increment] cannot be used in a WITHOUT ROWID table"));
+                        }
+                        // 自增列总是建为 INTEGER PRIMARY KEY，不受列类型影响
+                        if options.strict && !parsed_field.is_auto_increment {
+                            validate_strict_type(field, &parsed_field)?;
+                        }
+                        if parsed_field.is_auto_increment {
+                            validate_auto_increment(field, &parsed_field, options.backend, has_auto_increment)?;
+                            has_auto_increment = true;
+                        }
+                        parsed_fields.push(parsed_field);
+                    }
+                    parsed_fields
                 },
-                _ => panic!("Only structs with named fields are supported"),
+                _ => return Err(syn::Error::new_spanned(&input.ident, "Only structs with named fields are supported")),
             }
         },
-        _ => panic!("Only structs are supported"),
+        _ => return Err(syn::Error::new_spanned(&input.ident, "Only structs are supported")),
     };
+    let overrides = parse_sql_overrides(&input.attrs, options.backend, &fields)?;
+    let has_many = parse_has_many(&input.attrs, options.backend)?;
+    let projections = parse_projections(&input.attrs, &fields)?;
+    let unique_groups = parse_unique_groups(&input.attrs, &fields)?;
+    if let (Some(projection), false) = (projections.first(), input.generics.params.is_empty()) {
+        return Err(syn::Error::new_spanned(&projection.name, "#[projection(...)] is not supported on generic structs"));
+    }
+    let mut relation_methods: Vec<&Ident> = has_many.iter().map(|relation| &relation.method).collect();
+    for f in &fields {
+        if let Some(relation) = &f.belongs_to {
+            if relation_methods.contains(&&relation.method) {
+                return Err(syn::Error::new_spanned(&f.ident, format!("duplicate relation method `{}`, use `method = \"...\"` to rename one", relation.method)));
+            }
+            relation_methods.push(&relation.method);
+        }
+    }
+    // 覆盖的SQL按原样使用，无法在运行时替换其中的表名
+    if (options.runtime_prefix || options.dynamic_table) && [&overrides.insert, &overrides.update, &overrides.delete, &overrides.select_all, &overrides.select_by_id].iter().any(|sql| sql.is_some()) {
+        return Err(syn::Error::new_spanned(&input.ident, "#[sql_crud(runtime_prefix)] and #[sql_crud(dynamic_table)] cannot be combined with #[sql_override(...)], whose SQL has a fixed table name"));
+    }
+    
+    // 只有主键时没有可更新的列，默认的 UPDATE 语句 SET 子句为空，不生成 update
+    if overrides.update.is_none() && fields.iter().all(|f| f.is_primary_key) {
+        options.skip.update = true;
+    }
     
-    ParsedStruct {
+    Ok(ParsedStruct {
         name,
         table_name,
+        read_table,
+        write_table,
+        schema,
         fields,
         comment,
-    }
+        options,
+        sql_case,
+        generics: input.generics.clone(),
+        skipped_fields,
+        overrides,
+        has_many,
+        projections,
+        unique_groups,
+    })
 }
\ No newline at end of file
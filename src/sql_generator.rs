@@ -1,104 +1,768 @@
-use crate::parser::ParsedStruct;
+use crate::parser::{AllowedValue, Backend, HasMany, ParsedField, ParsedStruct, Projection, SqlCase, SqlType};
+use crate::utils::quote_ident;
 
-/// 生成创建表的SQL语句
-pub fn generate_create_table_sql(parsed: &ParsedStruct) -> String {
-    let mut sql = format!("CREATE TABLE IF NOT EXISTS {} (\n", parsed.table_name);
-    
-    let mut columns = Vec::new();
-    for field in &parsed.fields {
-        let mut column = format!("    {} {}", field.name, field.sql_type);
-        
-        if field.is_primary_key {
-            column.push_str(" PRIMARY KEY");
+/// 按后端规则引用标识符，标识符已在 `parse_struct` 中校验
+fn quoted(parsed: &ParsedStruct, name: &str) -> String {
+    quote_ident(parsed.options.backend, name).expect("identifiers are validated in parse_struct")
+}
+
+/// 按后端规则生成单引号字符串字面量
+///
+/// 单引号一律写成两个单引号；MySQL 默认把反斜杠当作转义符，反斜杠和 NUL 需要再转义，
+/// 其余后端的字符串中不能包含 NUL，直接去掉
+fn quote_string_literal(backend: Backend, s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('\'');
+    for c in s.chars() {
+        match (backend, c) {
+            (_, '\'') => quoted.push_str("''"),
+            (Backend::MySql, '\\') => quoted.push_str("\\\\"),
+            (Backend::MySql, '\0') => quoted.push_str("\\0"),
+            (_, '\0') => {}
+            _ => quoted.push(c),
         }
-        
-        if let Some(comment) = &field.comment {
-            column.push_str(&format!(" COMMENT '{}'", comment.replace('\'', "''")));
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// 按 #[sql_case] 转换关键字大小写，引号内的标识符、字符串不受影响
+fn apply_case(parsed: &ParsedStruct, sql: String) -> String {
+    if parsed.sql_case == SqlCase::Upper {
+        return sql;
+    }
+    let mut result = String::with_capacity(sql.len());
+    let mut quote = None;
+    for c in sql.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if matches!(c, '\'' | '"' | '`') => quote = Some(c),
+            None => {
+                result.extend(c.to_lowercase());
+                continue;
+            }
         }
-        
-        columns.push(column);
+        result.push(c);
+    }
+    result
+}
+
+/// 引用后的表名，指定了 #[schema] 时带上引用后的模式名
+fn qualified(parsed: &ParsedStruct, table: &str) -> String {
+    match &parsed.schema {
+        Some(schema) => format!("{}.{}", quoted(parsed, schema), quoted(parsed, table)),
+        None => quoted(parsed, table),
     }
+}
+
+/// 引用后的写入表名，用于建表、插入、更新和删除
+fn write_table(parsed: &ParsedStruct) -> String {
+    qualified(parsed, &parsed.write_table)
+}
+
+/// 引用后的读取表名，用于查询和统计
+fn read_table(parsed: &ParsedStruct) -> String {
+    qualified(parsed, &parsed.read_table)
+}
+
+/// 生成 Postgres 上建表前创建模式的SQL语句
+pub fn generate_create_schema_sql(parsed: &ParsedStruct) -> Option<String> {
+    let schema = parsed.schema.as_ref()?;
+    Some(apply_case(parsed, format!("CREATE SCHEMA IF NOT EXISTS {};", quoted(parsed, schema))))
+}
+
+/// 生成创建表的SQL语句
+pub fn generate_create_table_sql(parsed: &ParsedStruct) -> String {
+    let if_not_exists = if parsed.options.strict_create { "" } else { "IF NOT EXISTS " };
+    let mut sql = format!("CREATE TABLE {}{} (\n", if_not_exists, write_table(parsed));
+    
+    let mut columns = parsed.fields.iter()
+        .map(|f| format!("    {}", column_definition(parsed, f)))
+        .collect::<Vec<_>>();
+    // 联合唯一约束作为表级约束放在列定义之后
+    columns.extend(parsed.unique_groups.iter().map(|group| {
+        let names = group.iter().map(|name| quoted(parsed, name)).collect::<Vec<_>>();
+        format!("    UNIQUE ({})", names.join(", "))
+    }));
     
     sql.push_str(&columns.join(",\n"));
     sql.push_str("\n)");
     
-    if let Some(comment) = &parsed.comment {
-        sql.push_str(&format!(" COMMENT '{}'", comment.replace('\'', "''")));
+    if let (Some(comment), true) = (&parsed.comment, inline_comments(parsed)) {
+        sql.push_str(&format!(" COMMENT {}", quote_string_literal(parsed.options.backend, comment)));
+    }
+    
+    // SQLite 的表选项以逗号分隔
+    let table_options: Vec<&str> = [(parsed.options.strict, "STRICT"), (parsed.options.without_rowid, "WITHOUT ROWID")]
+        .into_iter()
+        .filter_map(|(enabled, option)| enabled.then_some(option))
+        .collect();
+    if !table_options.is_empty() {
+        sql.push(' ');
+        sql.push_str(&table_options.join(", "));
     }
     
     sql.push(';');
-    sql
+    apply_case(parsed, sql)
+}
+
+/// 注释是否内联在建表语句中
+///
+/// 只有 MySQL 支持内联 `COMMENT`；Postgres 改用 generate_comment_sqls 生成的 `COMMENT ON` 语句，SQLite 没有注释语法，注释被忽略
+fn inline_comments(parsed: &ParsedStruct) -> bool {
+    parsed.options.backend == Backend::MySql
+}
+
+/// 生成 Postgres 上表和列的 `COMMENT ON` 语句，在建表之后执行；其他后端返回空列表
+pub fn generate_comment_sqls(parsed: &ParsedStruct) -> Vec<String> {
+    if parsed.options.backend != Backend::Postgres {
+        return Vec::new();
+    }
+    let table = write_table(parsed);
+    let table_comment = parsed.comment.iter()
+        .map(|comment| format!("COMMENT ON TABLE {} IS {};", table, quote_string_literal(parsed.options.backend, comment)));
+    let column_comments = parsed.fields.iter()
+        .filter_map(|f| f.comment.as_ref().map(|comment| (f, comment)))
+        .map(|(f, comment)| format!("COMMENT ON COLUMN {}.{} IS {};",
+            table, quoted(parsed, &f.name), quote_string_literal(parsed.options.backend, comment)));
+    table_comment.chain(column_comments)
+        .map(|sql| apply_case(parsed, sql))
+        .collect()
+}
+
+/// 生成为已有表添加列的SQL语句
+pub fn generate_add_column_sql(parsed: &ParsedStruct, field: &ParsedField) -> String {
+    let sql = format!("ALTER TABLE {} ADD COLUMN {};", 
+        write_table(parsed), column_definition(parsed, field));
+    apply_case(parsed, sql)
+}
+
+/// 按后端把列的逻辑类型解析为建表语句中的类型
+pub fn column_type(sql_type: &SqlType, backend: Backend) -> String {
+    match (sql_type, backend) {
+        // Postgres 没有 TINYINT
+        (SqlType::TinyInt, Backend::Postgres) => "SMALLINT".into(),
+        (SqlType::TinyInt, _) => "TINYINT".into(),
+        (SqlType::SmallInt, _) => "SMALLINT".into(),
+        (SqlType::Int, _) => "INT".into(),
+        (SqlType::BigInt, _) => "BIGINT".into(),
+        (SqlType::UnsignedTinyInt, Backend::MySql) => "TINYINT UNSIGNED".into(),
+        (SqlType::UnsignedTinyInt, _) => "SMALLINT".into(),
+        (SqlType::UnsignedSmallInt, Backend::MySql) => "SMALLINT UNSIGNED".into(),
+        (SqlType::UnsignedSmallInt, _) => "INT".into(),
+        (SqlType::UnsignedInt, Backend::MySql) => "INT UNSIGNED".into(),
+        (SqlType::UnsignedInt, _) => "BIGINT".into(),
+        (SqlType::UnsignedBigInt, Backend::MySql) => "BIGINT UNSIGNED".into(),
+        (SqlType::UnsignedBigInt, _) => "NUMERIC(20)".into(),
+        (SqlType::Text { len: Some(len) }, _) => format!("VARCHAR({})", len),
+        (SqlType::Text { len: None }, _) => "TEXT".into(),
+        (SqlType::Char, _) => "CHAR(1)".into(),
+        // SQLite 没有原生布尔类型，以 INTEGER 存储
+        (SqlType::Bool, Backend::Sqlite) => "INTEGER".into(),
+        (SqlType::Bool, Backend::MySql) => "TINYINT(1)".into(),
+        (SqlType::Bool, _) => "BOOLEAN".into(),
+        // Postgres 没有 DOUBLE，FLOAT 不带精度时是双精度
+        (SqlType::Float, Backend::Postgres) => "REAL".into(),
+        (SqlType::Float, _) => "FLOAT".into(),
+        (SqlType::Double, Backend::Postgres) => "DOUBLE PRECISION".into(),
+        (SqlType::Double, _) => "DOUBLE".into(),
+        (SqlType::Decimal { precision: Some((precision, scale)) }, _) => format!("NUMERIC({},{})", precision, scale),
+        (SqlType::Decimal { precision: None }, _) => "NUMERIC".into(),
+        // Postgres 没有 DATETIME，带时区的类型在 sqlx 中只能与 TIMESTAMPTZ 互相编解码
+        (SqlType::DateTime, Backend::Postgres) => "TIMESTAMP".into(),
+        (SqlType::DateTime, _) => "DATETIME".into(),
+        (SqlType::DateTimeTz, Backend::Postgres) => "TIMESTAMPTZ".into(),
+        (SqlType::DateTimeTz, _) => "TIMESTAMP".into(),
+        (SqlType::Date, _) => "DATE".into(),
+        (SqlType::Time, _) => "TIME".into(),
+        (SqlType::Blob, Backend::Postgres) => "BYTEA".into(),
+        (SqlType::Blob, _) => "BLOB".into(),
+        (SqlType::Uuid, _) => "UUID".into(),
+        (SqlType::Json, Backend::Postgres) => "JSONB".into(),
+        (SqlType::Json, Backend::MySql) => "JSON".into(),
+        (SqlType::Json, _) => "TEXT".into(),
+        (SqlType::Array(element), _) => format!("{}[]", column_type(element, backend)),
+        (SqlType::Custom(sql_type), _) => sql_type.clone(),
+    }
+}
+
+/// 列定义：列名、类型、约束和注释
+fn column_definition(parsed: &ParsedStruct, field: &ParsedField) -> String {
+    let mut column = if field.is_auto_increment {
+        format!("{} {}", quoted(parsed, &field.name), auto_increment_column(parsed, field))
+    } else {
+        format!("{} {}", quoted(parsed, &field.name), column_type(&field.sql_type, parsed.options.backend))
+    };
+    
+    if field.is_primary_key && !field.is_auto_increment {
+        column.push_str(" PRIMARY KEY");
+    } else if field.is_unique && !field.is_primary_key {
+        column.push_str(" UNIQUE");
+    }
+    
+    if let (Some(comment), true) = (&field.comment, inline_comments(parsed)) {
+        column.push_str(&format!(" COMMENT {}", quote_string_literal(parsed.options.backend, comment)));
+    }
+    
+    if !field.allowed_values.is_empty() {
+        let values = field.allowed_values.iter()
+            .map(|value| match value {
+                AllowedValue::Text(text) => quote_string_literal(parsed.options.backend, text),
+                AllowedValue::Int(int) => int.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        column.push_str(&format!(" CHECK ({} IN ({}))", quoted(parsed, &field.name), values));
+    }
+    
+    column
+}
+
+/// 自增主键列的类型和约束
+fn auto_increment_column(parsed: &ParsedStruct, field: &ParsedField) -> String {
+    let sql_type = column_type(&field.sql_type, parsed.options.backend);
+    match parsed.options.backend {
+        // SQLite 只有 INTEGER PRIMARY KEY 才是 rowid 别名
+        Backend::Sqlite | Backend::Any => "INTEGER PRIMARY KEY AUTOINCREMENT".into(),
+        // 序列类型与列宽一致，RETURNING 读回的值才能解码为字段类型
+        Backend::Postgres => match sql_type.as_str() {
+            "BIGINT" => "BIGSERIAL PRIMARY KEY".into(),
+            "SMALLINT" => "SMALLSERIAL PRIMARY KEY".into(),
+            _ => "SERIAL PRIMARY KEY".into(),
+        },
+        Backend::MySql => format!("{} AUTO_INCREMENT PRIMARY KEY", sql_type),
+    }
+}
+
+/// 生成的语句及其占位符依次绑定的字段
+///
+/// 代码生成按 `binds` 逐个生成 `.bind(...)`，语句与绑定来自同一处，占位符数量和绑定次数不会不一致
+pub struct Statement<'a> {
+    pub sql: String,
+    pub binds: Vec<&'a ParsedField>,
 }
 
 /// 生成插入记录的SQL语句
-pub fn generate_insert_sql(parsed: &ParsedStruct) -> String {
-    let columns = parsed.fields.iter()
-        .map(|f| f.name.clone())
+pub fn generate_insert_sql(parsed: &ParsedStruct) -> Statement<'_> {
+    let binds = parsed.insertable_fields().collect();
+    if let Some(sql) = &parsed.overrides.insert {
+        return Statement { sql: sql.clone(), binds };
+    }
+    
+    let columns = parsed.insertable_fields()
+        .map(|f| quoted(parsed, &f.name))
         .collect::<Vec<_>>()
         .join(", ");
     
-    let placeholders = parsed.fields.iter()
+    let placeholders = parsed.insertable_fields()
         .enumerate()
-        .map(|(i, _)| format!("${}", i + 1))
+        .map(|(i, _)| parsed.options.backend.placeholder(i + 1))
+        .collect::<Vec<_>>()
+        .join(", ");
+    
+    // 没有需要绑定的列（如只有自增主键）时全部使用默认值，MySQL 不支持 DEFAULT VALUES
+    let mut sql = match parsed.options.backend {
+        _ if !columns.is_empty() => format!("INSERT INTO {} ({}) VALUES ({})", write_table(parsed), columns, placeholders),
+        Backend::MySql => format!("INSERT INTO {} () VALUES ()", write_table(parsed)),
+        Backend::Sqlite | Backend::Postgres | Backend::Any => format!("INSERT INTO {} DEFAULT VALUES", write_table(parsed)),
+    };
+    // Postgres 没有 last_insert_id，通过 RETURNING 取回自增主键
+    if let (Backend::Postgres, Some(key)) = (parsed.options.backend, parsed.auto_increment_field()) {
+        sql.push_str(&format!(" RETURNING {}", quoted(parsed, &key.name)));
+    }
+    sql.push(';');
+    Statement { sql: apply_case(parsed, sql), binds }
+}
+
+/// `insert_present` 在运行时拼接的插入语句的各部分
+pub struct PresentInsert<'a> {
+    /// 可插入的字段及其列名，按值为 `Some` 的字段筛选后拼接
+    pub columns: Vec<(&'a ParsedField, String)>,
+    /// 列名之前的部分
+    pub head: String,
+    /// 列名和占位符之间的部分
+    pub values: String,
+    /// 占位符之后的部分，Postgres 上有自增主键时带 `RETURNING`
+    pub tail: String,
+    /// 所有列都被省略时使用的语句
+    pub empty: String,
+}
+
+/// 生成 `insert_present` 的插入语句，列名和占位符在运行时按有值的字段拼接，不使用 #[sql_override(insert = "...")]
+pub fn generate_insert_present_sql(parsed: &ParsedStruct) -> PresentInsert<'_> {
+    let columns = parsed.insertable_fields()
+        .map(|f| (f, quoted(parsed, &f.name)))
+        .collect();
+    let table = write_table(parsed);
+    let returning = match (parsed.options.backend, parsed.auto_increment_field()) {
+        (Backend::Postgres, Some(key)) => format!(" RETURNING {}", quoted(parsed, &key.name)),
+        _ => String::new(),
+    };
+    let empty = match parsed.options.backend {
+        Backend::MySql => format!("INSERT INTO {} () VALUES (){};", table, returning),
+        Backend::Sqlite | Backend::Postgres | Backend::Any => format!("INSERT INTO {} DEFAULT VALUES{};", table, returning),
+    };
+    PresentInsert {
+        columns,
+        head: apply_case(parsed, format!("INSERT INTO {} (", table)),
+        values: apply_case(parsed, ") VALUES (".to_string()),
+        tail: apply_case(parsed, format!("){};", returning)),
+        empty: apply_case(parsed, empty),
+    }
+}
+
+/// 生成冲突时忽略的插入语句，冲突列为 `key`
+pub fn generate_insert_ignore_sql(parsed: &ParsedStruct, key: &ParsedField) -> String {
+    let columns = parsed.insertable_fields()
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    
+    let placeholders = parsed.insertable_fields()
+        .enumerate()
+        .map(|(i, _)| parsed.options.backend.placeholder(i + 1))
+        .collect::<Vec<_>>()
+        .join(", ");
+    
+    let key = quoted(parsed, &key.name);
+    let sql = match parsed.options.backend {
+        Backend::MySql => format!("INSERT INTO {} ({}) VALUES ({}) ON DUPLICATE KEY UPDATE {} = {};", 
+            write_table(parsed), columns, placeholders, key, key),
+        Backend::Sqlite | Backend::Postgres | Backend::Any => format!("INSERT INTO {} ({}) VALUES ({}) ON CONFLICT ({}) DO NOTHING;", 
+            write_table(parsed), columns, placeholders, key),
+    };
+    apply_case(parsed, sql)
+}
+
+/// 生成批量 upsert 语句，返回 `VALUES` 之后与各行占位符之后的部分，每行的 `(...)` 在运行时拼接
+///
+/// 冲突时更新除冲突列和主键以外的列，没有可更新的列时忽略冲突行；
+/// #[upsert_coalesce] 列只在新值非空时更新，已有的值不会被 NULL 覆盖
+pub fn generate_upsert_many_sql(parsed: &ParsedStruct, key: &ParsedField) -> (String, String) {
+    let columns = parsed.insertable_fields()
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let table = write_table(parsed);
+    let assignments: Vec<String> = parsed.insertable_fields()
+        .filter(|f| f.name != key.name && !f.is_primary_key)
+        .map(|f| {
+            let column = quoted(parsed, &f.name);
+            let new_value = match parsed.options.backend {
+                Backend::MySql => format!("VALUES({})", column),
+                Backend::Sqlite | Backend::Postgres | Backend::Any => format!("EXCLUDED.{}", column),
+            };
+            if f.is_upsert_coalesce {
+                format!("{} = COALESCE({}, {}.{})", column, new_value, table, column)
+            } else {
+                format!("{} = {}", column, new_value)
+            }
+        })
+        .collect();
+    
+    let key = quoted(parsed, &key.name);
+    let prefix = format!("INSERT INTO {} ({}) VALUES ", table, columns);
+    let suffix = match parsed.options.backend {
+        Backend::MySql if assignments.is_empty() => format!(" ON DUPLICATE KEY UPDATE {} = {};", key, key),
+        Backend::MySql => format!(" ON DUPLICATE KEY UPDATE {};", assignments.join(", ")),
+        Backend::Sqlite | Backend::Postgres | Backend::Any if assignments.is_empty() => format!(" ON CONFLICT ({}) DO NOTHING;", key),
+        Backend::Sqlite | Backend::Postgres | Backend::Any => format!(" ON CONFLICT ({}) DO UPDATE SET {};", key, assignments.join(", ")),
+    };
+    (apply_case(parsed, prefix), apply_case(parsed, suffix))
+}
+
+/// 生成在写入表上按冲突列查询记录的SQL语句
+pub fn generate_select_by_key_sql(parsed: &ParsedStruct, key: &ParsedField) -> String {
+    let columns = parsed.fields.iter()
+        .map(|f| quoted(parsed, &f.name))
         .collect::<Vec<_>>()
         .join(", ");
     
-    format!("INSERT INTO {} ({}) VALUES ({});", 
-        parsed.table_name, columns, placeholders)
+    let sql = format!("SELECT {} FROM {} WHERE {} = {};", 
+        columns, write_table(parsed), quoted(parsed, &key.name), parsed.options.backend.placeholder(1));
+    apply_case(parsed, sql)
 }
 
 /// 生成更新记录的SQL语句
-pub fn generate_update_sql(parsed: &ParsedStruct) -> String {
+pub fn generate_update_sql(parsed: &ParsedStruct) -> Statement<'_> {
     let primary_key = parsed.fields.iter()
         .find(|f| f.is_primary_key)
         .expect("No primary key defined");
+    let binds = parsed.updatable_fields().chain(std::iter::once(primary_key)).collect();
+    if let Some(sql) = &parsed.overrides.update {
+        return Statement { sql: sql.clone(), binds };
+    }
     
-    let set_clauses = parsed.fields.iter()
-        .filter(|f| !f.is_primary_key)
+    let set_clauses = parsed.updatable_fields()
         .enumerate()
-        .map(|(i, f)| format!("{} = ${}", f.name, i + 1))
+        .map(|(i, f)| format!("{} = {}", quoted(parsed, &f.name), parsed.options.backend.placeholder(i + 1)))
         .collect::<Vec<_>>()
         .join(", ");
     
-    let pk_index = parsed.fields.iter()
-        .filter(|f| !f.is_primary_key)
-        .count() + 1;
+    let pk_index = parsed.updatable_fields().count() + 1;
     
-    format!("UPDATE {} SET {} WHERE {} = ${};", 
-        parsed.table_name, set_clauses, primary_key.name, pk_index)
+    let sql = format!("UPDATE {} SET {} WHERE {} = {};", 
+        write_table(parsed), set_clauses, quoted(parsed, &primary_key.name), parsed.options.backend.placeholder(pk_index));
+    Statement { sql: apply_case(parsed, sql), binds }
+}
+
+/// 生成更新记录并返回更新后各列的SQL语句，在更新语句（包括覆盖语句）末尾追加 `RETURNING`
+pub fn generate_update_returning_sql(parsed: &ParsedStruct) -> String {
+    let columns = parsed.fields.iter()
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    
+    let returning = apply_case(parsed, format!(" RETURNING {};", columns));
+    format!("{}{}", generate_update_sql(parsed).sql.trim_end().trim_end_matches(';'), returning)
+}
+
+/// 生成原子地增加计数列的SQL语句
+pub fn generate_increment_sql(parsed: &ParsedStruct, field: &ParsedField) -> String {
+    let primary_key = parsed.fields.iter()
+        .find(|f| f.is_primary_key)
+        .expect("No primary key defined");
+    
+    let column = quoted(parsed, &field.name);
+    let sql = format!("UPDATE {} SET {} = {} + {} WHERE {} = {};", 
+        write_table(parsed), column, column, parsed.options.backend.placeholder(1),
+        quoted(parsed, &primary_key.name), parsed.options.backend.placeholder(2));
+    apply_case(parsed, sql)
 }
 
 /// 生成删除记录的SQL语句
-pub fn generate_delete_sql(parsed: &ParsedStruct) -> String {
+pub fn generate_delete_sql(parsed: &ParsedStruct) -> Statement<'_> {
     let primary_key = parsed.fields.iter()
         .find(|f| f.is_primary_key)
         .expect("No primary key defined");
+    let binds = vec![primary_key];
+    if let Some(sql) = &parsed.overrides.delete {
+        return Statement { sql: sql.clone(), binds };
+    }
     
-    format!("DELETE FROM {} WHERE {} = $1;", 
-        parsed.table_name, primary_key.name)
+    let sql = format!("DELETE FROM {} WHERE {} = {};", 
+        write_table(parsed), quoted(parsed, &primary_key.name), parsed.options.backend.placeholder(1));
+    Statement { sql: apply_case(parsed, sql), binds }
 }
 
 /// 生成查询记录的SQL语句
 pub fn generate_select_sql(parsed: &ParsedStruct) -> String {
+    if let Some(sql) = &parsed.overrides.select_all {
+        return sql.clone();
+    }
+    
+    let columns = parsed.fields.iter()
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    
+    let sql = format!("SELECT {} FROM {};", 
+        columns, read_table(parsed));
+    apply_case(parsed, sql)
+}
+
+/// 生成 #[has_many] 加载子记录时拼接在子结构体 `SELECT_SQL` 之后的条件，占位符绑定本记录的主键
+pub fn generate_has_many_where_sql(parsed: &ParsedStruct, relation: &HasMany) -> String {
+    let sql = format!(" WHERE {} = {};", 
+        quoted(parsed, &relation.foreign_key), parsed.options.backend.placeholder(1));
+    apply_case(parsed, sql)
+}
+
+/// 生成 `find_all_with_*` 批量加载子记录时拼接在子结构体 `SELECT_SQL` 之后的条件前缀，调用方在运行时补上占位符和 `);`
+pub fn generate_has_many_in_sql(parsed: &ParsedStruct, relation: &HasMany) -> String {
+    let sql = format!(" WHERE {} IN (", quoted(parsed, &relation.foreign_key));
+    apply_case(parsed, sql)
+}
+
+/// 生成以 `WHERE ` 结尾的查询语句前缀，调用方在运行时拼接条件
+pub fn generate_select_where_sql(parsed: &ParsedStruct) -> String {
     let columns = parsed.fields.iter()
-        .map(|f| f.name.clone())
+        .map(|f| quoted(parsed, &f.name))
         .collect::<Vec<_>>()
         .join(", ");
     
-    format!("SELECT {} FROM {};", columns, parsed.table_name)
+    let sql = format!("SELECT {} FROM {} WHERE ", 
+        columns, read_table(parsed));
+    apply_case(parsed, sql)
 }
 
 /// 生成按主键查询记录的SQL语句
-pub fn generate_select_by_id_sql(parsed: &ParsedStruct) -> String {
+pub fn generate_select_by_id_sql(parsed: &ParsedStruct) -> Statement<'_> {
     let primary_key = parsed.fields.iter()
         .find(|f| f.is_primary_key)
         .expect("No primary key defined");
+    let binds = vec![primary_key];
+    if let Some(sql) = &parsed.overrides.select_by_id {
+        return Statement { sql: sql.clone(), binds };
+    }
+    
+    let columns = parsed.fields.iter()
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    
+    let sql = format!("SELECT {} FROM {} WHERE {} = {};", 
+        columns, read_table(parsed), quoted(parsed, &primary_key.name), parsed.options.backend.placeholder(1));
+    Statement { sql: apply_case(parsed, sql), binds }
+}
+
+/// 生成 #[projection] 结构体只读取其字段对应列的查询语句和按主键查询语句
+pub fn generate_projection_select_sql(parsed: &ParsedStruct, projection: &Projection) -> (String, String) {
+    let primary_key = parsed.fields.iter()
+        .find(|f| f.is_primary_key)
+        .expect("No primary key defined");
+    
+    let columns = projection.fields.iter()
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    
+    let select_all = format!("SELECT {} FROM {};", columns, read_table(parsed));
+    let select_by_id = format!("SELECT {} FROM {} WHERE {} = {};", 
+        columns, read_table(parsed), quoted(parsed, &primary_key.name), parsed.options.backend.placeholder(1));
+    (apply_case(parsed, select_all), apply_case(parsed, select_by_id))
+}
+
+/// 生成按列查询记录的SQL语句
+pub fn generate_select_by_column_sql(parsed: &ParsedStruct, field: &ParsedField) -> String {
+    let columns = parsed.fields.iter()
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    
+    let sql = format!("SELECT {} FROM {} WHERE {} = {};", 
+        columns, read_table(parsed), quoted(parsed, &field.name), parsed.options.backend.placeholder(1));
+    apply_case(parsed, sql)
+}
+
+/// 生成按 LIKE 模式查询记录的SQL语句，Postgres 上使用不区分大小写的 ILIKE
+pub fn generate_search_by_column_sql(parsed: &ParsedStruct, field: &ParsedField) -> String {
+    let columns = parsed.fields.iter()
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let operator = match parsed.options.backend {
+        Backend::Postgres => "ILIKE",
+        Backend::Sqlite | Backend::MySql | Backend::Any => "LIKE",
+    };
+    
+    let sql = format!("SELECT {} FROM {} WHERE {} {} {};", 
+        columns, read_table(parsed), quoted(parsed, &field.name), operator, parsed.options.backend.placeholder(1));
+    apply_case(parsed, sql)
+}
+
+/// 生成按 JSON 路径查询记录的SQL语句，第一个参数为路径（如 `$.a.b`），第二个为比较的文本值
+pub fn generate_select_by_json_path_sql(parsed: &ParsedStruct, field: &ParsedField) -> String {
+    let columns = parsed.fields.iter()
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    
+    let backend = parsed.options.backend;
+    let column = quoted(parsed, &field.name);
+    // 提取出的值按文本比较，数字等非字符串值也能与字符串参数匹配
+    let extracted = match backend {
+        Backend::MySql => format!("JSON_UNQUOTE(JSON_EXTRACT({}, {}))", column, backend.placeholder(1)),
+        _ => format!("CAST(JSON_EXTRACT({}, {}) AS TEXT)", column, backend.placeholder(1)),
+    };
+    let sql = format!("SELECT {} FROM {} WHERE {} = {};", 
+        columns, read_table(parsed), extracted, backend.placeholder(2));
+    apply_case(parsed, sql)
+}
+
+/// 生成按列查询第一条记录的SQL语句
+pub fn generate_select_first_by_column_sql(parsed: &ParsedStruct, field: &ParsedField) -> String {
+    let columns = parsed.fields.iter()
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
     
+    let sql = format!("SELECT {} FROM {} WHERE {} = {} LIMIT 1;", 
+        columns, read_table(parsed), quoted(parsed, &field.name), parsed.options.backend.placeholder(1));
+    apply_case(parsed, sql)
+}
+
+/// 生成按列批量查询记录的SQL语句，返回 `IN (` 之前与 `)` 之后的部分，占位符在运行时拼接
+pub fn generate_select_by_column_in_sql(parsed: &ParsedStruct, field: &ParsedField) -> (String, String) {
     let columns = parsed.fields.iter()
-        .map(|f| f.name.clone())
+        .map(|f| quoted(parsed, &f.name))
         .collect::<Vec<_>>()
         .join(", ");
     
-    format!("SELECT {} FROM {} WHERE {} = $1;", 
-        columns, parsed.table_name, primary_key.name)
-}
\ No newline at end of file
+    let prefix = format!("SELECT {} FROM {} WHERE {} IN (", 
+        columns, read_table(parsed), quoted(parsed, &field.name));
+    (apply_case(parsed, prefix), ");".to_string())
+}
+
+/// 生成分页查询的SQL语句，按主键排序使各页不重叠，占位符依次为 LIMIT 和 OFFSET
+pub fn generate_select_page_sql(parsed: &ParsedStruct) -> String {
+    let columns = parsed.fields.iter()
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let order = parsed.fields.iter()
+        .filter(|f| f.is_primary_key)
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    
+    let sql = format!("SELECT {} FROM {} ORDER BY {} LIMIT {} OFFSET {};", 
+        columns, read_table(parsed), order,
+        parsed.options.backend.placeholder(1), parsed.options.backend.placeholder(2));
+    apply_case(parsed, sql)
+}
+
+/// 生成统计记录数的SQL语句
+pub fn generate_count_sql(parsed: &ParsedStruct) -> String {
+    let sql = format!("SELECT COUNT(*) FROM {};", 
+        read_table(parsed));
+    apply_case(parsed, sql)
+}
+
+/// 生成按调用方的 WHERE 子句统计记录数的SQL语句前缀，子句直接拼接在其后
+pub fn generate_count_where_sql(parsed: &ParsedStruct) -> String {
+    let sql = format!("SELECT COUNT(*) FROM {} WHERE ", 
+        read_table(parsed));
+    apply_case(parsed, sql)
+}
+
+/// 生成按调用方的 WHERE 子句判断记录是否存在的SQL语句，返回子句前后的两部分
+pub fn generate_exists_where_sql(parsed: &ParsedStruct) -> (String, String) {
+    let sql = format!("SELECT EXISTS(SELECT 1 FROM {} WHERE ", 
+        read_table(parsed));
+    (apply_case(parsed, sql), ")".to_string())
+}
+
+/// `<Name>Filter` 在运行时拼接语句使用的各部分
+pub struct FilterSql {
+    /// 查询记录的语句，不含 WHERE
+    pub select: String,
+    /// 统计记录数的语句，不含 WHERE
+    pub count: String,
+    /// 删除记录的语句，不含 WHERE
+    pub delete: String,
+    /// 条件之前的 ` WHERE `
+    pub where_keyword: String,
+    /// 连接条件的 ` AND `
+    pub and: String,
+    /// 空的 IN 列表对应的恒假条件
+    pub never: String,
+}
+
+/// 生成 `<Name>Filter` 拼接语句使用的各部分
+pub fn generate_filter_sql(parsed: &ParsedStruct) -> FilterSql {
+    let columns = parsed.fields.iter()
+        .map(|f| quoted(parsed, &f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    FilterSql {
+        select: apply_case(parsed, format!("SELECT {} FROM {}", columns, read_table(parsed))),
+        count: apply_case(parsed, format!("SELECT COUNT(*) FROM {}", read_table(parsed))),
+        delete: apply_case(parsed, format!("DELETE FROM {}", write_table(parsed))),
+        where_keyword: apply_case(parsed, " WHERE ".to_string()),
+        and: apply_case(parsed, " AND ".to_string()),
+        never: "1 = 0".to_string(),
+    }
+}
+
+/// 生成 `<Name>Filter` 中一个条件的列名和运算符部分，如 `"name" = `，占位符在运行时追加
+pub fn generate_filter_condition_sql(parsed: &ParsedStruct, field: &ParsedField, operator: &str) -> String {
+    apply_case(parsed, format!("{} {}", quoted(parsed, &field.name), operator))
+}
+
+/// 生成按列删除记录的SQL语句
+pub fn generate_delete_by_column_sql(parsed: &ParsedStruct, field: &ParsedField) -> String {
+    let sql = format!("DELETE FROM {} WHERE {} = {};", 
+        write_table(parsed), quoted(parsed, &field.name), parsed.options.backend.placeholder(1));
+    apply_case(parsed, sql)
+}
+
+/// 生成按列统计记录数的SQL语句
+pub fn generate_count_by_sql(parsed: &ParsedStruct, field: &ParsedField) -> String {
+    let sql = format!("SELECT COUNT(*) FROM {} WHERE {} = {};", 
+        read_table(parsed), quoted(parsed, &field.name), parsed.options.backend.placeholder(1));
+    apply_case(parsed, sql)
+}
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use super::*;
+
+    const BACKENDS: [Backend; 4] = [Backend::Sqlite, Backend::Postgres, Backend::MySql, Backend::Any];
+
+    /// 每个逻辑类型在 SQLite、Postgres、MySQL 和 Any 上的列类型
+    fn matrix() -> Vec<(SqlType, [&'static str; 4])> {
+        vec![
+            (SqlType::TinyInt, ["TINYINT", "SMALLINT", "TINYINT", "TINYINT"]),
+            (SqlType::SmallInt, ["SMALLINT", "SMALLINT", "SMALLINT", "SMALLINT"]),
+            (SqlType::Int, ["INT", "INT", "INT", "INT"]),
+            (SqlType::BigInt, ["BIGINT", "BIGINT", "BIGINT", "BIGINT"]),
+            (SqlType::UnsignedTinyInt, ["SMALLINT", "SMALLINT", "TINYINT UNSIGNED", "SMALLINT"]),
+            (SqlType::UnsignedSmallInt, ["INT", "INT", "SMALLINT UNSIGNED", "INT"]),
+            (SqlType::UnsignedInt, ["BIGINT", "BIGINT", "INT UNSIGNED", "BIGINT"]),
+            (SqlType::UnsignedBigInt, ["NUMERIC(20)", "NUMERIC(20)", "BIGINT UNSIGNED", "NUMERIC(20)"]),
+            (SqlType::Text { len: Some(64) }, ["VARCHAR(64)", "VARCHAR(64)", "VARCHAR(64)", "VARCHAR(64)"]),
+            (SqlType::Text { len: None }, ["TEXT", "TEXT", "TEXT", "TEXT"]),
+            (SqlType::Char, ["CHAR(1)", "CHAR(1)", "CHAR(1)", "CHAR(1)"]),
+            (SqlType::Bool, ["INTEGER", "BOOLEAN", "TINYINT(1)", "BOOLEAN"]),
+            (SqlType::Float, ["FLOAT", "REAL", "FLOAT", "FLOAT"]),
+            (SqlType::Double, ["DOUBLE", "DOUBLE PRECISION", "DOUBLE", "DOUBLE"]),
+            (SqlType::Decimal { precision: Some((19, 4)) }, ["NUMERIC(19,4)", "NUMERIC(19,4)", "NUMERIC(19,4)", "NUMERIC(19,4)"]),
+            (SqlType::Decimal { precision: None }, ["NUMERIC", "NUMERIC", "NUMERIC", "NUMERIC"]),
+            (SqlType::DateTime, ["DATETIME", "TIMESTAMP", "DATETIME", "DATETIME"]),
+            (SqlType::DateTimeTz, ["TIMESTAMP", "TIMESTAMPTZ", "TIMESTAMP", "TIMESTAMP"]),
+            (SqlType::Date, ["DATE", "DATE", "DATE", "DATE"]),
+            (SqlType::Time, ["TIME", "TIME", "TIME", "TIME"]),
+            (SqlType::Blob, ["BLOB", "BYTEA", "BLOB", "BLOB"]),
+            (SqlType::Uuid, ["UUID", "UUID", "UUID", "UUID"]),
+            (SqlType::Json, ["TEXT", "JSONB", "JSON", "TEXT"]),
+            (SqlType::Array(Box::new(SqlType::Double)), ["DOUBLE[]", "DOUBLE PRECISION[]", "DOUBLE[]", "DOUBLE[]"]),
+            (SqlType::Custom("money".into()), ["money", "money", "money", "money"]),
+        ]
+    }
+
+    /// 新增逻辑类型时这里无法编译，提醒补充矩阵
+    fn variant_index(sql_type: &SqlType) -> usize {
+        match sql_type {
+            SqlType::TinyInt => 0,
+            SqlType::SmallInt => 1,
+            SqlType::Int => 2,
+            SqlType::BigInt => 3,
+            SqlType::UnsignedTinyInt => 4,
+            SqlType::UnsignedSmallInt => 5,
+            SqlType::UnsignedInt => 6,
+            SqlType::UnsignedBigInt => 7,
+            SqlType::Text { .. } => 8,
+            SqlType::Char => 9,
+            SqlType::Bool => 10,
+            SqlType::Float => 11,
+            SqlType::Double => 12,
+            SqlType::Decimal { .. } => 13,
+            SqlType::DateTime => 14,
+            SqlType::DateTimeTz => 15,
+            SqlType::Date => 16,
+            SqlType::Time => 17,
+            SqlType::Blob => 18,
+            SqlType::Uuid => 19,
+            SqlType::Json => 20,
+            SqlType::Array(_) => 21,
+            SqlType::Custom(_) => 22,
+        }
+    }
+
+    #[test]
+    fn column_type_matrix() {
+        for (sql_type, expected) in matrix() {
+            for (i, (backend, expected)) in BACKENDS.into_iter().zip(expected).enumerate() {
+                assert_eq!(column_type(&sql_type, backend), expected, "{:?} on backend #{}", sql_type, i);
+            }
+        }
+    }
+
+    #[test]
+    fn matrix_covers_every_variant() {
+        let covered: HashSet<usize> = matrix().iter().map(|(sql_type, _)| variant_index(sql_type)).collect();
+        assert_eq!(covered.len(), 23);
+    }
+}
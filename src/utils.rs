@@ -1,6 +1,12 @@
-use syn::{Attribute, Lit, Meta, Type};
+use std::collections::HashSet;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{Attribute, GenericArgument, Ident, Lit, Meta, PathArguments, PathSegment, Type};
+use crate::parser::{Backend, SqlCase, SqlType};
 
 /// 提取字段注释，支持从文档注释（///）和 #[comment = "..."] 属性中提取
+///
+/// 显式的 `#[comment]` 优先于文档注释；文档注释逐行去掉首尾空白和空行后以换行连接
 pub fn extract_comment(attrs: &[Attribute]) -> Option<String> {
     let mut comments = Vec::new();
     let mut explicit_comment = None;
@@ -20,8 +26,14 @@ pub fn extract_comment(attrs: &[Attribute]) -> Option<String> {
         if attr.path.is_ident("doc") {
             if let Ok(Meta::NameValue(meta)) = attr.parse_meta() {
                 if let Lit::Str(lit_str) = meta.lit {
-                    let comment = lit_str.value().trim().to_string();
-                    comments.push(comment);
+                    // 块注释（/** ... */）的一个属性可能包含多行，行首的 `*` 一并去掉
+                    let value = lit_str.value();
+                    let lines = value.lines()
+                        .map(|line| line.trim())
+                        .map(|line| line.strip_prefix('*').map_or(line, str::trim_start))
+                        .filter(|line| !line.is_empty())
+                        .map(str::to_string);
+                    comments.extend(lines);
                 }
             }
         }
@@ -31,42 +43,565 @@ pub fn extract_comment(attrs: &[Attribute]) -> Option<String> {
         if comments.is_empty() {
             None
         } else {
-            Some(comments.join(" "))
+            Some(comments.join("\n"))
         }
     })
 }
 
-/// 将Rust类型映射到SQL类型
-pub fn map_type_to_sql(ty: &Type) -> String {
+/// 获取路径段上的泛型类型参数，忽略生命周期等其它参数
+pub fn generic_type_args(segment: &PathSegment) -> Vec<&Type> {
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// 判断是否为 `str` 类型，包括 `core::primitive::str`
+fn is_str(ty: &Type) -> bool {
+    is_named(ty, "str")
+}
+
+/// 去掉类型外层及泛型参数、引用、切片中的括号和宏展开留下的不可见分组，
+/// 使 `macro_rules!` 中以 `$t:ty` 传入的类型与直接写出的类型一样匹配
+pub fn normalize_type(ty: &Type) -> Type {
+    match ty {
+        Type::Group(group) => normalize_type(&group.elem),
+        Type::Paren(paren) => normalize_type(&paren.elem),
+        Type::Path(type_path) => {
+            let mut type_path = type_path.clone();
+            for segment in &mut type_path.path.segments {
+                if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                    for arg in &mut args.args {
+                        if let GenericArgument::Type(inner) = arg {
+                            *inner = normalize_type(inner);
+                        }
+                    }
+                }
+            }
+            Type::Path(type_path)
+        }
+        Type::Reference(reference) => {
+            let mut reference = reference.clone();
+            *reference.elem = normalize_type(&reference.elem);
+            Type::Reference(reference)
+        }
+        Type::Slice(slice) => {
+            let mut slice = slice.clone();
+            *slice.elem = normalize_type(&slice.elem);
+            Type::Slice(slice)
+        }
+        ty => ty.clone(),
+    }
+}
+
+/// 无法映射类型时附加在错误信息后的提示
+const UNSUPPORTED_TYPE_HINT: &str = "use #[sql_type = \"...\"] to set the column type explicitly, or #[skip] to exclude the field";
+
+/// 判断是否为指定名称且没有泛型参数的路径类型，如 `u8` 或 `core::primitive::u8`
+fn is_named(ty: &Type, name: &str) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.qself.is_none()
+        && type_path.path.segments.last().is_some_and(|segment| segment.ident == name && segment.arguments.is_empty()))
+}
+
+/// 判断是否为 `Option<T>`
+pub fn is_option(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.segments.last()
+        .is_some_and(|segment| segment.ident == "Option" && !generic_type_args(segment).is_empty()))
+}
+
+/// `Option<T>` 返回 `T`，其他类型原样返回
+pub fn option_inner(ty: &Type) -> &Type {
+    match ty {
+        Type::Path(type_path) => match type_path.path.segments.last() {
+            Some(segment) if segment.ident == "Option" => generic_type_args(segment).first().copied().unwrap_or(ty),
+            _ => ty,
+        },
+        _ => ty,
+    }
+}
+
+/// 判断是否为 `Uuid`
+pub fn is_uuid_type(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.segments.last().is_some_and(|segment| segment.ident == "Uuid"))
+}
+
+/// 判断是否为 `Duration`
+pub fn is_duration_type(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.segments.last().is_some_and(|segment| segment.ident == "Duration"))
+}
+
+/// 判断是否为按 `String` 映射的字符串类型：`String`、`&str` 或 `Cow<str>`
+pub fn is_string_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last().is_some_and(|segment| segment.ident == "String"
+            || (segment.ident == "Cow" && generic_type_args(segment).first().is_some_and(|inner| is_str(inner)))),
+        Type::Reference(reference) => is_str(&reference.elem),
+        _ => false,
+    }
+}
+
+/// 判断是否为 chrono 或 time 库的日期、日期时间类型
+pub fn is_datetime_type(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.segments.last()
+        .is_some_and(|segment| matches!(segment.ident.to_string().as_str(), "NaiveDateTime" | "NaiveDate" | "DateTime" | "OffsetDateTime" | "PrimitiveDateTime" | "Date")))
+}
+
+/// 判断是否为 `char`
+pub fn is_char_type(ty: &Type) -> bool {
+    is_named(ty, "char")
+}
+
+/// 判断是否为 `IpAddr`、`Ipv4Addr` 或 `Ipv6Addr`
+pub fn is_ip_addr_type(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.segments.last()
+        .is_some_and(|segment| matches!(segment.ident.to_string().as_str(), "IpAddr" | "Ipv4Addr" | "Ipv6Addr")))
+}
+
+/// 判断是否为 `serde_json::Value` 或导入后的 `Value`
+fn is_json_value(path: &syn::Path) -> bool {
+    let segments: Vec<String> = path.segments.iter().map(|segment| segment.ident.to_string()).collect();
+    matches!(segments.as_slice(), [value] if value == "Value")
+        || matches!(segments.as_slice(), [.., json, value] if json == "serde_json" && value == "Value")
+}
+
+/// 判断是否为 `Vec<u8>` 或 `Box<[u8]>`
+fn is_byte_buffer(segment: &syn::PathSegment) -> bool {
+    generic_type_args(segment).first().is_some_and(|inner| match (segment.ident.to_string().as_str(), inner) {
+        ("Vec", inner) => is_named(inner, "u8"),
+        ("Box", Type::Slice(slice)) => is_named(&slice.elem, "u8"),
+        _ => false,
+    })
+}
+
+/// 默认的类型映射表，每项为 `(Rust 类型名, 逻辑类型)`，逻辑类型在建表时按后端解析
+///
+/// `&str`、`Cow<'_, str>` 按 `String` 查找，`Box<[u8]>`、`Bytes` 按 `Vec<u8>` 查找，`serde_json::Value`、`JsonValue` 按 `Json` 查找，
+/// chrono 的 `DateTime` 只认带泛型参数的形式
+pub const DEFAULT_TYPE_MAP: &[(&str, SqlType)] = &[
+    ("i8", SqlType::TinyInt),
+    ("i16", SqlType::SmallInt),
+    ("i32", SqlType::Int),
+    ("i64", SqlType::BigInt),
+    ("isize", SqlType::BigInt),
+    ("u8", SqlType::UnsignedTinyInt),
+    ("u16", SqlType::UnsignedSmallInt),
+    ("u32", SqlType::UnsignedInt),
+    ("u64", SqlType::UnsignedBigInt),
+    ("usize", SqlType::UnsignedBigInt),
+    ("String", SqlType::Text { len: Some(255) }),
+    // sqlx 不能直接编解码 char，以单字符文本存储
+    ("char", SqlType::Char),
+    ("bool", SqlType::Bool),
+    ("f32", SqlType::Float),
+    ("f64", SqlType::Double),
+    ("Vec<u8>", SqlType::Blob),
+    // chrono
+    ("NaiveDateTime", SqlType::DateTime),
+    ("NaiveDate", SqlType::Date),
+    ("NaiveTime", SqlType::Time),
+    ("DateTime", SqlType::DateTimeTz),
+    // time 库的日期时间类型，与 chrono 的对应类型映射一致
+    ("OffsetDateTime", SqlType::DateTimeTz),
+    ("PrimitiveDateTime", SqlType::DateTime),
+    ("Date", SqlType::Date),
+    ("Time", SqlType::Time),
+    ("Uuid", SqlType::Uuid),
+    // serde_json::Value 和 sqlx::types::Json<T>
+    ("Json", SqlType::Json),
+    // rust_decimal，精度可由 #[decimal(precision = .., scale = ..)] 指定
+    ("Decimal", SqlType::Decimal { precision: None }),
+];
+
+/// SQLite STRICT 表中替代默认映射的类型，STRICT 表只接受 INT、INTEGER、REAL、TEXT、BLOB 和 ANY
+///
+/// 以结构体级类型映射的形式生效，因此用户的 `#[type_map]` 条目优先；日期时间等没有列出的类型需要手动指定
+pub const STRICT_TYPE_MAP: &[(&str, &str)] = &[
+    ("i8", "INTEGER"),
+    ("i16", "INTEGER"),
+    ("i32", "INTEGER"),
+    ("i64", "INTEGER"),
+    ("isize", "INTEGER"),
+    ("u8", "INTEGER"),
+    ("u16", "INTEGER"),
+    ("u32", "INTEGER"),
+    ("bool", "INTEGER"),
+    ("f32", "REAL"),
+    ("f64", "REAL"),
+    ("String", "TEXT"),
+    ("char", "TEXT"),
+    ("Vec<u8>", "BLOB"),
+    ("Json", "TEXT"),
+    ("Uuid", "BLOB"),
+];
+
+/// 在默认类型映射表中查找类型名
+fn lookup_type(name: &str) -> Option<SqlType> {
+    DEFAULT_TYPE_MAP.iter()
+        .find(|(rust_type, _)| *rust_type == name)
+        .map(|(_, sql_type)| sql_type.clone())
+}
+
+/// 将Rust类型映射到列的逻辑类型
+///
+/// `type_map` 为结构体上 `#[type_map("Foo" = "BAR")]` 给出的 `(类型名, SQL 类型)`，按路径最后一段匹配，映射为原样使用的 `SqlType::Custom`，
+/// 优先于 [`DEFAULT_TYPE_MAP`]；内置映射同样按最后一段匹配，`std::string::String`、`std::option::Option<T>` 等完整路径与短名称等价；
+/// 类型别名无法展开，需要按别名写入 `type_map`。两者都没有的类型返回错误信息，避免拼错的类型名原样进入建表语句。
+/// `Option<T>` 按 `T` 映射；引用、元组、除 `Vec<u8>` 外的 `Vec<T>`、映射和集合等无法存入单列的类型返回错误信息
+pub fn map_type_to_sql(ty: &Type, backend: Backend, type_map: &[(String, String)]) -> Result<SqlType, String> {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_some() => Err(format!(
+            "associated type paths like `<T as Trait>::Assoc` cannot be mapped; {}", UNSUPPORTED_TYPE_HINT)),
+        Type::Path(type_path) => {
+            let segment = type_path.path.segments.last().unwrap();
+            let ident = segment.ident.to_string();
+            if let Some((_, sql_type)) = type_map.iter().find(|(name, _)| *name == ident) {
+                return Ok(SqlType::Custom(sql_type.clone()));
+            }
+            let key = match ident.as_str() {
+                "Option" => return match generic_type_args(segment).first() {
+                    Some(inner) => map_type_to_sql(inner, backend, type_map),
+                    None => Err(unmapped_type_message(&ident, type_map)),
+                },
+                "Cow" if generic_type_args(segment).first().is_some_and(|inner| is_str(inner)) => "String",
+                "Vec" | "Box" if is_byte_buffer(segment) => "Vec<u8>",
+                "Bytes" => "Vec<u8>",
+                "JsonValue" => "Json",
+                "Value" if is_json_value(&type_path.path) => "Json",
+                // Postgres 的数组列，sqlx 不支持多维数组
+                "Vec" if backend == Backend::Postgres => return match generic_type_args(segment).first() {
+                    Some(inner) => match map_type_to_sql(inner, backend, type_map)? {
+                        SqlType::Array(_) => Err(format!(
+                            "nested `Vec<Vec<T>>` arrays are not supported; {}", UNSUPPORTED_TYPE_HINT)),
+                        element => Ok(SqlType::Array(Box::new(element))),
+                    },
+                    None => Err(unmapped_type_message(&ident, type_map)),
+                },
+                "Vec" => return Err(format!(
+                    "`Vec<T>` is only supported as a Postgres array column (except `Vec<u8>`); {}", UNSUPPORTED_TYPE_HINT)),
+                "HashMap" | "BTreeMap" | "HashSet" | "BTreeSet" => return Err(format!(
+                    "`{}` is not supported as a column type; {}", ident, UNSUPPORTED_TYPE_HINT)),
+                "DateTime" if generic_type_args(segment).is_empty() => return Ok(SqlType::Custom(ident)),
+                "u64" | "usize" if backend == Backend::Sqlite => return Err(format!(
+                    "`{}` is not supported on SQLite, whose integers are signed 64-bit; use `i64` or #[sql_type = \"...\"]", ident)),
+                name => name,
+            };
+            // 归一化后的类型名（如 `&str`、`Cow<str>` 对应的 `String`）同样先查结构体级映射
+            if let Some((_, sql_type)) = type_map.iter().find(|(name, _)| name == key) {
+                return Ok(SqlType::Custom(sql_type.clone()));
+            }
+            lookup_type(key).ok_or_else(|| unmapped_type_message(&ident, type_map))
+        }
+        Type::Reference(reference) if is_str(&reference.elem) => match type_map.iter().find(|(name, _)| name == "String") {
+            Some((_, sql_type)) => Ok(SqlType::Custom(sql_type.clone())),
+            None => Ok(lookup_type("String").unwrap()),
+        },
+        Type::Reference(_) => Err(format!(
+            "references are not supported as column types (except `&str`); {}", UNSUPPORTED_TYPE_HINT)),
+        Type::Tuple(_) => Err(format!(
+            "tuples are not supported as column types; {}", UNSUPPORTED_TYPE_HINT)),
+        _ => Err(format!("unsupported column type; {}", UNSUPPORTED_TYPE_HINT)),
+    }
+}
+
+/// 没有任何映射的类型的错误信息，类型名与已知类型相近时给出提示
+fn unmapped_type_message(ident: &str, type_map: &[(String, String)]) -> String {
+    let known: Vec<&str> = DEFAULT_TYPE_MAP.iter().map(|(name, _)| *name)
+        .chain(type_map.iter().map(|(name, _)| name.as_str()))
+        .collect();
+    let hint = match did_you_mean(ident, &known) {
+        Some(name) => format!(" (did you mean `{}`?)", name),
+        None => String::new(),
+    };
+    format!("`{}` has no known SQL type mapping{}; use #[sql_type = \"...\"] on the field or #[type_map(...)] on the struct", ident, hint)
+}
+
+/// 按 #[sql_transparent = "..."] 声明的内部类型映射SQL类型，只接受内置映射或 #[type_map] 中有的类型
+pub fn map_transparent_type(inner: &str, backend: Backend, type_map: &[(String, String)]) -> Result<SqlType, String> {
+    let ty: Type = syn::parse_str(inner).map_err(|_| format!("`{}` is not a valid type", inner))?;
+    map_type_to_sql(&ty, backend, type_map)
+}
+
+/// 将驼峰命名转换为蛇形命名，连续大写的缩写视为一个单词，如 `HTTPRequestLog` -> `http_request_log`
+pub fn camel_to_snake(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev != '_' && (prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_is_lower)) {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+/// 简单的英文复数形式：以 s/x/z/ch/sh 结尾加 `es`，否则加 `s`
+pub fn pluralize(name: &str) -> String {
+    if ["s", "x", "z", "ch", "sh"].iter().any(|suffix| name.ends_with(suffix)) {
+        format!("{}es", name)
+    } else {
+        format!("{}s", name)
+    }
+}
+
+/// 判断类型中是否出现指定标识符，用于检测字段类型是否使用了某个泛型参数
+pub fn type_contains_ident(ty: &Type, ident: &Ident) -> bool {
+    fn contains(tokens: TokenStream, ident: &Ident) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(i) => i == *ident,
+            TokenTree::Group(group) => contains(group.stream(), ident),
+            _ => false,
+        })
+    }
+    contains(ty.to_token_stream(), ident)
+}
+
+/// 判断是否为 `PhantomData<T>` 类型
+pub fn is_phantom_data(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.segments.last().unwrap().ident == "PhantomData")
+}
+
+/// 判断是否为整数类型
+pub fn is_integer_type(ty: &Type) -> bool {
     match ty {
         Type::Path(type_path) => {
             let ident = type_path.path.segments.last().unwrap().ident.to_string();
-            match ident.as_str() {
-                "i32" => "INT".into(),
-                "i64" => "BIGINT".into(),
-                "String" => "VARCHAR(255)".into(),
-                "bool" => "BOOLEAN".into(),
-                "f32" => "FLOAT".into(),
-                "f64" => "DOUBLE".into(),
-                "NaiveDateTime" => "DATETIME".into(),
-                "Uuid" => "UUID".into(),
-                _ => ident,
+            matches!(ident.as_str(), "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "isize" | "usize")
+        }
+        _ => false,
+    }
+}
+
+/// 判断是否可以用 #[decimal(...)] 指定精度：`Decimal`、`f32`、`f64` 及其 `Option`
+pub fn is_decimal_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => {
+            let segment = type_path.path.segments.last().unwrap();
+            match segment.ident.to_string().as_str() {
+                "Option" => generic_type_args(segment).first().is_some_and(|inner| is_decimal_type(inner)),
+                "Decimal" | "f32" | "f64" => true,
+                _ => false,
             }
         }
-        _ => "TEXT".into(),
+        _ => false,
     }
 }
 
-/// 获取表名，支持 #[table_name = "..."] 属性
-pub fn get_table_name(attrs: &[Attribute], default: &str) -> String {
+/// 获取 #[name = "..."] 形式属性的字符串值
+pub fn get_string_attr(attrs: &[Attribute], name: &str) -> Option<String> {
+    for attr in attrs {
+        if attr.path.is_ident(name) {
+            if let Ok(Meta::NameValue(meta)) = attr.parse_meta() {
+                if let Lit::Str(lit_str) = meta.lit {
+                    return Some(lit_str.value());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// 获取表名，支持 #[table_name = "..."] 和 #[table_naming = "..."] 属性
+///
+/// 显式的 `table_name` 优先；否则按 `table_naming` 由结构体名推导，默认为 `snake`
+pub fn get_table_name(attrs: &[Attribute], default: &str) -> syn::Result<String> {
+    let mut naming = None;
     for attr in attrs {
         if attr.path.is_ident("table_name") {
             if let Ok(Meta::NameValue(meta)) = attr.parse_meta() {
                 if let Lit::Str(lit_str) = meta.lit {
-                    return lit_str.value();
+                    return Ok(lit_str.value());
+                }
+            }
+        }
+        if attr.path.is_ident("table_naming") {
+            if let Ok(Meta::NameValue(meta)) = attr.parse_meta() {
+                if let Lit::Str(lit_str) = meta.lit {
+                    naming = Some(lit_str);
                 }
             }
         }
     }
-    default.to_lowercase()
-}
\ No newline at end of file
+    
+    let naming = match naming {
+        Some(naming) => naming,
+        None => return Ok(camel_to_snake(default)),
+    };
+    match naming.value().as_str() {
+        "snake" => Ok(camel_to_snake(default)),
+        "lower" => Ok(default.to_lowercase()),
+        "preserve" => Ok(default.to_string()),
+        "snake_plural" => Ok(pluralize(&camel_to_snake(default))),
+        _ => Err(syn::Error::new_spanned(
+            naming,
+            "unknown table naming, expected one of: snake, lower, preserve, snake_plural",
+        )),
+    }
+}
+
+/// 获取生成SQL的关键字大小写，支持 #[sql_case = "upper" | "lower"] 属性
+pub fn get_sql_case(attrs: &[Attribute]) -> syn::Result<SqlCase> {
+    for attr in attrs {
+        if attr.path.is_ident("sql_case") {
+            if let Ok(Meta::NameValue(meta)) = attr.parse_meta() {
+                if let Lit::Str(lit_str) = meta.lit {
+                    return match lit_str.value().as_str() {
+                        "upper" => Ok(SqlCase::Upper),
+                        "lower" => Ok(SqlCase::Lower),
+                        _ => Err(syn::Error::new_spanned(lit_str, "unknown sql case, expected \"upper\" or \"lower\"")),
+                    };
+                }
+            }
+        }
+    }
+    Ok(SqlCase::Upper)
+}
+
+/// 按后端规则引用SQL标识符：MySQL 使用反引号，其余使用双引号
+///
+/// 标识符包含引号字符或 NUL 时无法安全引用，返回错误信息
+pub fn quote_ident(backend: Backend, name: &str) -> Result<String, String> {
+    let quote = match backend {
+        Backend::MySql => '`',
+        Backend::Sqlite | Backend::Postgres | Backend::Any => '"',
+    };
+    if name.contains(quote) || name.contains('\0') {
+        return Err(format!("identifier `{}` must not contain {} or NUL", name.escape_default(), quote));
+    }
+    Ok(format!("{}{}{}", quote, name, quote))
+}
+
+/// 统计SQL语句中的占位符数量，忽略引号内的内容
+///
+/// `$N` 风格按不同的序号计数，同一序号可以出现多次；`?` 风格按出现次数计数
+pub fn count_placeholders(backend: Backend, sql: &str) -> usize {
+    let mut indices = HashSet::new();
+    let mut count = 0;
+    let mut quote = None;
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if matches!(c, '\'' | '"' | '`') => quote = Some(c),
+            None => match backend {
+                Backend::Sqlite | Backend::Postgres if c == '$' => {
+                    let mut index = String::new();
+                    while let Some(d) = chars.next_if(|d| d.is_ascii_digit()) {
+                        index.push(d);
+                    }
+                    if !index.is_empty() {
+                        indices.insert(index);
+                    }
+                }
+                Backend::MySql | Backend::Any if c == '?' => count += 1,
+                _ => {}
+            },
+        }
+    }
+    count + indices.len()
+}
+
+/// 计算两个字符串的编辑距离
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+/// 从候选项中找出与输入最接近的一个，距离过大时返回 `None`
+pub fn did_you_mean<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates.iter()
+        .map(|candidate| (levenshtein(name, candidate), *candidate))
+        .filter(|(distance, candidate)| *distance <= (candidate.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, ItemStruct};
+    use super::*;
+
+    #[test]
+    fn extract_comment_joins_doc_lines() {
+        let item: ItemStruct = parse_quote! {
+            ///   First line.
+            ///
+            /// Second line with  inner  spaces.
+            ///Third line.
+            struct User;
+        };
+        assert_eq!(extract_comment(&item.attrs).as_deref(), Some("First line.\nSecond line with  inner  spaces.\nThird line."));
+    }
+
+    #[test]
+    fn extract_comment_strips_block_comment_stars() {
+        let item: ItemStruct = parse_quote! {
+            /**
+             * First line.
+             * Second line.
+             */
+            struct User;
+        };
+        assert_eq!(extract_comment(&item.attrs).as_deref(), Some("First line.\nSecond line."));
+    }
+
+    #[test]
+    fn explicit_comment_wins_over_doc_comments() {
+        let item: ItemStruct = parse_quote! {
+            /// Doc comment.
+            #[comment = "explicit"]
+            /// More doc comment.
+            struct User;
+        };
+        assert_eq!(extract_comment(&item.attrs).as_deref(), Some("explicit"));
+        let item: ItemStruct = parse_quote!(struct User;);
+        assert_eq!(extract_comment(&item.attrs), None);
+    }
+
+    #[test]
+    fn camel_to_snake_splits_words_and_acronyms() {
+        for (name, expected) in [
+            ("User", "user"),
+            ("UserProfile", "user_profile"),
+            ("HTTPRequestLog", "http_request_log"),
+            ("UserID", "user_id"),
+            ("Utf8Data", "utf8_data"),
+            ("Page2Html", "page2_html"),
+            ("already_snake", "already_snake"),
+            ("Snake_Case", "snake_case"),
+        ] {
+            assert_eq!(camel_to_snake(name), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn pluralize_adds_es_after_sibilants() {
+        for (name, expected) in [
+            ("user", "users"),
+            ("address", "addresses"),
+            ("box", "boxes"),
+            ("match", "matches"),
+            ("wish", "wishes"),
+            ("http_request_log", "http_request_logs"),
+        ] {
+            assert_eq!(pluralize(name), expected, "{}", name);
+        }
+    }
+}
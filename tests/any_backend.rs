@@ -0,0 +1,37 @@
+//! `#[sql_crud(backend = "any")]` 在运行时选择数据库的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, Clone, PartialEq)]
+#[table_name = "users"]
+#[sql_crud(backend = "any")]
+struct User {
+    #[primary_key]
+    id: i64,
+    #[queryable]
+    name: String,
+    score: f64,
+}
+
+#[test]
+fn any_uses_question_mark_placeholders() {
+    assert_eq!(User::INSERT_SQL, "INSERT INTO \"users\" (\"id\", \"name\", \"score\") VALUES (?, ?, ?);");
+}
+
+#[tokio::test]
+async fn round_trips_through_an_any_pool() {
+    sqlx::any::install_default_drivers();
+    let pool = sqlx::any::AnyPoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    User::init_table(&pool).await.unwrap();
+
+    let mut alice = User { id: 1, name: "alice".into(), score: 1.5 };
+    alice.insert(&pool).await.unwrap();
+    User { id: 2, name: "bob".into(), score: 2.0 }.insert(&pool).await.unwrap();
+    assert_eq!(User::find_by_name(&pool, &"alice".to_string()).await.unwrap(), vec![alice.clone()]);
+
+    alice.score = 3.0;
+    alice.update(&pool).await.unwrap();
+    assert_eq!(User::find_by_id(&pool, &1).await.unwrap(), Some(alice.clone()));
+
+    alice.delete(&pool).await.unwrap();
+    assert_eq!(User::count(&pool).await.unwrap(), 1);
+}
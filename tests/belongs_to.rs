@@ -0,0 +1,79 @@
+//! `#[belongs_to(...)]` 多对一关系加载的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+struct Post {
+    #[primary_key]
+    id: i64,
+    #[belongs_to(User)]
+    user_id: i64,
+    #[belongs_to(User, method = "reviewer")]
+    reviewer_id: Option<i64>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "comments"]
+#[sql_crud(error)]
+struct Comment {
+    #[primary_key]
+    id: i64,
+    #[belongs_to(Post)]
+    post_id: i64,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+#[tokio::test]
+async fn loads_the_parent_record() {
+    let pool = pool().await;
+    User::init_table(&pool).await.unwrap();
+    Post::init_table(&pool).await.unwrap();
+    let alice = User { id: 1, name: "alice".to_string() };
+    let bob = User { id: 2, name: "bob".to_string() };
+    alice.insert(&pool).await.unwrap();
+    bob.insert(&pool).await.unwrap();
+    let post = Post { id: 1, user_id: 1, reviewer_id: Some(2) };
+    post.insert(&pool).await.unwrap();
+
+    assert_eq!(post.user(&pool).await.unwrap(), Some(alice));
+    assert_eq!(post.reviewer(&pool).await.unwrap(), Some(bob));
+}
+
+#[tokio::test]
+async fn dangling_foreign_key_returns_none() {
+    let pool = pool().await;
+    User::init_table(&pool).await.unwrap();
+    let post = Post { id: 1, user_id: 42, reviewer_id: Some(43) };
+
+    assert_eq!(post.user(&pool).await.unwrap(), None);
+    assert_eq!(post.reviewer(&pool).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn null_foreign_key_skips_the_query() {
+    let pool = pool().await;
+    // 没有建 users 表，查询会失败，返回 None 说明没有访问数据库
+    let post = Post { id: 1, user_id: 1, reviewer_id: None };
+
+    assert_eq!(post.reviewer(&pool).await.unwrap(), None);
+    assert!(post.user(&pool).await.is_err());
+}
+
+#[tokio::test]
+async fn uses_the_child_error_type() {
+    let pool = pool().await;
+    let comment = Comment { id: 1, post_id: 1 };
+
+    let err = comment.post(&pool).await.unwrap_err();
+    assert!(matches!(err, CommentError::Query { .. }));
+}
@@ -0,0 +1,36 @@
+//! 二进制列的往返测试
+
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+struct Attachment {
+    #[primary_key]
+    id: i64,
+    data: Vec<u8>,
+    thumbnail: Option<Vec<u8>>,
+}
+
+#[tokio::test]
+async fn blob_round_trip() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    assert!(Attachment::CREATE_TABLE_SQL.contains("\"data\" BLOB"));
+    assert!(Attachment::CREATE_TABLE_SQL.contains("\"thumbnail\" BLOB"));
+    Attachment::init_table(&pool).await.unwrap();
+
+    let payloads = [
+        Attachment { id: 1, data: Vec::new(), thumbnail: None },
+        Attachment { id: 2, data: vec![0, 1, 2, 255], thumbnail: Some(Vec::new()) },
+        Attachment { id: 3, data: (0..1024 * 1024).map(|i| i as u8).collect(), thumbnail: Some(vec![7; 16]) },
+    ];
+    for attachment in &payloads {
+        attachment.insert(&pool).await.unwrap();
+    }
+    for attachment in &payloads {
+        let found = Attachment::find_by_id(&pool, &attachment.id).await.unwrap();
+        assert_eq!(found.as_ref(), Some(attachment));
+    }
+}
@@ -0,0 +1,31 @@
+//! `#[sql_crud(blocking)]` 生成的同步方法的测试，在普通的同步代码中调用
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, Clone, PartialEq)]
+#[table_name = "users"]
+#[sql_crud(blocking)]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+#[test]
+fn blocking_methods_run_without_an_async_context() {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    let pool = runtime.block_on(sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:")).unwrap();
+    User::init_table_blocking(&pool, &runtime).unwrap();
+
+    let mut alice = User { id: 1, name: "alice".into() };
+    alice.insert_blocking(&pool, &runtime).unwrap();
+    User { id: 2, name: "bob".into() }.insert_blocking(&pool, &runtime).unwrap();
+    assert_eq!(User::count_blocking(&pool, &runtime).unwrap(), 2);
+
+    alice.name = "alicia".into();
+    alice.update_blocking(&pool, &runtime).unwrap();
+    assert_eq!(User::find_by_id_blocking(&pool, &1, &runtime).unwrap(), Some(alice.clone()));
+
+    alice.delete_blocking(&pool, &runtime).unwrap();
+    User::delete_by_id_blocking(&pool, &2, &runtime).unwrap();
+    assert!(User::find_all_blocking(&pool, &runtime).unwrap().is_empty());
+}
@@ -0,0 +1,64 @@
+//! `#[sql_crud(busy_retry(...))]` 在数据库被锁定时重试的测试
+use std::str::FromStr;
+use std::time::Duration;
+
+use macros::SqlCRUD;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Connection, SqliteConnection, SqlitePool};
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+#[sql_crud(busy_retry(attempts = 20, backoff_ms = 10, max_backoff_ms = 50))]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+#[sql_crud(busy_retry(attempts = 1))]
+struct NoRetryUser {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+/// 两个连接打开同一个数据库文件，返回的连接池不等待锁，锁冲突立即报错
+async fn open(name: &str) -> (SqlitePool, SqliteConnection) {
+    let path = std::env::temp_dir().join(format!("macros-busy-retry-{}-{}.db", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let options = SqliteConnectOptions::from_str(&format!("sqlite://{}", path.display())).unwrap()
+        .create_if_missing(true)
+        .busy_timeout(Duration::ZERO);
+    let pool = SqlitePoolOptions::new().max_connections(1).connect_with(options.clone()).await.unwrap();
+    User::init_table(&pool).await.unwrap();
+    let locker = SqliteConnection::connect_with(&options).await.unwrap();
+    (pool, locker)
+}
+
+#[tokio::test]
+async fn insert_retries_until_the_lock_is_released() {
+    let (pool, mut locker) = open("retry").await;
+    sqlx::query("BEGIN EXCLUSIVE").execute(&mut locker).await.unwrap();
+    let release = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        sqlx::query("COMMIT").execute(&mut locker).await.unwrap();
+        locker
+    });
+
+    let user = User { id: 1, name: "alice".into() };
+    user.insert(&pool).await.unwrap();
+    release.await.unwrap().close().await.unwrap();
+    assert_eq!(User::find_by_id(&pool, &1).await.unwrap(), Some(user));
+}
+
+#[tokio::test]
+async fn gives_up_with_the_original_error() {
+    let (pool, mut locker) = open("give-up").await;
+    sqlx::query("BEGIN EXCLUSIVE").execute(&mut locker).await.unwrap();
+
+    let err = NoRetryUser { id: 1, name: "alice".into() }.insert(&pool).await.unwrap_err();
+    assert!(err.to_string().contains("database is locked"), "{}", err);
+    sqlx::query("ROLLBACK").execute(&mut locker).await.unwrap();
+}
@@ -0,0 +1,59 @@
+//! `char` 字段以单字符文本存储的往返测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, Debug, Clone, PartialEq)]
+#[sql_crud(from_row)]
+struct Flag {
+    #[primary_key]
+    id: i64,
+    #[queryable]
+    code: char,
+    marker: Option<char>,
+}
+
+#[derive(SqlCRUD)]
+#[sql_crud(backend = "postgres", from_row, skip(insert, update, delete, find))]
+#[allow(dead_code)]
+struct PgFlag {
+    #[primary_key]
+    id: i64,
+    code: char,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    Flag::init_table(&pool).await.unwrap();
+    pool
+}
+
+#[test]
+fn char_columns_are_char_1() {
+    assert!(Flag::CREATE_TABLE_SQL.contains("\"code\" CHAR(1),"), "{}", Flag::CREATE_TABLE_SQL);
+    assert!(Flag::CREATE_TABLE_SQL.contains("\"marker\" CHAR(1)\n"), "{}", Flag::CREATE_TABLE_SQL);
+    assert!(PgFlag::CREATE_TABLE_SQL.contains("\"code\" CHAR(1)"), "{}", PgFlag::CREATE_TABLE_SQL);
+}
+
+#[tokio::test]
+async fn char_round_trip() {
+    let pool = pool().await;
+    let flags = [
+        Flag { id: 1, code: 'a', marker: Some('é') },
+        Flag { id: 2, code: 'Z', marker: None },
+    ];
+    for flag in &flags {
+        flag.insert(&pool).await.unwrap();
+    }
+    assert_eq!(Flag::find_all(&pool).await.unwrap(), flags);
+    assert_eq!(Flag::find_by_code(&pool, &'Z').await.unwrap(), vec![flags[1].clone()]);
+}
+
+#[tokio::test]
+async fn rejects_multi_character_text() {
+    let pool = pool().await;
+    sqlx::query("INSERT INTO \"flag\" (\"id\", \"code\") VALUES (1, 'ab')").execute(&pool).await.unwrap();
+    assert!(matches!(Flag::find_by_id(&pool, &1).await, Err(sqlx::Error::ColumnDecode { .. })));
+}
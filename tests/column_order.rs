@@ -0,0 +1,39 @@
+//! 插入、查询语句的列顺序与字段声明顺序一致
+
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD)]
+#[sql_crud(from_row)]
+#[allow(dead_code)]
+struct Profile {
+    zeta: String,
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    alpha: i32,
+    #[column = "middle_name"]
+    middle: Option<String>,
+    #[skip]
+    cache: Vec<String>,
+    beta: bool,
+}
+
+/// 取出 `start` 与 `end` 之间以逗号分隔的列名
+fn columns<'a>(sql: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let from = sql.find(start).unwrap() + start.len();
+    let to = from + sql[from..].find(end).unwrap();
+    sql[from..to].split(", ").map(|column| column.trim_matches('"')).collect()
+}
+
+#[test]
+fn insert_and_select_follow_field_order() {
+    let fields = ["zeta", "id", "alpha", "middle_name", "beta"];
+    let select = columns(Profile::SELECT_SQL, "SELECT ", " FROM");
+    let select_by_id = columns(Profile::SELECT_BY_ID_SQL, "SELECT ", " FROM");
+    let insert = columns(Profile::INSERT_SQL, "(", ")");
+
+    assert_eq!(select, fields);
+    assert_eq!(select_by_id, fields);
+    // 自增主键由数据库赋值，不出现在插入列中，其余顺序不变
+    assert_eq!(insert, fields.iter().copied().filter(|field| *field != "id").collect::<Vec<_>>());
+}
@@ -0,0 +1,88 @@
+//! 表和列注释的建表语句测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_name = "users"]
+#[comment = "registered users"]
+#[sql_crud(backend = "postgres")]
+struct PgUser {
+    #[primary_key]
+    id: i64,
+    #[comment = "login e-mail, it's unique"]
+    email: String,
+    name: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_name = "users"]
+#[comment = "registered users"]
+#[sql_crud(backend = "mysql")]
+struct MySqlUser {
+    #[primary_key]
+    id: i64,
+    #[comment = "login e-mail"]
+    email: String,
+}
+
+#[test]
+fn postgres_uses_comment_on_statements() {
+    assert!(!PgUser::CREATE_TABLE_SQL.contains("COMMENT"), "{}", PgUser::CREATE_TABLE_SQL);
+    assert_eq!(PgUser::COMMENT_SQLS, [
+        "COMMENT ON TABLE \"users\" IS 'registered users';",
+        "COMMENT ON COLUMN \"users\".\"email\" IS 'login e-mail, it''s unique';",
+    ]);
+}
+
+#[test]
+fn mysql_keeps_inline_comments() {
+    assert!(MySqlUser::CREATE_TABLE_SQL.contains("`email` VARCHAR(255) COMMENT 'login e-mail'"));
+    assert!(MySqlUser::CREATE_TABLE_SQL.ends_with(") COMMENT 'registered users';"));
+}
+
+/// 用户表
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+struct SqliteUser {
+    #[primary_key]
+    id: i64,
+    /// 用户名
+    name: String,
+    #[comment = "login e-mail"]
+    email: String,
+}
+
+#[tokio::test]
+async fn sqlite_ignores_comments() {
+    assert!(!SqliteUser::CREATE_TABLE_SQL.contains("COMMENT"), "{}", SqliteUser::CREATE_TABLE_SQL);
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    SqliteUser::init_table(&pool).await.unwrap();
+    let user = SqliteUser { id: 1, name: "alice".into(), email: "alice@example.com".into() };
+    user.insert(&pool).await.unwrap();
+    assert_eq!(SqliteUser::find_by_id(&pool, &1).await.unwrap(), Some(user));
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_name = "users"]
+#[sql_crud(backend = "postgres")]
+struct PgDocUser {
+    #[primary_key]
+    id: i64,
+    /// 登录邮箱，
+    /// 全局唯一，
+    /// 不区分大小写
+    email: String,
+    /// 被显式注释覆盖的文档注释
+    #[comment = "display name"]
+    name: String,
+}
+
+#[test]
+fn doc_comments_become_column_comments() {
+    assert_eq!(PgDocUser::COMMENT_SQLS, [
+        "COMMENT ON COLUMN \"users\".\"email\" IS '登录邮箱，\n全局唯一，\n不区分大小写';",
+        "COMMENT ON COLUMN \"users\".\"name\" IS 'display name';",
+    ]);
+}
@@ -0,0 +1,48 @@
+//! 结构体上 `#[unique(...)]` 联合唯一约束的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "memberships"]
+#[unique(tenant_id, email)]
+#[unique(tenant_id, slug)]
+#[allow(clippy::duplicated_attributes)]
+struct Membership {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    tenant_id: i64,
+    #[column = "email_address"]
+    #[sqlx(rename = "email_address")]
+    email: String,
+    slug: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[table_name = "memberships"]
+#[sql_crud(backend = "mysql", skip(insert, update, delete, find))]
+#[unique(tenant_id, email)]
+#[allow(dead_code)]
+struct MySqlMembership {
+    #[primary_key]
+    id: i64,
+    tenant_id: i64,
+    email: String,
+}
+
+#[test]
+fn emits_table_level_unique_constraints() {
+    let sql = Membership::CREATE_TABLE_SQL;
+    assert!(sql.contains(",\n    UNIQUE (\"tenant_id\", \"email_address\"),\n    UNIQUE (\"tenant_id\", \"slug\")\n)"), "{}", sql);
+    assert!(MySqlMembership::CREATE_TABLE_SQL.contains("UNIQUE (`tenant_id`, `email`)"), "{}", MySqlMembership::CREATE_TABLE_SQL);
+}
+
+#[tokio::test]
+async fn rejects_duplicate_pairs() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    Membership::init_table(&pool).await.unwrap();
+    let member = |tenant_id, email: &str, slug: &str| Membership { id: 0, tenant_id, email: email.into(), slug: slug.into() };
+    member(1, "a@example.com", "a").insert(&pool).await.unwrap();
+    member(2, "a@example.com", "a").insert(&pool).await.unwrap();
+    assert!(member(1, "a@example.com", "b").insert(&pool).await.is_err());
+    assert!(member(1, "b@example.com", "a").insert(&pool).await.is_err());
+}
@@ -0,0 +1,45 @@
+//! `count_where`、`exists_where` 按子句和绑定参数统计的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "scores"]
+struct Score {
+    #[primary_key]
+    id: i64,
+    points: i32,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[table_name = "scores"]
+#[sql_crud(backend = "postgres", skip(insert, update, delete))]
+#[allow(dead_code)]
+struct PgScore {
+    #[primary_key]
+    id: i64,
+    points: i32,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    Score::init_table(&pool).await.unwrap();
+    for (id, points) in [(1, 10), (2, 20), (3, 30)] {
+        Score { id, points }.insert(&pool).await.unwrap();
+    }
+    pool
+}
+
+#[tokio::test]
+async fn counts_matching_rows() {
+    let pool = pool().await;
+    assert_eq!(Score::count_where(&pool, "points >= $1 AND points < $2", |q| q.bind(15).bind(40)).await.unwrap(), 2);
+    assert_eq!(Score::count_where(&pool, "points > $1", |q| q.bind(100)).await.unwrap(), 0);
+    assert_eq!(Score::count_where(&pool, "", |q| q).await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn checks_existence() {
+    let pool = pool().await;
+    assert!(Score::exists_where(&pool, "points = $1", |q| q.bind(20)).await.unwrap());
+    assert!(!Score::exists_where(&pool, "points > $1", |q| q.bind(100)).await.unwrap());
+    assert!(Score::exists_where(&pool, "", |q| q).await.unwrap());
+}
@@ -0,0 +1,49 @@
+//! `#[decimal(...)]` 的建表语句和往返测试
+
+use macros::SqlCRUD;
+use rust_decimal::Decimal;
+
+// sqlx 的 SQLite 驱动不支持 Decimal，这里只检查建表语句
+#[derive(SqlCRUD)]
+#[sql_crud(skip(insert, update, delete, find))]
+#[allow(dead_code)]
+struct Invoice {
+    #[primary_key]
+    id: i64,
+    total: Decimal,
+    #[decimal(precision = 19, scale = 4)]
+    amount: Decimal,
+    #[decimal(precision = 10)]
+    discount: Option<Decimal>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+struct Measurement {
+    #[primary_key]
+    id: i64,
+    #[decimal(precision = 10, scale = 4)]
+    value: f64,
+}
+
+#[test]
+fn decimal_ddl() {
+    assert!(Invoice::CREATE_TABLE_SQL.contains("\"total\" NUMERIC,"));
+    assert!(Invoice::CREATE_TABLE_SQL.contains("\"amount\" NUMERIC(19,4)"));
+    assert!(Invoice::CREATE_TABLE_SQL.contains("\"discount\" NUMERIC(10,0)"));
+}
+
+#[tokio::test]
+async fn float_numeric_round_trip() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    assert!(Measurement::CREATE_TABLE_SQL.contains("\"value\" NUMERIC(10,4)"));
+    Measurement::init_table(&pool).await.unwrap();
+
+    let measurement = Measurement { id: 1, value: 123456.7891 };
+    measurement.insert(&pool).await.unwrap();
+    let found = Measurement::find_by_id(&pool, &1).await.unwrap();
+    assert_eq!(found, Some(measurement));
+}
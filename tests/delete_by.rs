@@ -0,0 +1,75 @@
+//! 按列删除记录的 `delete_by_<column>` 测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "sessions"]
+struct Session {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    #[queryable]
+    session_id: String,
+    #[queryable]
+    user_id: Option<i64>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "tokens"]
+#[sql_crud(error)]
+struct Token {
+    #[primary_key]
+    id: i64,
+    #[queryable]
+    owner: String,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+async fn insert(pool: &sqlx::SqlitePool, session_id: &str, user_id: Option<i64>) {
+    Session { id: 0, session_id: session_id.to_string(), user_id }.insert(pool).await.unwrap();
+}
+
+#[tokio::test]
+async fn deletes_matching_rows_and_returns_count() {
+    let pool = pool().await;
+    Session::init_table(&pool).await.unwrap();
+    insert(&pool, "a", Some(1)).await;
+    insert(&pool, "a", Some(2)).await;
+    insert(&pool, "b", Some(1)).await;
+
+    assert_eq!(Session::delete_by_session_id(&pool, &"a".to_string()).await.unwrap(), 2);
+    assert_eq!(Session::delete_by_session_id(&pool, &"a".to_string()).await.unwrap(), 0);
+    assert_eq!(Session::count(&pool).await.unwrap(), 1);
+    assert_eq!(Session::find_by_session_id(&pool, &"b".to_string()).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn option_columns_and_transactions() {
+    let pool = pool().await;
+    Session::init_table(&pool).await.unwrap();
+    insert(&pool, "a", Some(7)).await;
+    insert(&pool, "b", Some(7)).await;
+    insert(&pool, "c", None).await;
+
+    let mut tx = pool.begin().await.unwrap();
+    assert_eq!(Session::delete_by_user_id(&mut *tx, &Some(7)).await.unwrap(), 2);
+    tx.rollback().await.unwrap();
+    assert_eq!(Session::count(&pool).await.unwrap(), 3);
+
+    assert_eq!(Session::delete_by_user_id(&pool, &Some(7)).await.unwrap(), 2);
+    assert_eq!(Session::count(&pool).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn sql_and_error_type() {
+    let pool = pool().await;
+    match Token::delete_by_owner(&pool, &"x".to_string()).await.unwrap_err() {
+        TokenError::Query { operation, sql, .. } => {
+            assert_eq!(operation, "delete_by_owner");
+            assert_eq!(sql, "DELETE FROM \"tokens\" WHERE \"owner\" = $1;");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
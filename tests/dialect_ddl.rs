@@ -0,0 +1,125 @@
+//! 各后端默认列类型的建表语句矩阵测试
+use macros::SqlCRUD;
+
+/// 只用类型名参与映射，建表语句不需要 chrono 本身
+mod chrono {
+    pub struct NaiveDateTime;
+    pub struct NaiveDate;
+    pub struct NaiveTime;
+    pub struct Utc;
+    pub struct DateTime<Tz>(pub Tz);
+}
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+macro_rules! all_types {
+    ($name:ident, $backend:literal) => {
+        #[derive(SqlCRUD)]
+        #[sql_crud(backend = $backend, skip(insert, update, delete, find))]
+        #[table_name = "t"]
+        #[allow(dead_code)]
+        struct $name {
+            #[primary_key]
+            id: i64,
+            int: i32,
+            text: String,
+            flag: bool,
+            float: f32,
+            double: f64,
+            naive_datetime: NaiveDateTime,
+            datetime: DateTime<Utc>,
+            date: NaiveDate,
+            time: NaiveTime,
+            offset_datetime: time::OffsetDateTime,
+            primitive_datetime: time::PrimitiveDateTime,
+            blob: Vec<u8>,
+            #[sql_type = "MONEY"]
+            custom: i64,
+        }
+    };
+}
+
+all_types!(SqliteTypes, "sqlite");
+all_types!(PgTypes, "postgres");
+all_types!(MySqlTypes, "mysql");
+
+/// 按列名取出建表语句中的列类型
+fn column_types(sql: &str) -> Vec<(String, String)> {
+    sql.lines()
+        .skip(1)
+        .filter(|line| !line.starts_with(')'))
+        .map(|line| {
+            let line = line.trim().trim_end_matches(',');
+            let (name, ty) = line.split_once(' ').unwrap();
+            (name.trim_matches(|c| c == '"' || c == '`').to_string(), ty.to_string())
+        })
+        .collect()
+}
+
+fn expect(sql: &str, expected: &[(&str, &str)]) {
+    let actual = column_types(sql);
+    let expected: Vec<(String, String)> = expected.iter()
+        .map(|(name, ty)| (name.to_string(), ty.to_string()))
+        .collect();
+    assert_eq!(actual, expected, "{}", sql);
+}
+
+#[test]
+fn sqlite_matrix() {
+    expect(SqliteTypes::CREATE_TABLE_SQL, &[
+        ("id", "BIGINT PRIMARY KEY"),
+        ("int", "INT"),
+        ("text", "VARCHAR(255)"),
+        ("flag", "INTEGER"),
+        ("float", "FLOAT"),
+        ("double", "DOUBLE"),
+        ("naive_datetime", "DATETIME"),
+        ("datetime", "TIMESTAMP"),
+        ("date", "DATE"),
+        ("time", "TIME"),
+        ("offset_datetime", "TIMESTAMP"),
+        ("primitive_datetime", "DATETIME"),
+        ("blob", "BLOB"),
+        ("custom", "MONEY"),
+    ]);
+}
+
+#[test]
+fn postgres_matrix() {
+    expect(PgTypes::CREATE_TABLE_SQL, &[
+        ("id", "BIGINT PRIMARY KEY"),
+        ("int", "INT"),
+        ("text", "VARCHAR(255)"),
+        ("flag", "BOOLEAN"),
+        ("float", "REAL"),
+        ("double", "DOUBLE PRECISION"),
+        ("naive_datetime", "TIMESTAMP"),
+        ("datetime", "TIMESTAMPTZ"),
+        ("date", "DATE"),
+        ("time", "TIME"),
+        ("offset_datetime", "TIMESTAMPTZ"),
+        ("primitive_datetime", "TIMESTAMP"),
+        ("blob", "BYTEA"),
+        ("custom", "MONEY"),
+    ]);
+}
+
+#[test]
+fn mysql_matrix() {
+    expect(MySqlTypes::CREATE_TABLE_SQL, &[
+        ("id", "BIGINT PRIMARY KEY"),
+        ("int", "INT"),
+        ("text", "VARCHAR(255)"),
+        ("flag", "TINYINT(1)"),
+        ("float", "FLOAT"),
+        ("double", "DOUBLE"),
+        ("naive_datetime", "DATETIME"),
+        ("datetime", "TIMESTAMP"),
+        ("date", "DATE"),
+        ("time", "TIME"),
+        ("offset_datetime", "TIMESTAMP"),
+        ("primitive_datetime", "DATETIME"),
+        ("blob", "BLOB"),
+        ("custom", "MONEY"),
+    ]);
+}
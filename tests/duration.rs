@@ -0,0 +1,88 @@
+//! `#[duration(...)]` 把 `Duration` 存为整数的往返测试
+use std::time::Duration;
+
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, Debug, PartialEq)]
+#[sql_crud(from_row)]
+struct Probe {
+    #[primary_key]
+    id: i64,
+    #[duration(millis)]
+    latency: Duration,
+    #[duration(seconds)]
+    timeout: Option<Duration>,
+    #[duration(micros)]
+    #[queryable]
+    jitter: Duration,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    Probe::init_table(&pool).await.unwrap();
+    pool
+}
+
+#[test]
+fn duration_columns_are_bigint() {
+    assert!(Probe::CREATE_TABLE_SQL.contains("\"latency\" BIGINT,"));
+    assert!(Probe::CREATE_TABLE_SQL.contains("\"timeout\" BIGINT,"));
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let pool = pool().await;
+    let probes = [
+        Probe { id: 1, latency: Duration::ZERO, timeout: None, jitter: Duration::ZERO },
+        Probe { id: 2, latency: Duration::from_millis(1500), timeout: Some(Duration::from_secs(30)), jitter: Duration::from_micros(7) },
+        Probe {
+            id: 3,
+            latency: Duration::from_millis(i64::MAX as u64),
+            timeout: Some(Duration::from_secs(i64::MAX as u64)),
+            jitter: Duration::from_micros(i64::MAX as u64),
+        },
+    ];
+    for probe in &probes {
+        probe.insert(&pool).await.unwrap();
+    }
+    for probe in probes {
+        assert_eq!(Probe::find_by_id(&pool, &probe.id).await.unwrap(), Some(probe));
+    }
+    assert_eq!(Probe::count_by_jitter(&pool, &Duration::from_micros(7)).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn sub_unit_precision_is_truncated() {
+    let pool = pool().await;
+    let mut probe = Probe {
+        id: 1,
+        latency: Duration::from_micros(2999),
+        timeout: Some(Duration::from_millis(59_999)),
+        jitter: Duration::from_nanos(1999),
+    };
+    probe.insert(&pool).await.unwrap();
+    let stored = Probe::find_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(stored.latency, Duration::from_millis(2));
+    assert_eq!(stored.timeout, Some(Duration::from_secs(59)));
+    assert_eq!(stored.jitter, Duration::from_micros(1));
+
+    probe.latency = Duration::from_millis(5);
+    probe.update(&pool).await.unwrap();
+    assert_eq!(Probe::find_by_id(&pool, &1).await.unwrap().unwrap().latency, Duration::from_millis(5));
+}
+
+#[tokio::test]
+async fn negative_values_fail_to_decode() {
+    let pool = pool().await;
+    let sql = format!("INSERT INTO {} (id, latency, timeout, jitter) VALUES (1, -1, NULL, 0)", Probe::table_name());
+    sqlx::query(&sql)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let err = Probe::find_by_id(&pool, &1).await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::ColumnDecode { ref index, .. } if index == "latency"), "{err:?}");
+}
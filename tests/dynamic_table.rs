@@ -0,0 +1,87 @@
+//! `#[sql_crud(dynamic_table)]` 在运行时指定表名的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq, Clone)]
+#[table_name = "events"]
+#[sql_crud(dynamic_table)]
+struct Event {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    kind: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "counters"]
+#[sql_crud(dynamic_table, error)]
+struct Counter {
+    #[primary_key]
+    name: String,
+    value: i64,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+#[tokio::test]
+async fn tenant_tables_are_isolated() {
+    let pool = pool().await;
+    Event::init_table_in(&pool, "events_tenant1").await.unwrap();
+    Event::init_table_in(&pool, "events_tenant2").await.unwrap();
+
+    let mut click = Event { id: 0, kind: "click".to_string() };
+    click.id = click.insert_in_table(&pool, "events_tenant1").await.unwrap();
+    Event { id: 0, kind: "view".to_string() }.insert_in_table(&pool, "events_tenant2").await.unwrap();
+    Event { id: 0, kind: "scroll".to_string() }.insert_in_table(&pool, "events_tenant2").await.unwrap();
+
+    assert_eq!(Event::find_all_in_table(&pool, "events_tenant1").await.unwrap(), vec![click]);
+    assert_eq!(Event::count_in_table(&pool, "events_tenant2").await.unwrap(), 2);
+
+    let raw: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM events_tenant1").fetch_one(&pool).await.unwrap();
+    assert_eq!(raw, 1);
+}
+
+#[tokio::test]
+async fn update_find_and_delete_in_table() {
+    let pool = pool().await;
+    Event::init_table_in(&pool, "shard_a").await.unwrap();
+    let mut event = Event { id: 0, kind: "before".to_string() };
+    event.id = event.insert_in_table(&pool, "shard_a").await.unwrap();
+
+    event.kind = "after".to_string();
+    event.update_in_table(&pool, "shard_a").await.unwrap();
+    assert_eq!(Event::find_by_id_in_table(&pool, "shard_a", &event.id).await.unwrap(), Some(event.clone()));
+
+    event.delete_in_table(&pool, "shard_a").await.unwrap();
+    let id = Event { id: 0, kind: "again".to_string() }.insert_in_table(&pool, "shard_a").await.unwrap();
+    Event::delete_by_id_in_table(&pool, "shard_a", &id).await.unwrap();
+    assert_eq!(Event::count_in_table(&pool, "shard_a").await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn invalid_table_names_are_rejected() {
+    let pool = pool().await;
+    for table in ["", "1events", "events; DROP TABLE x", "events\"", "événements", "a-b"] {
+        let err = Event::count_in_table(&pool, table).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::Configuration(_)), "{}: {:?}", table, err);
+    }
+    assert!(Event::init_table_in(&pool, "_ok_123").await.is_ok());
+}
+
+#[tokio::test]
+async fn error_type_records_the_operation() {
+    let pool = pool().await;
+    Counter::init_table_in(&pool, "counters_x").await.unwrap();
+    Counter { name: "a".to_string(), value: 1 }.insert_in_table(&pool, "counters_x").await.unwrap();
+
+    match Counter::find_all_in_table(&pool, "bad name").await.unwrap_err() {
+        CounterError::Query { operation, source, .. } => {
+            assert_eq!(operation, "find_all");
+            assert!(matches!(source, sqlx::Error::Configuration(_)));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    let err = Counter { name: "missing".to_string(), value: 0 }.update_in_table(&pool, "counters_x").await.unwrap_err();
+    assert!(matches!(err, CounterError::NotFound { operation: "update", .. }));
+}
@@ -0,0 +1,82 @@
+//! `#[sql_enum]` 枚举作为主键的测试
+use macros::SqlCRUD;
+
+#[derive(sqlx::Type, Debug, PartialEq)]
+#[sqlx(rename_all = "lowercase")]
+enum Region {
+    North,
+    South,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "regions"]
+struct RegionInfo {
+    #[primary_key]
+    #[sql_enum(text, values("north", "south"))]
+    region: Region,
+    label: String,
+}
+
+#[derive(SqlCRUD)]
+#[sql_crud(backend = "mysql", skip(insert, update, delete, find))]
+#[table_name = "regions"]
+#[allow(dead_code)]
+struct MySqlRegionInfo {
+    #[primary_key]
+    #[sql_enum(text)]
+    region: Region,
+}
+
+#[derive(SqlCRUD)]
+#[sql_crud(backend = "postgres", skip(insert, update, delete, find))]
+#[table_name = "regions"]
+#[allow(dead_code)]
+struct PgRegionInfo {
+    #[primary_key]
+    #[sql_enum(text)]
+    region: Region,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+#[test]
+fn ddl_uses_text_primary_key() {
+    assert!(RegionInfo::CREATE_TABLE_SQL.contains("\"region\" TEXT PRIMARY KEY CHECK (\"region\" IN ('north', 'south'))"), "{}", RegionInfo::CREATE_TABLE_SQL);
+    assert!(PgRegionInfo::CREATE_TABLE_SQL.contains("\"region\" TEXT PRIMARY KEY"), "{}", PgRegionInfo::CREATE_TABLE_SQL);
+    // MySQL 的 TEXT 列作为键需要前缀长度
+    assert!(MySqlRegionInfo::CREATE_TABLE_SQL.contains("`region` VARCHAR(255) PRIMARY KEY"), "{}", MySqlRegionInfo::CREATE_TABLE_SQL);
+}
+
+#[tokio::test]
+async fn helpers_take_the_enum_by_reference() {
+    let pool = pool().await;
+    RegionInfo::init_table(&pool).await.unwrap();
+    RegionInfo { region: Region::North, label: "n".to_string() }.insert(&pool).await.unwrap();
+    RegionInfo { region: Region::South, label: "s".to_string() }.insert(&pool).await.unwrap();
+
+    let north: &Region = &Region::North;
+    let found = RegionInfo::find_by_id(&pool, north).await.unwrap();
+    assert_eq!(found, Some(RegionInfo { region: Region::North, label: "n".to_string() }));
+
+    RegionInfo { region: Region::North, label: "renamed".to_string() }.update(&pool).await.unwrap();
+    assert_eq!(RegionInfo::find_by_id(&pool, north).await.unwrap().unwrap().label, "renamed");
+
+    RegionInfo::delete_by_id(&pool, &Region::South).await.unwrap();
+    assert_eq!(RegionInfo::find_by_id(&pool, &Region::South).await.unwrap(), None);
+    assert_eq!(RegionInfo::count(&pool).await.unwrap(), 1);
+
+    let stored: String = sqlx::query_scalar("SELECT region FROM regions").fetch_one(&pool).await.unwrap();
+    assert_eq!(stored, "north");
+}
+
+#[tokio::test]
+async fn duplicate_and_unknown_keys_are_rejected() {
+    let pool = pool().await;
+    RegionInfo::init_table(&pool).await.unwrap();
+    RegionInfo { region: Region::North, label: "a".to_string() }.insert(&pool).await.unwrap();
+
+    assert!(RegionInfo { region: Region::North, label: "b".to_string() }.insert(&pool).await.is_err());
+    assert!(sqlx::query("INSERT INTO regions (region, label) VALUES ('east', 'e')").execute(&pool).await.is_err());
+}
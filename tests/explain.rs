@@ -0,0 +1,29 @@
+//! `explain_*` 查询计划方法的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    id: i64,
+    #[queryable]
+    email: String,
+}
+
+#[tokio::test]
+async fn plan_reflects_indexes() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    User::init_table(&pool).await.unwrap();
+
+    let plan = User::explain_find_by_email(&pool).await.unwrap();
+    assert!(plan.iter().all(|detail| !detail.contains("USING INDEX")), "{:?}", plan);
+    assert!(plan.iter().any(|detail| detail.starts_with("SCAN")), "{:?}", plan);
+
+    sqlx::query("CREATE INDEX idx_users_email ON users (email)").execute(&pool).await.unwrap();
+    let plan = User::explain_find_by_email(&pool).await.unwrap();
+    assert!(plan.iter().any(|detail| detail.contains("USING INDEX idx_users_email")), "{:?}", plan);
+
+    let plan = User::explain_find_by_id(&pool).await.unwrap();
+    assert!(plan.iter().any(|detail| detail.starts_with("SEARCH")), "{:?}", plan);
+    assert!(!User::explain_find_all(&pool).await.unwrap().is_empty());
+}
@@ -0,0 +1,61 @@
+//! `#[sql_crud(filter)]` 生成的 `<Name>Filter` 的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "products"]
+#[sql_crud(filter)]
+struct Product {
+    #[primary_key]
+    id: i32,
+    name: String,
+    #[column = "stock_count"]
+    #[sqlx(rename = "stock_count")]
+    stock: i64,
+    note: Option<String>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[table_name = "products"]
+#[sql_crud(filter, backend = "mysql", skip(insert, update, delete))]
+#[allow(dead_code)]
+struct MySqlProduct {
+    #[primary_key]
+    id: i32,
+    name: String,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    Product::init_table(&pool).await.unwrap();
+    let products = [(1, "apple", 5, None), (2, "apricot", 0, Some("seasonal")), (3, "banana", 7, None), (4, "avocado", 3, None)];
+    for (id, name, stock, note) in products {
+        Product { id, name: name.into(), stock, note: note.map(Into::into) }.insert(&pool).await.unwrap();
+    }
+    pool
+}
+
+#[test]
+fn builds_sql_from_column_names() {
+    let filter = Product::filter().name_like("a%").stock_ne(&0).id_in(&[1, 2, 4]);
+    assert_eq!(filter.sql(), "SELECT \"id\", \"name\", \"stock_count\", \"note\" FROM \"products\" \
+        WHERE \"name\" LIKE $1 AND \"stock_count\" <> $2 AND \"id\" IN ($3, $4, $5);");
+    assert_eq!(Product::filter().sql(), "SELECT \"id\", \"name\", \"stock_count\", \"note\" FROM \"products\";");
+    assert_eq!(MySqlProduct::filter().name_eq("x").id_in(&[1, 2]).sql(), "SELECT `id`, `name` FROM `products` WHERE `name` = ? AND `id` IN (?, ?);");
+}
+
+#[tokio::test]
+async fn fetches_and_counts_matching_records() {
+    let pool = pool().await;
+    let products = Product::filter().name_like("a%").stock_ne(&0).id_in(&[1, 2, 4]).fetch(&pool).await.unwrap();
+    assert_eq!(products.iter().map(|product| product.id).collect::<Vec<_>>(), vec![1, 4]);
+    assert_eq!(Product::filter().note_is_null().count(&pool).await.unwrap(), 3);
+    assert_eq!(Product::filter().name_in(&["banana", "kiwi"]).count(&pool).await.unwrap(), 1);
+    assert_eq!(Product::filter().id_in(&[]).count(&pool).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn deletes_matching_records() {
+    let pool = pool().await;
+    assert_eq!(Product::filter().note_eq("seasonal").delete(&pool).await.unwrap(), 1);
+    assert_eq!(Product::filter().count(&pool).await.unwrap(), 3);
+}
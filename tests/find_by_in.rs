@@ -0,0 +1,47 @@
+//! `find_by_<column>_in` 的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    id: i64,
+    #[queryable]
+    name: String,
+    #[queryable]
+    code: i64,
+}
+
+async fn pool_with_users() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    User::init_table(&pool).await.unwrap();
+    for (id, name) in [(1, "alice"), (2, "bob"), (3, "carol")] {
+        User { id, name: name.into(), code: id * 10 }.insert(&pool).await.unwrap();
+    }
+    pool
+}
+
+#[tokio::test]
+async fn empty_slice_returns_nothing() {
+    // 未建表的连接池也能调用，说明空列表时不访问数据库
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    assert!(User::find_by_code_in(&pool, &[]).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn finds_every_listed_value() {
+    let pool = pool_with_users().await;
+    let users = User::find_by_name_in(&pool, &["alice".to_string(), "carol".to_string(), "dave".to_string()]).await.unwrap();
+    assert_eq!(users.iter().map(|user| user.id).collect::<Vec<_>>(), vec![1, 3]);
+}
+
+#[tokio::test]
+async fn splits_values_over_the_parameter_limit() {
+    let pool = pool_with_users().await;
+    // 超过 SQLite 默认的 32766 个参数，需要分两批查询
+    let mut codes: Vec<i64> = (100..33_000).collect();
+    codes.push(20);
+    codes.insert(0, 10);
+    let users = User::find_by_code_in(&pool, &codes).await.unwrap();
+    assert_eq!(users.iter().map(|user| user.id).collect::<Vec<_>>(), vec![1, 2]);
+}
@@ -0,0 +1,26 @@
+//! `find_first_by_<column>` 的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    id: i64,
+    #[queryable]
+    team: String,
+}
+
+#[tokio::test]
+async fn returns_one_matching_row_or_none() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    User::init_table(&pool).await.unwrap();
+    for (id, team) in [(1, "red"), (2, "blue"), (3, "red")] {
+        User { id, team: team.into() }.insert(&pool).await.unwrap();
+    }
+
+    let first = User::find_first_by_team(&pool, &"red".to_string()).await.unwrap().unwrap();
+    assert_eq!(first.team, "red");
+    assert!([1, 3].contains(&first.id));
+    assert_eq!(User::find_first_by_team(&pool, &"blue".to_string()).await.unwrap(), Some(User { id: 2, team: "blue".into() }));
+    assert_eq!(User::find_first_by_team(&pool, &"green".to_string()).await.unwrap(), None);
+}
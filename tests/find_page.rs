@@ -0,0 +1,56 @@
+//! `find_page` 分页查询和总数的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "items"]
+struct Item {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "missing"]
+#[sql_crud(error)]
+struct Missing {
+    #[primary_key]
+    id: i64,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+#[tokio::test]
+async fn pages_are_ordered_by_primary_key() {
+    let pool = pool().await;
+    Item::init_table(&pool).await.unwrap();
+    for id in [3, 1, 5, 2, 4] {
+        Item { id, name: format!("item{}", id) }.insert(&pool).await.unwrap();
+    }
+
+    let ids = |items: Vec<Item>| items.into_iter().map(|item| item.id).collect::<Vec<_>>();
+    let (first, total) = Item::find_page(&pool, 2, 0).await.unwrap();
+    assert_eq!((ids(first), total), (vec![1, 2], 5));
+    let (second, _) = Item::find_page(&pool, 2, 2).await.unwrap();
+    assert_eq!(ids(second), [3, 4]);
+    let (last, total) = Item::find_page(&pool, 2, 4).await.unwrap();
+    assert_eq!((ids(last), total), (vec![5], 5));
+    let (past_end, total) = Item::find_page(&pool, 2, 10).await.unwrap();
+    assert_eq!((past_end.len(), total), (0, 5));
+}
+
+#[tokio::test]
+async fn errors_report_the_failing_query() {
+    let pool = pool().await;
+    match Missing::find_page(&pool, 10, 0).await.unwrap_err() {
+        MissingError::Query { operation, sql, .. } => {
+            assert_eq!(operation, "find_page");
+            assert_eq!(sql, "SELECT \"id\" FROM \"missing\" ORDER BY \"id\" LIMIT $1 OFFSET $2;");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    // 失败的事务已回滚，连接可以继续使用
+    Missing::init_table(&pool).await.unwrap();
+    assert_eq!(Missing::find_page(&pool, 10, 0).await.unwrap(), (vec![], 0));
+}
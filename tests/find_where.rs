@@ -0,0 +1,43 @@
+//! `find_where`、`find_one_where` 按子句和绑定参数查询的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "tasks"]
+struct Task {
+    #[primary_key]
+    id: i64,
+    owner: String,
+    priority: i32,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    Task::init_table(&pool).await.unwrap();
+    for (id, owner, priority) in [(1, "ann", 1), (2, "ann", 5), (3, "bob", 5), (4, "ann", 9)] {
+        Task { id, owner: owner.into(), priority }.insert(&pool).await.unwrap();
+    }
+    pool
+}
+
+#[tokio::test]
+async fn binds_parameters_into_the_clause() {
+    let pool = pool().await;
+    let owner = String::from("ann");
+    let tasks = Task::find_where(&pool, "owner = $1 AND priority >= $2 ORDER BY id", |q| q.bind(owner.clone()).bind(5)).await.unwrap();
+    assert_eq!(tasks.iter().map(|task| task.id).collect::<Vec<_>>(), vec![2, 4]);
+}
+
+#[tokio::test]
+async fn empty_clause_returns_every_record() {
+    let pool = pool().await;
+    assert_eq!(Task::find_where(&pool, " ", |q| q).await.unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn finds_one_or_none() {
+    let pool = pool().await;
+    let task = Task::find_one_where(&pool, "owner = $1 AND priority = $2", |q| q.bind("bob").bind(5)).await.unwrap();
+    assert_eq!(task.map(|task| task.id), Some(3));
+    let missing = Task::find_one_where(&pool, "owner = $1 AND priority = $2", |q| q.bind("bob").bind(1)).await.unwrap();
+    assert!(missing.is_none());
+}
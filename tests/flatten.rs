@@ -0,0 +1,70 @@
+//! `#[flatten(...)]` 嵌入结构体展开为列的测试
+use macros::SqlCRUD;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Address {
+    street: String,
+    city: String,
+    zip: Option<String>,
+}
+
+#[derive(SqlCRUD, Debug, Clone, PartialEq)]
+#[table_name = "customers"]
+#[sql_crud(from_row)]
+struct Customer {
+    #[primary_key]
+    id: i64,
+    name: String,
+    #[flatten(prefix = "address_", fields(street: "VARCHAR(255)", city: "VARCHAR(255)", zip: "VARCHAR(16)"))]
+    address: Address,
+    #[flatten(fields(street: "TEXT", city: "TEXT", zip: "TEXT"))]
+    billing: Address,
+    active: bool,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+fn address(street: &str, city: &str, zip: Option<&str>) -> Address {
+    Address { street: street.to_string(), city: city.to_string(), zip: zip.map(str::to_string) }
+}
+
+#[test]
+fn sub_fields_become_prefixed_columns() {
+    assert_eq!(Customer::columns(), [
+        ("id", "BIGINT"),
+        ("name", "VARCHAR(255)"),
+        ("address_street", "VARCHAR(255)"),
+        ("address_city", "VARCHAR(255)"),
+        ("address_zip", "VARCHAR(16)"),
+        ("billing_street", "TEXT"),
+        ("billing_city", "TEXT"),
+        ("billing_zip", "TEXT"),
+        ("active", "INTEGER"),
+    ]);
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let pool = pool().await;
+    Customer::init_table(&pool).await.unwrap();
+    let mut customer = Customer {
+        id: 1,
+        name: "alice".to_string(),
+        address: address("1 Main St", "Springfield", Some("12345")),
+        billing: address("PO Box 7", "Shelbyville", None),
+        active: true,
+    };
+    customer.insert(&pool).await.unwrap();
+    assert_eq!(Customer::find_by_id(&pool, &1).await.unwrap(), Some(customer.clone()));
+
+    customer.address.city = "Capital City".to_string();
+    customer.billing.zip = Some("99999".to_string());
+    customer.update(&pool).await.unwrap();
+    assert_eq!(Customer::find_all(&pool).await.unwrap(), [customer.clone()]);
+
+    customer.clone().into_insert(&pool).await.unwrap_err();
+    customer.delete(&pool).await.unwrap();
+    assert_eq!(Customer::count(&pool).await.unwrap(), 0);
+}
@@ -0,0 +1,57 @@
+//! 泛型结构体的测试
+use std::marker::PhantomData;
+
+use macros::SqlCRUD;
+
+/// 只用于区分类型的标记参数
+#[derive(Debug, PartialEq)]
+struct Admin;
+
+#[derive(SqlCRUD, Debug, PartialEq)]
+#[table_name = "users"]
+#[sql_crud(from_row)]
+struct User<Role> {
+    #[primary_key]
+    id: i64,
+    name: String,
+    role: PhantomData<Role>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "tags"]
+struct Tag<K> {
+    #[primary_key]
+    #[sql_type = "TEXT"]
+    key: K,
+    label: String,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+#[tokio::test]
+async fn phantom_parameter_round_trips() {
+    let pool = pool().await;
+    User::<Admin>::init_table(&pool).await.unwrap();
+    assert!(!User::<Admin>::CREATE_TABLE_SQL.contains("role"), "{}", User::<Admin>::CREATE_TABLE_SQL);
+
+    let mut user = User::<Admin> { id: 1, name: "alice".into(), role: PhantomData };
+    user.insert(&pool).await.unwrap();
+    user.name = "alicia".into();
+    user.update(&pool).await.unwrap();
+    assert_eq!(User::<Admin>::find_by_id(&pool, &1).await.unwrap(), Some(user));
+}
+
+#[tokio::test]
+async fn generic_key_round_trips() {
+    let pool = pool().await;
+    Tag::<String>::init_table(&pool).await.unwrap();
+
+    let tag = Tag { key: "rust".to_string(), label: "Rust".into() };
+    tag.insert(&pool).await.unwrap();
+    assert_eq!(Tag::<String>::find_by_id(&pool, &"rust".to_string()).await.unwrap(), Some(tag));
+    assert_eq!(Tag::<String>::find_all(&pool).await.unwrap().len(), 1);
+    Tag::<String>::delete_by_id(&pool, &"rust".to_string()).await.unwrap();
+    assert_eq!(Tag::<String>::count(&pool).await.unwrap(), 0);
+}
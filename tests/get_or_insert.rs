@@ -0,0 +1,29 @@
+//! `get_or_insert` 的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    #[unique]
+    email: String,
+    name: String,
+}
+
+#[tokio::test]
+async fn same_unique_value_returns_the_existing_row() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    User::init_table(&pool).await.unwrap();
+
+    let first = User::get_or_insert(&pool, &User { id: 0, email: "alice@example.com".into(), name: "alice".into() }).await.unwrap();
+    let second = User::get_or_insert(&pool, &User { id: 0, email: "alice@example.com".into(), name: "alicia".into() }).await.unwrap();
+    assert_eq!(first.id, second.id);
+    assert_eq!(second.name, "alice");
+    assert_eq!(User::count(&pool).await.unwrap(), 1);
+
+    let other = User::get_or_insert(&pool, &User { id: 0, email: "bob@example.com".into(), name: "bob".into() }).await.unwrap();
+    assert_ne!(other.id, first.id);
+    assert_eq!(User::count(&pool).await.unwrap(), 2);
+}
@@ -0,0 +1,137 @@
+//! `#[has_many(...)]` 一对多关系加载的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+// 指向同一子结构体的多个关系以相同的参数开头，clippy 会误认为是重复的属性
+#[allow(clippy::duplicated_attributes)]
+#[has_many(Post, foreign_key = "user_id")]
+#[has_many(Post, foreign_key = "reviewer_id", name = "reviewed_posts")]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+struct Post {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    #[sql_type = "INTEGER REFERENCES users(id)"]
+    user_id: i64,
+    reviewer_id: Option<i64>,
+    title: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "teams"]
+#[sql_crud(error)]
+#[has_many(Member, foreign_key = "team_id")]
+struct Team {
+    #[primary_key]
+    id: i64,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "members"]
+struct Member {
+    #[primary_key]
+    id: i64,
+    team_id: i64,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+fn post(user_id: i64, reviewer_id: Option<i64>, title: &str) -> Post {
+    Post { id: 0, user_id, reviewer_id, title: title.to_string() }
+}
+
+#[tokio::test]
+async fn children_belong_to_the_right_parent() {
+    let pool = pool().await;
+    User::init_table(&pool).await.unwrap();
+    Post::init_table(&pool).await.unwrap();
+    let alice = User { id: 1, name: "alice".to_string() };
+    let bob = User { id: 2, name: "bob".to_string() };
+    let carol = User { id: 3, name: "carol".to_string() };
+    for user in [&alice, &bob, &carol] {
+        user.insert(&pool).await.unwrap();
+    }
+    post(1, Some(2), "a1").insert(&pool).await.unwrap();
+    post(2, None, "b1").insert(&pool).await.unwrap();
+    post(1, Some(2), "a2").insert(&pool).await.unwrap();
+
+    let titles = |posts: Vec<Post>| posts.into_iter().map(|p| p.title).collect::<Vec<_>>();
+    assert_eq!(titles(alice.posts(&pool).await.unwrap()), ["a1", "a2"]);
+    assert_eq!(titles(bob.posts(&pool).await.unwrap()), ["b1"]);
+    assert!(carol.posts(&pool).await.unwrap().is_empty());
+
+    assert_eq!(titles(bob.reviewed_posts(&pool).await.unwrap()), ["a1", "a2"]);
+    assert!(alice.reviewed_posts(&pool).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn errors_use_the_parent_error_type() {
+    let pool = pool().await;
+    let team = Team { id: 1 };
+    match team.members(&pool).await.unwrap_err() {
+        TeamError::Query { operation, sql, .. } => {
+            assert_eq!(operation, "members");
+            assert_eq!(sql, "SELECT \"id\", \"team_id\" FROM \"members\" WHERE \"team_id\" = $1;");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    Member::init_table(&pool).await.unwrap();
+    Member { id: 1, team_id: 1 }.insert(&pool).await.unwrap();
+    Member { id: 2, team_id: 2 }.insert(&pool).await.unwrap();
+    assert_eq!(team.members(&pool).await.unwrap(), vec![Member { id: 1, team_id: 1 }]);
+}
+
+#[tokio::test]
+async fn find_all_with_groups_children_by_parent() {
+    let pool = pool().await;
+    User::init_table(&pool).await.unwrap();
+    Post::init_table(&pool).await.unwrap();
+    for (id, name) in [(1, "alice"), (2, "bob"), (3, "carol")] {
+        User { id, name: name.to_string() }.insert(&pool).await.unwrap();
+    }
+    post(3, None, "c1").insert(&pool).await.unwrap();
+    post(1, Some(3), "a1").insert(&pool).await.unwrap();
+    post(3, Some(3), "c2").insert(&pool).await.unwrap();
+    post(3, None, "c3").insert(&pool).await.unwrap();
+
+    let loaded = User::find_all_with_posts(&pool).await.unwrap()
+        .into_iter()
+        .map(|(user, posts)| (user.name, posts.into_iter().map(|p| p.title).collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+    assert_eq!(loaded, [
+        ("alice".to_string(), vec!["a1".to_string()]),
+        ("bob".to_string(), vec![]),
+        ("carol".to_string(), vec!["c1".to_string(), "c2".to_string(), "c3".to_string()]),
+    ]);
+
+    let reviewed = User::find_all_with_reviewed_posts(&pool).await.unwrap();
+    let counts = reviewed.iter().map(|(user, posts)| (user.id, posts.len())).collect::<Vec<_>>();
+    assert_eq!(counts, [(1, 0), (2, 0), (3, 2)]);
+}
+
+#[tokio::test]
+async fn find_all_with_reports_the_in_query() {
+    let pool = pool().await;
+    Team::init_table(&pool).await.unwrap();
+    assert!(Team::find_all_with_members(&pool).await.unwrap().is_empty());
+
+    Team { id: 1 }.insert(&pool).await.unwrap();
+    Team { id: 2 }.insert(&pool).await.unwrap();
+    match Team::find_all_with_members(&pool).await.unwrap_err() {
+        TeamError::Query { operation, sql, .. } => {
+            assert_eq!(operation, "find_all_with_members");
+            assert_eq!(sql, "SELECT \"id\", \"team_id\" FROM \"members\" WHERE \"team_id\" IN ($1, $2);");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
@@ -0,0 +1,58 @@
+//! `#[sql_crud(hooks)]` 写操作钩子的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+#[sql_crud(hooks)]
+struct User {
+    #[primary_key]
+    id: i64,
+    email: String,
+}
+
+impl UserHooks for User {
+    fn before_insert(&mut self) -> Result<(), sqlx::Error> {
+        if self.email.is_empty() {
+            return Err(sqlx::Error::Protocol("email is required".into()));
+        }
+        self.email = self.email.to_lowercase();
+        Ok(())
+    }
+
+    fn before_delete(&self) -> Result<(), sqlx::Error> {
+        Err(sqlx::Error::Protocol("users are never deleted".into()))
+    }
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+#[tokio::test]
+async fn before_insert_can_modify_the_record() {
+    let pool = pool().await;
+    User::init_table(&pool).await.unwrap();
+    let mut user = User { id: 1, email: "Alice@Example.COM".into() };
+    user.insert(&pool).await.unwrap();
+    assert_eq!(user.email, "alice@example.com");
+    assert_eq!(User::find_by_id(&pool, &1).await.unwrap().unwrap().email, "alice@example.com");
+}
+
+#[tokio::test]
+async fn before_insert_error_runs_no_sql() {
+    // 没有建表，若执行了SQL会得到 "no such table" 错误
+    let pool = pool().await;
+    let err = User { id: 1, email: String::new() }.insert(&pool).await.unwrap_err();
+    assert!(matches!(&err, sqlx::Error::Protocol(msg) if msg == "email is required"), "{}", err);
+}
+
+#[tokio::test]
+async fn before_delete_error_keeps_the_row() {
+    let pool = pool().await;
+    User::init_table(&pool).await.unwrap();
+    let mut user = User { id: 1, email: "bob@example.com".into() };
+    user.insert(&pool).await.unwrap();
+    let err = user.delete(&pool).await.unwrap_err();
+    assert!(matches!(&err, sqlx::Error::Protocol(msg) if msg == "users are never deleted"), "{}", err);
+    assert_eq!(User::find_by_id(&pool, &1).await.unwrap(), Some(user));
+}
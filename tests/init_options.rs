@@ -0,0 +1,106 @@
+//! `init_table_with` 和 `configure_connection` 设置 SQLite PRAGMA 的测试
+use macros::SqlCRUD;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Connection, SqlitePool};
+use std::str::FromStr;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "parents"]
+struct Parent {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "children"]
+#[sql_crud(init_options, repository)]
+struct Child {
+    #[primary_key]
+    id: i64,
+    #[sql_type = "INTEGER REFERENCES parents(id)"]
+    parent_id: i64,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "tags"]
+#[sql_crud(init_options, error)]
+struct Tag {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+/// sqlx 默认启用外键检查，这里显式关闭以观察 PRAGMA 的效果
+async fn pool_without_foreign_keys() -> SqlitePool {
+    let options = SqliteConnectOptions::from_str("sqlite::memory:").unwrap().foreign_keys(false);
+    SqlitePoolOptions::new().max_connections(1).connect_with(options).await.unwrap()
+}
+
+#[tokio::test]
+async fn foreign_key_violation_is_accepted_without_the_pragma() {
+    let pool = pool_without_foreign_keys().await;
+    Parent::init_table(&pool).await.unwrap();
+    Child::init_table(&pool).await.unwrap();
+
+    Child { id: 1, parent_id: 42 }.insert(&pool).await.unwrap();
+    assert_eq!(Child::count(&pool).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn foreign_key_violation_is_rejected_with_the_pragma() {
+    let pool = pool_without_foreign_keys().await;
+    Parent::init_table(&pool).await.unwrap();
+    Child::init_table_with(&pool, ChildInitOptions { foreign_keys: true, ..Default::default() }).await.unwrap();
+
+    Parent { id: 1, name: "a".to_string() }.insert(&pool).await.unwrap();
+    Child { id: 1, parent_id: 1 }.insert(&pool).await.unwrap();
+    assert!(Child { id: 2, parent_id: 42 }.insert(&pool).await.is_err());
+    assert_eq!(Child::count(&pool).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn repository_forwards_init_table_with() {
+    let pool = pool_without_foreign_keys().await;
+    Parent::init_table(&pool).await.unwrap();
+    let repo = ChildRepo::new(pool.clone());
+    repo.init_table_with(ChildInitOptions { foreign_keys: true, ..Default::default() }).await.unwrap();
+
+    assert!(Child { id: 1, parent_id: 42 }.insert(&pool).await.is_err());
+}
+
+#[tokio::test]
+async fn init_table_with_commits_its_transaction() {
+    let pool = pool_without_foreign_keys().await;
+    Tag::init_table_with(&pool, TagInitOptions { foreign_keys: true, ..Default::default() }).await.unwrap();
+
+    Tag { id: 1, name: "a".to_string() }.insert(&pool).await.unwrap();
+    assert_eq!(Tag::count(&pool).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn configure_connection_sets_pragmas() {
+    let options = SqliteConnectOptions::from_str("sqlite::memory:").unwrap().foreign_keys(false);
+    let mut conn = sqlx::SqliteConnection::connect_with(&options).await.unwrap();
+    let init = ChildInitOptions {
+        foreign_keys: true,
+        journal_mode: Some("memory"),
+        busy_timeout_ms: Some(250),
+    };
+    Child::configure_connection(&mut conn, &init).await.unwrap();
+
+    let foreign_keys: i64 = sqlx::query_scalar("PRAGMA foreign_keys").fetch_one(&mut conn).await.unwrap();
+    let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode").fetch_one(&mut conn).await.unwrap();
+    let busy_timeout: i64 = sqlx::query_scalar("PRAGMA busy_timeout").fetch_one(&mut conn).await.unwrap();
+    assert_eq!(foreign_keys, 1);
+    assert_eq!(journal_mode, "memory");
+    assert_eq!(busy_timeout, 250);
+}
+
+#[tokio::test]
+async fn unknown_journal_mode_is_rejected() {
+    let pool = pool_without_foreign_keys().await;
+    let init = ChildInitOptions { journal_mode: Some("wal; DROP TABLE parents"), ..Default::default() };
+    let err = Child::init_table_with(&pool, init).await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::Configuration(_)));
+}
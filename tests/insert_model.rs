@@ -0,0 +1,58 @@
+//! `#[sql_crud(insert_model)]` 生成 `New<Name>` 的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+#[sql_crud(insert_model)]
+struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    name: String,
+    created_at: Option<String>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "tags"]
+#[sql_crud(error, insert_model(derive(Debug, Clone, PartialEq)))]
+struct Tag {
+    #[primary_key]
+    name: String,
+    #[skip]
+    #[sqlx(skip)]
+    cached: bool,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+#[tokio::test]
+async fn returns_the_row_with_database_values() {
+    let pool = pool().await;
+    User::init_table(&pool).await.unwrap();
+    // 由触发器填充的列只能通过读回的记录得到
+    sqlx::query("CREATE TRIGGER users_created_at AFTER INSERT ON users BEGIN UPDATE users SET created_at = '2024-01-01' WHERE id = NEW.id; END;")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let alice = NewUser { name: "alice".to_string(), created_at: None }.insert(&pool).await.unwrap();
+    let bob = NewUser { name: "bob".to_string(), created_at: None }.clone().insert(&pool).await.unwrap();
+
+    assert_eq!(alice, User { id: 1, name: "alice".to_string(), created_at: Some("2024-01-01".to_string()) });
+    assert_eq!(bob.id, 2);
+    assert_eq!(User::find_all(&pool).await.unwrap(), [alice, bob]);
+}
+
+#[tokio::test]
+async fn configurable_derives_and_skipped_fields() {
+    let pool = pool().await;
+    Tag::init_table(&pool).await.unwrap();
+    let new_tag = NewTag { name: "rust".to_string() };
+    assert_eq!(new_tag.clone(), new_tag);
+
+    let tag = new_tag.insert(&pool).await.unwrap();
+    assert_eq!(tag, Tag { name: "rust".to_string(), cached: false });
+    assert!(matches!(NewTag { name: "rust".to_string() }.insert(&pool).await, Err(TagError::Query { operation: "insert", .. })));
+}
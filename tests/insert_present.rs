@@ -0,0 +1,80 @@
+//! `insert_present` 省略值为 `None` 的字段、由列默认值填充的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "settings"]
+#[sql_crud(insert_present)]
+struct Setting {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    name: String,
+    #[sql_type = "TEXT DEFAULT 'light'"]
+    theme: Option<String>,
+    #[sql_type = "INTEGER DEFAULT 10"]
+    page_size: Option<i64>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "flags"]
+#[sql_crud(insert_present, error)]
+struct Flag {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    #[sql_type = "TEXT DEFAULT 'unnamed'"]
+    name: Option<String>,
+    #[sql_type = "INTEGER DEFAULT 1"]
+    enabled: Option<bool>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "tags"]
+#[sql_crud(insert_present)]
+struct Tag {
+    #[primary_key]
+    label: String,
+    #[sql_type = "TEXT DEFAULT 'gray'"]
+    color: Option<String>,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    Setting::init_table(&pool).await.unwrap();
+    Flag::init_table(&pool).await.unwrap();
+    Tag::init_table(&pool).await.unwrap();
+    pool
+}
+
+#[tokio::test]
+async fn none_fields_take_column_defaults() {
+    let pool = pool().await;
+    let id = Setting { id: 0, name: "a".into(), theme: None, page_size: Some(50) }.insert_present(&pool).await.unwrap();
+    let stored = Setting::find_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(stored, Setting { id, name: "a".into(), theme: Some("light".into()), page_size: Some(50) });
+
+    // 静态的 insert 仍然写入 NULL
+    let id = Setting { id: 0, name: "b".into(), theme: None, page_size: None }.insert(&pool).await.unwrap();
+    assert_eq!(Setting::find_by_id(&pool, &id).await.unwrap().unwrap().theme, None);
+}
+
+#[tokio::test]
+async fn all_none_uses_default_values() {
+    let pool = pool().await;
+    let first = Flag { id: 0, name: None, enabled: None }.insert_present(&pool).await.unwrap();
+    let second = Flag { id: 0, name: Some("beta".into()), enabled: None }.insert_present(&pool).await.unwrap();
+    let flags = Flag::find_all(&pool).await.unwrap();
+    assert_eq!(flags, vec![
+        Flag { id: first, name: Some("unnamed".into()), enabled: Some(true) },
+        Flag { id: second, name: Some("beta".into()), enabled: Some(true) },
+    ]);
+}
+
+#[tokio::test]
+async fn inserts_without_auto_increment_key() {
+    let pool = pool().await;
+    Tag { label: "urgent".into(), color: None }.insert_present(&pool).await.unwrap();
+    Tag { label: "done".into(), color: Some("green".into()) }.insert_present(&pool).await.unwrap();
+    assert_eq!(Tag::find_by_id(&pool, &"urgent".to_string()).await.unwrap().unwrap().color.as_deref(), Some("gray"));
+    assert_eq!(Tag::find_by_id(&pool, &"done".to_string()).await.unwrap().unwrap().color.as_deref(), Some("green"));
+}
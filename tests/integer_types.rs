@@ -0,0 +1,102 @@
+//! 整数类型的列类型映射和往返测试
+
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+struct Counters {
+    #[primary_key]
+    id: i64,
+    tiny: i8,
+    small: i16,
+    byte: u8,
+    word: u16,
+    dword: u32,
+}
+
+#[derive(SqlCRUD)]
+#[sql_crud(backend = "mysql", skip(insert, update, delete, find))]
+#[allow(dead_code)]
+struct MySqlCounters {
+    #[primary_key]
+    id: i64,
+    tiny: i8,
+    small: i16,
+    byte: u8,
+    word: u16,
+    dword: u32,
+    qword: u64,
+    size: usize,
+    signed: isize,
+}
+
+#[derive(SqlCRUD)]
+#[sql_crud(backend = "postgres", skip(insert, update, delete, find))]
+#[allow(dead_code)]
+struct PgCounters {
+    #[primary_key]
+    id: i64,
+    tiny: i8,
+    small: i16,
+    dword: u32,
+    qword: u64,
+}
+
+#[derive(SqlCRUD)]
+#[sql_crud(backend = "postgres", skip(update, delete, find))]
+#[allow(dead_code)]
+struct PgSmallKey {
+    #[primary_key]
+    #[auto_increment]
+    id: i16,
+    name: String,
+}
+
+#[derive(SqlCRUD)]
+#[sql_crud(backend = "postgres", skip(update, delete, find))]
+#[allow(dead_code)]
+struct PgIntKey {
+    #[primary_key]
+    #[auto_increment]
+    id: i32,
+    name: String,
+}
+
+#[test]
+fn integer_ddl() {
+    let sqlite = Counters::CREATE_TABLE_SQL;
+    for column in ["\"tiny\" TINYINT", "\"small\" SMALLINT", "\"byte\" SMALLINT", "\"word\" INT", "\"dword\" BIGINT"] {
+        assert!(sqlite.contains(column), "{} not in {}", column, sqlite);
+    }
+    let mysql = MySqlCounters::CREATE_TABLE_SQL;
+    for column in ["`tiny` TINYINT,", "`small` SMALLINT,", "`byte` TINYINT UNSIGNED", "`word` SMALLINT UNSIGNED", "`dword` INT UNSIGNED", "`qword` BIGINT UNSIGNED", "`size` BIGINT UNSIGNED", "`signed` BIGINT"] {
+        assert!(mysql.contains(column), "{} not in {}", column, mysql);
+    }
+    let postgres = PgCounters::CREATE_TABLE_SQL;
+    for column in ["\"tiny\" SMALLINT", "\"small\" SMALLINT", "\"dword\" BIGINT", "\"qword\" NUMERIC(20)"] {
+        assert!(postgres.contains(column), "{} not in {}", column, postgres);
+    }
+    assert!(PgSmallKey::CREATE_TABLE_SQL.contains("\"id\" SMALLSERIAL PRIMARY KEY"), "{}", PgSmallKey::CREATE_TABLE_SQL);
+    assert!(PgIntKey::CREATE_TABLE_SQL.contains("\"id\" SERIAL PRIMARY KEY"), "{}", PgIntKey::CREATE_TABLE_SQL);
+}
+
+#[tokio::test]
+async fn integer_round_trip() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    Counters::init_table(&pool).await.unwrap();
+
+    let counters = Counters {
+        id: 1,
+        tiny: i8::MIN,
+        small: i16::MIN,
+        byte: u8::MAX,
+        word: u16::MAX,
+        dword: u32::MAX,
+    };
+    counters.insert(&pool).await.unwrap();
+    let found = Counters::find_by_id(&pool, &1).await.unwrap();
+    assert_eq!(found, Some(counters));
+}
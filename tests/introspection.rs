@@ -0,0 +1,33 @@
+//! `columns` 和 `primary_key_columns` 的元数据测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, Default)]
+#[sql_crud(from_row)]
+#[allow(dead_code)]
+struct Member {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    #[column = "display_name"]
+    name: String,
+    #[sql_type = "TEXT"]
+    bio: Option<String>,
+    #[skip]
+    cached: u32,
+    active: bool,
+}
+
+#[test]
+fn columns_follow_field_order() {
+    assert_eq!(Member::columns(), [
+        ("id", "BIGINT"),
+        ("display_name", "VARCHAR(255)"),
+        ("bio", "TEXT"),
+        ("active", "INTEGER"),
+    ]);
+}
+
+#[test]
+fn primary_key_columns() {
+    assert_eq!(Member::primary_key_columns(), ["id"]);
+}
@@ -0,0 +1,62 @@
+//! `#[ip_as_text]` 把 IP 地址存为文本的往返测试
+use std::net::{IpAddr, Ipv4Addr};
+
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, Debug, Clone, PartialEq)]
+#[sql_crud(from_row)]
+struct AuditEntry {
+    #[primary_key]
+    id: i64,
+    #[ip_as_text]
+    #[queryable]
+    client: IpAddr,
+    #[ip_as_text]
+    proxy: Option<Ipv4Addr>,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    AuditEntry::init_table(&pool).await.unwrap();
+    pool
+}
+
+#[test]
+fn ip_columns_are_varchar() {
+    assert!(AuditEntry::CREATE_TABLE_SQL.contains("\"client\" VARCHAR(45),"));
+    assert!(AuditEntry::CREATE_TABLE_SQL.contains("\"proxy\" VARCHAR(45)"));
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let pool = pool().await;
+    let entries = [
+        AuditEntry { id: 1, client: "203.0.113.7".parse().unwrap(), proxy: Some(Ipv4Addr::new(10, 0, 0, 1)) },
+        AuditEntry { id: 2, client: "2001:db8::ff00:42:8329".parse().unwrap(), proxy: None },
+    ];
+    for entry in &entries {
+        entry.insert(&pool).await.unwrap();
+    }
+    let stored: Option<String> = sqlx::query_scalar(&format!("SELECT client FROM {} WHERE id = 2", AuditEntry::table_name()))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored.as_deref(), Some("2001:db8::ff00:42:8329"));
+    assert_eq!(AuditEntry::find_by_client(&pool, &entries[1].client).await.unwrap(), [entries[1].clone()]);
+    for entry in entries {
+        assert_eq!(AuditEntry::find_by_id(&pool, &entry.id).await.unwrap(), Some(entry));
+    }
+}
+
+#[tokio::test]
+async fn malformed_text_fails_to_decode() {
+    let pool = pool().await;
+    let sql = format!("INSERT INTO {} (id, client, proxy) VALUES (1, '127.0.0.1', '::1')", AuditEntry::table_name());
+    sqlx::query(&sql).execute(&pool).await.unwrap();
+    let err = AuditEntry::find_by_id(&pool, &1).await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::ColumnDecode { ref index, .. } if index == "proxy"), "{err:?}");
+}
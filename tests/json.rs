@@ -0,0 +1,54 @@
+//! JSON 列的往返和按路径查询测试
+
+use macros::SqlCRUD;
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+struct Settings {
+    theme: String,
+    limits: Vec<u32>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+struct Document {
+    #[primary_key]
+    id: i64,
+    #[json_indexed]
+    metadata: serde_json::Value,
+    settings: Json<Settings>,
+    extra: Option<serde_json::Value>,
+}
+
+#[tokio::test]
+async fn json_round_trip_and_path_query() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    for column in ["\"metadata\" TEXT", "\"settings\" TEXT", "\"extra\" TEXT"] {
+        assert!(Document::CREATE_TABLE_SQL.contains(column));
+    }
+    Document::init_table(&pool).await.unwrap();
+
+    let first = Document {
+        id: 1,
+        metadata: serde_json::json!({ "author": { "name": "ann", "age": 31 }, "tags": ["a", "b"] }),
+        settings: Json(Settings { theme: "dark".into(), limits: vec![1, 2] }),
+        extra: None,
+    };
+    let second = Document {
+        id: 2,
+        metadata: serde_json::json!({ "author": { "name": "bob", "age": 40 } }),
+        settings: Json(Settings { theme: "light".into(), limits: Vec::new() }),
+        extra: Some(serde_json::json!([1, null])),
+    };
+    first.insert(&pool).await.unwrap();
+    second.insert(&pool).await.unwrap();
+
+    assert_eq!(Document::find_by_id(&pool, &2).await.unwrap().as_ref(), Some(&second));
+    assert_eq!(Document::find_by_metadata_json_path(&pool, "$.author.name", "ann").await.unwrap(), vec![first]);
+    assert_eq!(Document::find_by_metadata_json_path(&pool, "$.author.age", "40").await.unwrap(), vec![second]);
+    assert!(Document::find_by_metadata_json_path(&pool, "$.author.name", "eve").await.unwrap().is_empty());
+}
@@ -0,0 +1,47 @@
+//! `migration_from` 和 `add_column_<field>_sql` 的测试
+use macros::SqlCRUD;
+
+/// 旧版本的表结构
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+struct UserV1 {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+/// 新增了两列的表结构
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+struct UserV2 {
+    #[primary_key]
+    id: i64,
+    name: String,
+    email: Option<String>,
+    age: Option<i32>,
+}
+
+#[test]
+fn only_missing_columns_are_added() {
+    assert_eq!(UserV2::migration_from(UserV1::columns()), [
+        "ALTER TABLE \"users\" ADD COLUMN \"email\" VARCHAR(255);",
+        "ALTER TABLE \"users\" ADD COLUMN \"age\" INT;",
+    ]);
+    assert!(UserV2::migration_from(UserV2::columns()).is_empty());
+    assert_eq!(UserV2::add_column_age_sql(), "ALTER TABLE \"users\" ADD COLUMN \"age\" INT;");
+}
+
+#[tokio::test]
+async fn migrated_table_accepts_the_new_struct() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    UserV1::init_table(&pool).await.unwrap();
+    UserV1 { id: 1, name: "alice".into() }.insert(&pool).await.unwrap();
+
+    for sql in UserV2::migration_from(UserV1::columns()) {
+        sqlx::query(&sql).execute(&pool).await.unwrap();
+    }
+    assert_eq!(UserV2::find_by_id(&pool, &1).await.unwrap(), Some(UserV2 { id: 1, name: "alice".into(), email: None, age: None }));
+    let bob = UserV2 { id: 2, name: "bob".into(), email: Some("bob@example.com".into()), age: Some(30) };
+    bob.insert(&pool).await.unwrap();
+    assert_eq!(UserV2::find_by_id(&pool, &2).await.unwrap(), Some(bob));
+}
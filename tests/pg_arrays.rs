@@ -0,0 +1,34 @@
+//! Postgres 数组列的类型映射测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_name = "articles"]
+#[sql_crud(backend = "postgres")]
+struct Article {
+    #[primary_key]
+    id: i64,
+    #[queryable]
+    scores: Vec<i32>,
+    tags: Vec<String>,
+    ratings: Option<Vec<f64>>,
+    flags: Vec<Option<bool>>,
+    attachments: Vec<Vec<u8>>,
+    body: Vec<u8>,
+}
+
+#[test]
+fn vec_fields_map_to_array_columns() {
+    let columns = Article::columns();
+    assert_eq!(columns, [
+        ("id", "BIGINT"),
+        ("scores", "INT[]"),
+        ("tags", "VARCHAR(255)[]"),
+        ("ratings", "DOUBLE PRECISION[]"),
+        ("flags", "BOOLEAN[]"),
+        ("attachments", "BYTEA[]"),
+        ("body", "BYTEA"),
+    ]);
+    assert!(Article::CREATE_TABLE_SQL.contains("\"tags\" VARCHAR(255)[],"), "{}", Article::CREATE_TABLE_SQL);
+    assert!(Article::CREATE_TABLE_SQL.contains("\"ratings\" DOUBLE PRECISION[],"), "{}", Article::CREATE_TABLE_SQL);
+}
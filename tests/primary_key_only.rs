@@ -0,0 +1,29 @@
+//! 只有主键的结构体不生成 update，其余方法照常可用
+
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[sql_crud(repository)]
+struct Tag {
+    #[primary_key]
+    name: String,
+}
+
+#[tokio::test]
+async fn primary_key_only_struct() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    Tag::init_table(&pool).await.unwrap();
+
+    let tag = Tag { name: "rust".into() };
+    tag.insert(&pool).await.unwrap();
+    assert_eq!(Tag::find_by_id(&pool, &"rust".to_string()).await.unwrap(), Some(tag));
+
+    let repo = TagRepo::new(pool.clone());
+    assert_eq!(repo.find_all().await.unwrap().len(), 1);
+    Tag::delete_by_id(&pool, &"rust".to_string()).await.unwrap();
+    assert_eq!(Tag::count(&pool).await.unwrap(), 0);
+}
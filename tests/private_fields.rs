@@ -0,0 +1,72 @@
+//! 私有字段、跳过的字段和重命名的列在其他模块中调用生成方法的测试
+mod accounts {
+    use macros::SqlCRUD;
+
+    #[derive(SqlCRUD, Debug, PartialEq)]
+    #[table_name = "accounts"]
+    #[sql_crud(from_row, builder, filter, insert_model)]
+    #[projection(AccountName: id, name)]
+    pub struct Account {
+        #[primary_key]
+        #[auto_increment]
+        id: i64,
+        #[column = "display_name"]
+        #[queryable]
+        name: String,
+        email: Option<String>,
+        #[skip]
+        cached_label: Option<String>,
+    }
+
+    impl Account {
+        pub fn new(name: &str, email: Option<&str>) -> Self {
+            Account { id: 0, name: name.into(), email: email.map(Into::into), cached_label: None }
+        }
+
+        pub fn id(&self) -> i64 {
+            self.id
+        }
+
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        pub fn rename(&mut self, name: &str) {
+            self.name = name.into();
+        }
+    }
+}
+
+use accounts::Account;
+
+async fn pool() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    Account::init_table(&pool).await.unwrap();
+    pool
+}
+
+#[tokio::test]
+async fn generated_methods_work_outside_the_module() {
+    let pool = pool().await;
+    let id = Account::new("ann", Some("ann@example.com")).insert(&pool).await.unwrap();
+    let mut account = Account::find_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!((account.id(), account.name()), (id, "ann"));
+
+    account.rename("anne");
+    account.update(&pool).await.unwrap();
+    assert_eq!(Account::find_by_name(&pool, &"anne".to_string()).await.unwrap().len(), 1);
+    assert_eq!(Account::filter().name_eq("anne").count(&pool).await.unwrap(), 1);
+
+    account.delete(&pool).await.unwrap();
+    assert_eq!(Account::count(&pool).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn generated_types_expose_their_fields() {
+    let pool = pool().await;
+    let bob = accounts::NewAccount { name: "bob".into(), email: None }.insert(&pool).await.unwrap();
+    let id = Account::builder().name("cy".to_string()).insert(&pool).await.unwrap().id();
+
+    let names = accounts::AccountName::find_all(&pool).await.unwrap();
+    assert_eq!(names.iter().map(|account| (account.id, account.name.as_str())).collect::<Vec<_>>(), vec![(bob.id(), "bob"), (id, "cy")]);
+}
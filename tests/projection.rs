@@ -0,0 +1,62 @@
+//! `#[projection(...)]` 投影结构体的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+#[projection(UserSummary: id, name)]
+#[projection(UserContact: email)]
+struct User {
+    #[primary_key]
+    id: i32,
+    name: String,
+    #[column = "email_address"]
+    #[sqlx(rename = "email_address")]
+    email: Option<String>,
+    bio: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "missing"]
+#[sql_crud(error)]
+#[projection(MissingIds: id)]
+#[allow(dead_code)]
+struct Missing {
+    #[primary_key]
+    id: i64,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+#[test]
+fn selects_only_the_projected_columns() {
+    assert_eq!(UserSummary::SELECT_SQL, "SELECT \"id\", \"name\" FROM \"users\";");
+    assert_eq!(UserSummary::SELECT_BY_ID_SQL, "SELECT \"id\", \"name\" FROM \"users\" WHERE \"id\" = $1;");
+    assert_eq!(UserContact::SELECT_SQL, "SELECT \"email_address\" FROM \"users\";");
+}
+
+#[tokio::test]
+async fn maps_rows_to_the_projection() {
+    let pool = pool().await;
+    User::init_table(&pool).await.unwrap();
+    User { id: 1, name: "alice".to_string(), email: Some("a@example.com".to_string()), bio: "long".to_string() }.insert(&pool).await.unwrap();
+    User { id: 2, name: "bob".to_string(), email: None, bio: "longer".to_string() }.insert(&pool).await.unwrap();
+
+    let summaries = UserSummary::find_all(&pool).await.unwrap();
+    let summaries = summaries.into_iter().map(|s| (s.id, s.name)).collect::<Vec<_>>();
+    assert_eq!(summaries, [(1, "alice".to_string()), (2, "bob".to_string())]);
+
+    let bob = UserSummary::find_by_id(&pool, &2).await.unwrap().unwrap();
+    assert_eq!(bob.name, "bob");
+    assert!(UserSummary::find_by_id(&pool, &3).await.unwrap().is_none());
+
+    let contacts = UserContact::find_all(&pool).await.unwrap();
+    assert_eq!(contacts.into_iter().map(|c| c.email).collect::<Vec<_>>(), [Some("a@example.com".to_string()), None]);
+}
+
+#[tokio::test]
+async fn errors_use_the_parent_error_type() {
+    let pool = pool().await;
+    assert!(matches!(MissingIds::find_all(&pool).await, Err(MissingError::Query { operation: "find_all", .. })));
+}
@@ -0,0 +1,93 @@
+//! 完整路径、宏展开的字段类型推导列类型的测试
+extern crate alloc;
+
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD)]
+#[sql_crud(skip(insert, update, delete, find))]
+#[table_name = "t"]
+#[allow(dead_code)]
+struct Qualified {
+    #[primary_key]
+    id: ::core::primitive::i64,
+    std_string: std::string::String,
+    alloc_string: alloc::string::String,
+    rooted_string: ::std::string::String,
+    std_option: std::option::Option<i32>,
+    core_option: core::option::Option<std::string::String>,
+    bytes: std::vec::Vec<core::primitive::u8>,
+    boxed_bytes: std::boxed::Box<[::core::primitive::u8]>,
+    cow: std::borrow::Cow<'static, core::primitive::str>,
+    json: sqlx::types::Json<std::vec::Vec<i32>>,
+    value: ::serde_json::Value,
+    flag: core::primitive::bool,
+}
+
+macro_rules! with_field_type {
+    ($name:ident, $ty:ty) => {
+        #[derive(SqlCRUD)]
+        #[sql_crud(skip(insert, update, delete, find))]
+        #[table_name = "m"]
+        #[allow(dead_code)]
+        struct $name {
+            #[primary_key]
+            id: i64,
+            value: $ty,
+            optional: Option<$ty>,
+        }
+    };
+}
+
+with_field_type!(FromMacro, std::string::String);
+with_field_type!(FromMacroBytes, Vec<u8>);
+
+#[derive(SqlCRUD)]
+#[sql_crud(skip(insert, update, delete, find))]
+#[table_name = "a"]
+#[type_map(Name = "VARCHAR(64)")]
+#[allow(dead_code)]
+struct Aliased {
+    #[primary_key]
+    id: i64,
+    name: Name,
+}
+
+type Name = String;
+
+fn column(sql: &str, name: &str) -> String {
+    let prefix = format!("\"{}\" ", name);
+    sql.lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix(&prefix))
+        .unwrap_or_else(|| panic!("column {} not in {}", name, sql))
+        .trim_end_matches(',')
+        .to_string()
+}
+
+#[test]
+fn fully_qualified_paths_map_like_short_names() {
+    let sql = Qualified::CREATE_TABLE_SQL;
+    assert_eq!(column(sql, "id"), "BIGINT PRIMARY KEY");
+    for name in ["std_string", "alloc_string", "rooted_string", "core_option", "cow"] {
+        assert_eq!(column(sql, name), "VARCHAR(255)", "{}", name);
+    }
+    assert_eq!(column(sql, "std_option"), "INT");
+    assert_eq!(column(sql, "bytes"), "BLOB");
+    assert_eq!(column(sql, "boxed_bytes"), "BLOB");
+    assert_eq!(column(sql, "json"), "TEXT");
+    assert_eq!(column(sql, "value"), "TEXT");
+    assert_eq!(column(sql, "flag"), "INTEGER");
+}
+
+#[test]
+fn types_passed_through_macro_rules() {
+    assert_eq!(column(FromMacro::CREATE_TABLE_SQL, "value"), "VARCHAR(255)");
+    assert_eq!(column(FromMacro::CREATE_TABLE_SQL, "optional"), "VARCHAR(255)");
+    assert_eq!(column(FromMacroBytes::CREATE_TABLE_SQL, "value"), "BLOB");
+    assert_eq!(column(FromMacroBytes::CREATE_TABLE_SQL, "optional"), "BLOB");
+}
+
+#[test]
+fn type_aliases_go_through_type_map() {
+    assert_eq!(column(Aliased::CREATE_TABLE_SQL, "name"), "VARCHAR(64)");
+}
@@ -0,0 +1,32 @@
+//! 原始标识符字段（如 `r#type`）的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "events"]
+struct Event {
+    #[primary_key]
+    id: i64,
+    #[queryable]
+    r#type: String,
+    r#match: i64,
+}
+
+#[test]
+fn column_names_drop_the_raw_prefix() {
+    assert!(Event::CREATE_TABLE_SQL.contains("\"type\" VARCHAR(255)"), "{}", Event::CREATE_TABLE_SQL);
+    assert!(Event::CREATE_TABLE_SQL.contains("\"match\" BIGINT"), "{}", Event::CREATE_TABLE_SQL);
+}
+
+#[tokio::test]
+async fn raw_identifier_fields_round_trip() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    Event::init_table(&pool).await.unwrap();
+
+    let mut event = Event { id: 1, r#type: "goal".into(), r#match: 7 };
+    event.insert(&pool).await.unwrap();
+    assert_eq!(Event::find_by_type(&pool, &"goal".to_string()).await.unwrap(), vec![Event { id: 1, r#type: "goal".into(), r#match: 7 }]);
+
+    event.r#match = 8;
+    event.update(&pool).await.unwrap();
+    assert_eq!(Event::find_by_id(&pool, &1).await.unwrap(), Some(event));
+}
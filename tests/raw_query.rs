@@ -0,0 +1,26 @@
+//! `#[sql_crud(raw_query)]` 生成的 `find_by_sql` 的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+#[sql_crud(raw_query)]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+    age: i64,
+}
+
+#[tokio::test]
+async fn appends_the_where_clause() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    User::init_table(&pool).await.unwrap();
+    for (id, name, age) in [(1, "alice", 30), (2, "bob", 17), (3, "carol", 42)] {
+        User { id, name: name.into(), age }.insert(&pool).await.unwrap();
+    }
+
+    let adults = User::find_by_sql(&pool, "age >= 18 ORDER BY age DESC").await.unwrap();
+    assert_eq!(adults.iter().map(|user| user.id).collect::<Vec<_>>(), vec![3, 1]);
+    assert!(User::find_by_sql(&pool, "name = 'dave'").await.unwrap().is_empty());
+    assert!(User::find_by_sql(&pool, "no_such_column = 1").await.is_err());
+}
@@ -0,0 +1,42 @@
+//! `#[read_table]` 和 `#[write_table]` 分别用于查询和写入的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_prefix = ""]
+#[read_table = "active_users"]
+#[write_table = "users"]
+struct User {
+    #[primary_key]
+    id: i64,
+    #[queryable]
+    name: String,
+    active: bool,
+}
+
+#[test]
+fn statements_pick_their_table() {
+    assert_eq!(User::table_name(), "user");
+    assert!(User::CREATE_TABLE_SQL.contains("\"users\""), "{}", User::CREATE_TABLE_SQL);
+    assert!(User::INSERT_SQL.contains("\"users\""), "{}", User::INSERT_SQL);
+    assert!(User::DELETE_SQL.contains("\"users\""), "{}", User::DELETE_SQL);
+    assert!(User::SELECT_SQL.contains("\"active_users\""), "{}", User::SELECT_SQL);
+    assert!(User::COUNT_SQL.contains("\"active_users\""), "{}", User::COUNT_SQL);
+}
+
+#[tokio::test]
+async fn reads_go_through_the_view() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    User::init_table(&pool).await.unwrap();
+    sqlx::query("CREATE VIEW active_users AS SELECT * FROM users WHERE active").execute(&pool).await.unwrap();
+
+    let mut alice = User { id: 1, name: "alice".into(), active: true };
+    alice.insert(&pool).await.unwrap();
+    User { id: 2, name: "bob".into(), active: false }.insert(&pool).await.unwrap();
+    assert_eq!(User::find_all(&pool).await.unwrap(), vec![User { id: 1, name: "alice".into(), active: true }]);
+    assert_eq!(User::find_by_id(&pool, &2).await.unwrap(), None);
+    assert_eq!(User::count(&pool).await.unwrap(), 1);
+
+    alice.active = false;
+    alice.update(&pool).await.unwrap();
+    assert!(User::find_by_name(&pool, &"alice".to_string()).await.unwrap().is_empty());
+}
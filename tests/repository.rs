@@ -0,0 +1,43 @@
+//! `#[sql_crud(repository)]` 生成的 `<Name>Repo` 的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, Clone, PartialEq)]
+#[table_name = "users"]
+#[sql_crud(repository)]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+fn assert_clone<T: Clone>() {}
+
+#[test]
+fn repo_is_clone() {
+    assert_clone::<UserRepo>();
+}
+
+#[tokio::test]
+async fn repo_forwards_crud() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    let repo = UserRepo::new(pool);
+    repo.init_table().await.unwrap();
+
+    let mut alice = User { id: 1, name: "alice".into() };
+    let bob = User { id: 2, name: "bob".into() };
+    repo.insert(&alice).await.unwrap();
+    repo.insert(&bob).await.unwrap();
+    assert_eq!(repo.find_by_id(&1).await.unwrap(), Some(alice.clone()));
+    assert_eq!(repo.find_all().await.unwrap(), vec![alice.clone(), bob.clone()]);
+
+    alice.name = "alicia".into();
+    repo.update(&alice).await.unwrap();
+    // 克隆的仓储共享同一个连接池
+    let cloned = repo.clone();
+    assert_eq!(cloned.find_by_id(&1).await.unwrap(), Some(alice.clone()));
+
+    repo.delete(&alice).await.unwrap();
+    repo.delete_by_id(&2).await.unwrap();
+    assert!(repo.find_all().await.unwrap().is_empty());
+    assert!(sqlx::query("SELECT 1").fetch_one(repo.pool()).await.is_ok());
+}
@@ -0,0 +1,105 @@
+//! `#[sql_crud(runtime_prefix)]` 在运行时为表名加前缀的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq, Clone)]
+#[table_name = "users"]
+#[sql_crud(runtime_prefix)]
+struct User {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    name: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "accounts"]
+#[sql_crud(runtime_prefix, error)]
+struct Account {
+    #[primary_key]
+    id: i64,
+    owner: String,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+#[test]
+fn table_name_with_prefix() {
+    assert_eq!(User::table_name_with_prefix("tenant1_"), "tenant1_users");
+    assert_eq!(User::table_name(), "users");
+}
+
+#[tokio::test]
+async fn tenants_are_isolated() {
+    let pool = pool().await;
+    User::init_table_with_prefix(&pool, "tenant1_").await.unwrap();
+    User::init_table_with_prefix(&pool, "tenant2_").await.unwrap();
+
+    let mut alice = User { id: 0, name: "alice".to_string() };
+    alice.id = alice.insert_with_prefix(&pool, "tenant1_").await.unwrap();
+    User { id: 0, name: "bob".to_string() }.insert_with_prefix(&pool, "tenant2_").await.unwrap();
+    User { id: 0, name: "carol".to_string() }.insert_with_prefix(&pool, "tenant2_").await.unwrap();
+
+    assert_eq!(User::count_with_prefix(&pool, "tenant1_").await.unwrap(), 1);
+    assert_eq!(User::count_with_prefix(&pool, "tenant2_").await.unwrap(), 2);
+    assert_eq!(User::find_all_with_prefix(&pool, "tenant1_").await.unwrap(), vec![alice.clone()]);
+
+    let raw: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tenant2_users").fetch_one(&pool).await.unwrap();
+    assert_eq!(raw, 2);
+}
+
+#[tokio::test]
+async fn update_find_and_delete_with_prefix() {
+    let pool = pool().await;
+    User::init_table_with_prefix(&pool, "t_").await.unwrap();
+    let mut user = User { id: 0, name: "before".to_string() };
+    user.id = user.insert_with_prefix(&pool, "t_").await.unwrap();
+
+    user.name = "after".to_string();
+    user.update_with_prefix(&pool, "t_").await.unwrap();
+    assert_eq!(User::find_by_id_with_prefix(&pool, "t_", &user.id).await.unwrap(), Some(user.clone()));
+
+    user.delete_with_prefix(&pool, "t_").await.unwrap();
+    assert_eq!(User::find_by_id_with_prefix(&pool, "t_", &user.id).await.unwrap(), None);
+
+    let id = User { id: 0, name: "again".to_string() }.insert_with_prefix(&pool, "t_").await.unwrap();
+    User::delete_by_id_with_prefix(&pool, "t_", &id).await.unwrap();
+    assert_eq!(User::count_with_prefix(&pool, "t_").await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn unprefixed_methods_still_use_the_plain_table() {
+    let pool = pool().await;
+    User::init_table(&pool).await.unwrap();
+    User::init_table_with_prefix(&pool, "x_").await.unwrap();
+    User { id: 0, name: "plain".to_string() }.insert(&pool).await.unwrap();
+
+    assert_eq!(User::count(&pool).await.unwrap(), 1);
+    assert_eq!(User::count_with_prefix(&pool, "x_").await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn quotes_in_prefix_are_rejected() {
+    let pool = pool().await;
+    let err = User::count_with_prefix(&pool, "evil\"; DROP TABLE users; --").await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::Configuration(_)));
+
+    match Account::count_with_prefix(&pool, "a\"b").await.unwrap_err() {
+        AccountError::Query { operation, source, .. } => {
+            assert_eq!(operation, "count");
+            assert!(matches!(source, sqlx::Error::Configuration(_)));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn error_type_works_with_prefix() {
+    let pool = pool().await;
+    Account::init_table_with_prefix(&pool, "acme_").await.unwrap();
+    Account { id: 1, owner: "a".to_string() }.insert_with_prefix(&pool, "acme_").await.unwrap();
+
+    let err = Account { id: 2, owner: "b".to_string() }.update_with_prefix(&pool, "acme_").await.unwrap_err();
+    assert!(matches!(err, AccountError::NotFound { operation: "update", .. }));
+}
@@ -0,0 +1,91 @@
+//! `#[schema = "..."]` 带模式名的表引用测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_name = "events"]
+#[schema = "analytics"]
+#[comment = "tracked events"]
+#[sql_crud(backend = "postgres", create_schema)]
+struct PgEvent {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    #[queryable]
+    #[comment = "event kind"]
+    kind: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_name = "events"]
+#[schema = "analytics"]
+#[sql_crud(backend = "mysql")]
+struct MySqlEvent {
+    #[primary_key]
+    id: i64,
+    kind: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "events"]
+#[schema = "aux"]
+struct AttachedEvent {
+    #[primary_key]
+    id: i64,
+    kind: String,
+}
+
+#[test]
+fn table_names() {
+    assert_eq!(PgEvent::table_name(), "events");
+    assert_eq!(PgEvent::qualified_table_name(), "analytics.events");
+}
+
+#[test]
+fn every_statement_uses_the_qualified_table() {
+    let qualified = "\"analytics\".\"events\"";
+    for sql in [
+        PgEvent::CREATE_TABLE_SQL,
+        PgEvent::INSERT_SQL,
+        PgEvent::UPDATE_SQL,
+        PgEvent::DELETE_SQL,
+        PgEvent::SELECT_SQL,
+        PgEvent::SELECT_BY_ID_SQL,
+        PgEvent::COUNT_SQL,
+    ] {
+        assert!(sql.contains(qualified), "{}", sql);
+    }
+    assert_eq!(PgEvent::COMMENT_SQLS, [
+        "COMMENT ON TABLE \"analytics\".\"events\" IS 'tracked events';",
+        "COMMENT ON COLUMN \"analytics\".\"events\".\"kind\" IS 'event kind';",
+    ]);
+    assert!(PgEvent::add_column_kind_sql().contains("ALTER TABLE \"analytics\".\"events\""));
+}
+
+#[test]
+fn create_schema_sql() {
+    assert_eq!(PgEvent::CREATE_SCHEMA_SQL, "CREATE SCHEMA IF NOT EXISTS \"analytics\";");
+}
+
+#[test]
+fn mysql_quotes_schema_with_backticks() {
+    assert!(MySqlEvent::SELECT_SQL.contains("FROM `analytics`.`events`"), "{}", MySqlEvent::SELECT_SQL);
+}
+
+#[tokio::test]
+async fn sqlite_attached_database() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    sqlx::query("ATTACH DATABASE ':memory:' AS aux").execute(&pool).await.unwrap();
+    AttachedEvent::init_table(&pool).await.unwrap();
+
+    let event = AttachedEvent { id: 1, kind: "click".to_string() };
+    event.insert(&pool).await.unwrap();
+    assert_eq!(AttachedEvent::find_all(&pool).await.unwrap(), vec![event]);
+
+    let main_tables: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM main.sqlite_master WHERE name = 'events'")
+        .fetch_one(&pool).await.unwrap();
+    let aux_tables: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM aux.sqlite_master WHERE name = 'events'")
+        .fetch_one(&pool).await.unwrap();
+    assert_eq!((main_tables, aux_tables), (0, 1));
+}
@@ -0,0 +1,47 @@
+//! `#[searchable]` 生成的 `search_<field>` 的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "books"]
+struct Book {
+    #[primary_key]
+    id: i64,
+    #[searchable]
+    title: String,
+    #[searchable]
+    #[column = "subtitle_text"]
+    #[sqlx(rename = "subtitle_text")]
+    subtitle: Option<String>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[table_name = "books"]
+#[sql_crud(backend = "postgres", skip(insert, update, delete))]
+#[allow(dead_code)]
+struct PgBook {
+    #[primary_key]
+    id: i64,
+    #[searchable]
+    title: String,
+}
+
+#[tokio::test]
+async fn searches_with_a_bound_pattern() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    Book::init_table(&pool).await.unwrap();
+    for (id, title, subtitle) in [(1, "Rust in Action", None), (2, "Programming Rust", Some("Fast, Safe Systems")), (3, "The Go Book", None)] {
+        Book { id, title: title.into(), subtitle: subtitle.map(Into::into) }.insert(&pool).await.unwrap();
+    }
+
+    let books = Book::search_title(&pool, "%rust%").await.unwrap();
+    assert_eq!(books.iter().map(|book| book.id).collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(Book::search_subtitle(&pool, "%safe%").await.unwrap().len(), 1);
+    // 模式作为参数绑定，不会被当作SQL解释
+    assert!(Book::search_title(&pool, "%' OR '1' = '1").await.unwrap().is_empty());
+}
+
+#[test]
+fn generated_for_postgres() {
+    // Postgres 上的语句使用 ILIKE，这里只检查方法能够生成
+    let _ = PgBook::search_title;
+}
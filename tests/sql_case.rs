@@ -0,0 +1,43 @@
+//! `#[sql_case = "..."]` 关键字大小写的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "UserAccounts"]
+#[sql_case = "lower"]
+struct User {
+    #[primary_key]
+    id: i64,
+    #[sql_enum(text, values("Active", "Banned"))]
+    status: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+#[sql_case = "upper"]
+struct UpperUser {
+    #[primary_key]
+    id: i64,
+}
+
+#[test]
+fn lower_case_keeps_quoted_identifiers_and_literals() {
+    assert_eq!(User::SELECT_SQL, "select \"id\", \"status\" from \"UserAccounts\";");
+    assert_eq!(User::INSERT_SQL, "insert into \"UserAccounts\" (\"id\", \"status\") values ($1, $2);");
+    assert!(User::CREATE_TABLE_SQL.starts_with("create table if not exists \"UserAccounts\" ("), "{}", User::CREATE_TABLE_SQL);
+    assert!(User::CREATE_TABLE_SQL.contains("\"status\" text check (\"status\" in ('Active', 'Banned'))"), "{}", User::CREATE_TABLE_SQL);
+}
+
+#[test]
+fn upper_case_is_the_default() {
+    assert_eq!(UpperUser::SELECT_SQL, "SELECT \"id\" FROM \"users\";");
+}
+
+#[tokio::test]
+async fn lower_case_sql_runs() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    User::init_table(&pool).await.unwrap();
+    let user = User { id: 1, status: "Active".into() };
+    user.insert(&pool).await.unwrap();
+    assert_eq!(User::find_by_id(&pool, &1).await.unwrap(), Some(user));
+    assert!(User { id: 2, status: "active".into() }.insert(&pool).await.is_err());
+}
@@ -0,0 +1,54 @@
+//! `#[sql_enum(...)]` 枚举列的往返和 CHECK 约束测试
+
+use macros::SqlCRUD;
+
+#[derive(sqlx::Type, Debug, PartialEq)]
+#[sqlx(rename_all = "lowercase")]
+enum Status {
+    Active,
+    Suspended,
+}
+
+#[derive(sqlx::Type, Debug, PartialEq)]
+#[repr(i32)]
+enum Priority {
+    Low = 0,
+    High = 1,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+struct Account {
+    #[primary_key]
+    id: i64,
+    #[sql_enum(text, values("active", "suspended"))]
+    status: Status,
+    #[sql_enum(int, values(0, 1))]
+    priority: Priority,
+    #[sql_enum(text)]
+    previous: Option<Status>,
+}
+
+#[tokio::test]
+async fn enum_round_trip_and_check() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    assert!(Account::CREATE_TABLE_SQL.contains("\"status\" TEXT CHECK (\"status\" IN ('active', 'suspended'))"));
+    assert!(Account::CREATE_TABLE_SQL.contains("\"priority\" INTEGER CHECK (\"priority\" IN (0, 1))"));
+    assert!(Account::CREATE_TABLE_SQL.contains("\"previous\" TEXT"));
+    Account::init_table(&pool).await.unwrap();
+
+    let account = Account { id: 1, status: Status::Suspended, priority: Priority::High, previous: Some(Status::Active) };
+    account.insert(&pool).await.unwrap();
+    assert_eq!(Account::find_by_id(&pool, &1).await.unwrap(), Some(account));
+
+    let raw: (String,) = sqlx::query_as("SELECT status FROM account WHERE id = 1").fetch_one(&pool).await.unwrap();
+    assert_eq!(raw.0, "suspended");
+
+    let rejected = sqlx::query("INSERT INTO account (id, status, priority) VALUES (2, 'deleted', 0)")
+        .execute(&pool)
+        .await;
+    assert!(rejected.is_err());
+}
@@ -0,0 +1,100 @@
+//! 生成语句的快照测试，以及 `#[sql_crud(self_check)]` 生成的自检测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_name = "users"]
+struct SqliteUser {
+    #[primary_key]
+    id: i64,
+    name: String,
+    email: Option<String>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_name = "users"]
+#[sql_crud(backend = "postgres")]
+struct PgUser {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    name: String,
+    email: Option<String>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_name = "users"]
+#[sql_crud(backend = "mysql")]
+struct MySqlUser {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    name: String,
+    email: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+// 组合多种影响绑定列表的选项，展开出的 `#[cfg(test)]` 模块在本测试中运行
+#[derive(SqlCRUD)]
+#[allow(dead_code)]
+#[table_name = "shapes"]
+#[sql_crud(from_row, update_returning, self_check)]
+struct Shape {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    #[skip]
+    cached: bool,
+    #[flatten(fields(x: "INTEGER", y: "INTEGER"))]
+    origin: Point,
+    label: Option<String>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_name = "shapes"]
+#[sql_crud(backend = "mysql", self_check, skip(update))]
+struct MySqlShape {
+    #[primary_key]
+    id: i64,
+    label: String,
+}
+
+#[test]
+fn sqlite_statements() {
+    assert_eq!(SqliteUser::INSERT_SQL, "INSERT INTO \"users\" (\"id\", \"name\", \"email\") VALUES ($1, $2, $3);");
+    assert_eq!(SqliteUser::UPDATE_SQL, "UPDATE \"users\" SET \"name\" = $1, \"email\" = $2 WHERE \"id\" = $3;");
+    assert_eq!(SqliteUser::DELETE_SQL, "DELETE FROM \"users\" WHERE \"id\" = $1;");
+    assert_eq!(SqliteUser::SELECT_SQL, "SELECT \"id\", \"name\", \"email\" FROM \"users\";");
+    assert_eq!(SqliteUser::SELECT_BY_ID_SQL, "SELECT \"id\", \"name\", \"email\" FROM \"users\" WHERE \"id\" = $1;");
+    assert_eq!(SqliteUser::COUNT_SQL, "SELECT COUNT(*) FROM \"users\";");
+}
+
+#[test]
+fn postgres_statements() {
+    assert_eq!(PgUser::INSERT_SQL, "INSERT INTO \"users\" (\"name\", \"email\") VALUES ($1, $2) RETURNING \"id\";");
+    assert_eq!(PgUser::UPDATE_SQL, "UPDATE \"users\" SET \"name\" = $1, \"email\" = $2 WHERE \"id\" = $3;");
+    assert_eq!(PgUser::DELETE_SQL, "DELETE FROM \"users\" WHERE \"id\" = $1;");
+    assert_eq!(PgUser::SELECT_BY_ID_SQL, "SELECT \"id\", \"name\", \"email\" FROM \"users\" WHERE \"id\" = $1;");
+}
+
+#[test]
+fn mysql_statements() {
+    assert_eq!(MySqlUser::INSERT_SQL, "INSERT INTO `users` (`name`, `email`) VALUES (?, ?);");
+    assert_eq!(MySqlUser::UPDATE_SQL, "UPDATE `users` SET `name` = ?, `email` = ? WHERE `id` = ?;");
+    assert_eq!(MySqlUser::DELETE_SQL, "DELETE FROM `users` WHERE `id` = ?;");
+    assert_eq!(MySqlUser::SELECT_BY_ID_SQL, "SELECT `id`, `name`, `email` FROM `users` WHERE `id` = ?;");
+}
+
+#[test]
+fn combined_options_keep_binds_in_step() {
+    assert_eq!(Shape::INSERT_SQL, "INSERT INTO \"shapes\" (\"origin_x\", \"origin_y\", \"label\") VALUES ($1, $2, $3);");
+    assert_eq!(Shape::UPDATE_RETURNING_SQL, "UPDATE \"shapes\" SET \"origin_x\" = $1, \"origin_y\" = $2, \"label\" = $3 WHERE \"id\" = $4 RETURNING \"id\", \"origin_x\", \"origin_y\", \"label\";");
+}
@@ -0,0 +1,36 @@
+//! 结构体级 `#[sql_type_map(...)]` 类型映射的建表语句测试
+use macros::SqlCRUD;
+
+#[derive(Debug, sqlx::Type)]
+#[sqlx(transparent)]
+struct Money(i64);
+
+#[derive(Debug, sqlx::Type)]
+#[sqlx(transparent)]
+struct Slug(String);
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[sql_type_map(Money = "NUMERIC(19,4)", Slug = "VARCHAR(80)")]
+struct Order {
+    #[primary_key]
+    id: i64,
+    subtotal: Money,
+    total: Option<Money>,
+    #[sql_type = "TEXT"]
+    refund: Money,
+    slug: Slug,
+}
+
+#[test]
+fn mapped_types_use_the_table() {
+    let sql = Order::CREATE_TABLE_SQL;
+    assert!(sql.contains("\"subtotal\" NUMERIC(19,4),"), "{}", sql);
+    assert!(sql.contains("\"total\" NUMERIC(19,4),"), "{}", sql);
+    assert!(sql.contains("\"slug\" VARCHAR(80)"), "{}", sql);
+}
+
+#[test]
+fn field_sql_type_beats_the_table() {
+    assert!(Order::CREATE_TABLE_SQL.contains("\"refund\" TEXT,"));
+}
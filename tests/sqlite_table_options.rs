@@ -0,0 +1,82 @@
+//! SQLite `STRICT` 和 `WITHOUT ROWID` 表选项的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "readings"]
+#[sql_crud(strict)]
+struct Reading {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    #[sql_type = "INT"]
+    value: i64,
+    label: String,
+    ratio: f64,
+    active: bool,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "settings"]
+#[sql_crud(strict, without_rowid)]
+struct Setting {
+    #[primary_key]
+    key: String,
+    value: Option<String>,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "tags"]
+#[sql_crud(without_rowid)]
+struct Tag {
+    #[primary_key]
+    name: String,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+#[test]
+fn table_options_follow_the_column_list() {
+    assert!(Reading::CREATE_TABLE_SQL.ends_with(") STRICT;"), "{}", Reading::CREATE_TABLE_SQL);
+    assert!(Setting::CREATE_TABLE_SQL.ends_with(") STRICT, WITHOUT ROWID;"), "{}", Setting::CREATE_TABLE_SQL);
+    assert!(Tag::CREATE_TABLE_SQL.ends_with(") WITHOUT ROWID;"), "{}", Tag::CREATE_TABLE_SQL);
+}
+
+#[test]
+fn strict_tables_infer_strict_types() {
+    assert_eq!(Reading::columns(), [
+        ("id", "INTEGER"),
+        ("value", "INT"),
+        ("label", "TEXT"),
+        ("ratio", "REAL"),
+        ("active", "INTEGER"),
+    ]);
+}
+
+#[tokio::test]
+async fn strict_tables_reject_mistyped_values() {
+    let pool = pool().await;
+    Reading::init_table(&pool).await.unwrap();
+    let id = Reading { id: 0, value: 7, label: "ok".into(), ratio: 0.5, active: true }.insert(&pool).await.unwrap();
+    assert_eq!(Reading::find_by_id(&pool, &id).await.unwrap().unwrap().value, 7);
+
+    let err = sqlx::query("INSERT INTO readings (value, label, ratio, active) VALUES ('abc', 'x', 0.0, 0)")
+        .execute(&pool)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("cannot store TEXT value in INT column"), "{}", err);
+}
+
+#[tokio::test]
+async fn without_rowid_tables_round_trip() {
+    let pool = pool().await;
+    Setting::init_table(&pool).await.unwrap();
+    let setting = Setting { key: "theme".into(), value: Some("dark".into()) };
+    setting.insert(&pool).await.unwrap();
+    assert_eq!(Setting::find_by_id(&pool, &"theme".to_string()).await.unwrap(), Some(setting));
+}
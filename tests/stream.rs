@@ -0,0 +1,30 @@
+//! `#[sql_crud(stream)]` 生成的 `find_all_stream` 的测试
+use std::pin::pin;
+
+use futures_core::Stream;
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+#[sql_crud(stream)]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+#[tokio::test]
+async fn streams_every_record() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    User::init_table(&pool).await.unwrap();
+    for (id, name) in [(1, "alice"), (2, "bob")] {
+        User { id, name: name.into() }.insert(&pool).await.unwrap();
+    }
+
+    let mut stream = pin!(User::find_all_stream(&pool));
+    let mut names = Vec::new();
+    while let Some(user) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+        names.push(user.unwrap().name);
+    }
+    assert_eq!(names, ["alice", "bob"]);
+}
@@ -0,0 +1,46 @@
+//! `#[sql_crud(strict_create)]` 去掉 `IF NOT EXISTS` 的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "items"]
+#[sql_crud(strict_create)]
+struct StrictItem {
+    #[primary_key]
+    id: i64,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "items"]
+struct LenientItem {
+    #[primary_key]
+    id: i64,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap()
+}
+
+#[test]
+fn create_table_sql() {
+    assert!(StrictItem::CREATE_TABLE_SQL.starts_with("CREATE TABLE \"items\" ("), "{}", StrictItem::CREATE_TABLE_SQL);
+    assert!(LenientItem::CREATE_TABLE_SQL.starts_with("CREATE TABLE IF NOT EXISTS \"items\" ("), "{}", LenientItem::CREATE_TABLE_SQL);
+}
+
+#[tokio::test]
+async fn existing_table_is_an_error() {
+    let pool = pool().await;
+    StrictItem::init_table(&pool).await.unwrap();
+    assert!(StrictItem::init_table(&pool).await.is_err());
+    LenientItem::init_table(&pool).await.unwrap();
+}
+
+#[tokio::test]
+async fn failed_init_table_releases_its_transaction() {
+    let pool = pool().await;
+    StrictItem::init_table(&pool).await.unwrap();
+    assert!(StrictItem::init_table(&pool).await.is_err());
+    // 连接池只有一个连接，失败的事务已回滚，后续语句在自动提交模式下执行
+    StrictItem { id: 1 }.insert(&pool).await.unwrap();
+    pool.begin().await.unwrap().rollback().await.unwrap();
+    assert_eq!(StrictItem::count(&pool).await.unwrap(), 1);
+}
@@ -0,0 +1,49 @@
+//! `#[max_length = N]` 和 `#[text]` 的建表语句与长度校验测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug)]
+#[sql_crud(error)]
+struct Article {
+    #[primary_key]
+    id: i64,
+    title: String,
+    #[max_length = 3]
+    code: String,
+    #[max_length = 2000]
+    summary: Option<String>,
+    #[text]
+    body: String,
+}
+
+#[test]
+fn string_column_shapes() {
+    let sql = Article::CREATE_TABLE_SQL;
+    assert!(sql.contains("\"title\" VARCHAR(255),"), "{}", sql);
+    assert!(sql.contains("\"code\" VARCHAR(3),"), "{}", sql);
+    assert!(sql.contains("\"summary\" VARCHAR(2000),"), "{}", sql);
+    assert!(sql.contains("\"body\" TEXT"), "{}", sql);
+}
+
+#[tokio::test]
+async fn over_length_strings_are_rejected_before_insert() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    Article::init_table(&pool).await.unwrap();
+
+    let mut article = Article { id: 1, title: "t".into(), code: "ÄÖÜ".into(), summary: None, body: "b".repeat(5000) };
+    assert!(article.validate().is_empty(), "length counts characters, not bytes");
+    article.insert(&pool).await.unwrap();
+
+    article.id = 2;
+    article.code = "ABCD".into();
+    match article.insert(&pool).await {
+        Err(ArticleError::Validation { errors, .. }) => {
+            assert_eq!(errors, [("code", "length 4 exceeds the maximum of 3".to_string())]);
+        }
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+    assert_eq!(Article::count(&pool).await.unwrap(), 1);
+}
@@ -0,0 +1,69 @@
+//! 由结构体名推导表名（`#[table_naming]`）的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+struct HTTPRequestLog {
+    #[primary_key]
+    id: i64,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_naming = "lower"]
+struct UserProfile {
+    #[primary_key]
+    id: i64,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_naming = "preserve"]
+struct AuditEntry {
+    #[primary_key]
+    id: i64,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_naming = "snake_plural"]
+struct BoxItem {
+    #[primary_key]
+    id: i64,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_naming = "snake_plural"]
+struct Address {
+    #[primary_key]
+    id: i64,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[allow(dead_code)]
+#[table_naming = "snake_plural"]
+#[table_name = "people"]
+struct Person {
+    #[primary_key]
+    id: i64,
+}
+
+#[test]
+fn derives_table_names() {
+    assert_eq!(HTTPRequestLog::table_name(), "http_request_log");
+    assert_eq!(UserProfile::table_name(), "userprofile");
+    assert_eq!(AuditEntry::table_name(), "AuditEntry");
+    assert_eq!(BoxItem::table_name(), "box_items");
+    assert_eq!(Address::table_name(), "addresses");
+}
+
+#[test]
+fn explicit_table_name_wins() {
+    assert_eq!(Person::table_name(), "people");
+}
+
+#[test]
+fn derived_name_flows_into_sql() {
+    assert!(AuditEntry::SELECT_SQL.contains("FROM \"AuditEntry\""), "{}", AuditEntry::SELECT_SQL);
+}
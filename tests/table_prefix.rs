@@ -0,0 +1,95 @@
+//! `#[table_prefix]` 和环境变量 `SQL_CRUD_TABLE_PREFIX` 的表名前缀测试
+//!
+//! 宏在编译测试时读取环境变量，`option_env!` 读到的是同一个值；
+//! 用 `SQL_CRUD_TABLE_PREFIX=env_ cargo test --test table_prefix` 覆盖设置了环境变量的情况
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_prefix = "app_"]
+struct UserProfile {
+    #[primary_key]
+    id: i64,
+    #[queryable]
+    name: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "orders"]
+#[table_prefix = "app_"]
+struct Order {
+    #[primary_key]
+    id: i64,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "widgets"]
+struct Widget {
+    #[primary_key]
+    id: i64,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "gadgets"]
+#[table_prefix = ""]
+struct Gadget {
+    #[primary_key]
+    id: i64,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "accounts"]
+#[table_prefix = "app_"]
+#[read_table = "account_view"]
+#[write_table = "account_log"]
+struct Account {
+    #[primary_key]
+    id: i64,
+}
+
+const ENV_PREFIX: &str = match option_env!("SQL_CRUD_TABLE_PREFIX") {
+    Some(prefix) => prefix,
+    None => "",
+};
+
+#[test]
+fn attribute_prefixes_derived_and_explicit_names() {
+    assert_eq!(UserProfile::table_name(), "app_user_profile");
+    assert_eq!(Order::table_name(), "app_orders");
+    for sql in [Order::CREATE_TABLE_SQL, Order::INSERT_SQL, Order::DELETE_SQL, Order::SELECT_SQL, Order::COUNT_SQL] {
+        assert!(sql.contains("\"app_orders\""), "{}", sql);
+    }
+}
+
+#[test]
+fn attribute_prefixes_read_and_write_tables() {
+    assert_eq!(Account::table_name(), "app_accounts");
+    for sql in [Account::SELECT_SQL, Account::COUNT_SQL] {
+        assert!(sql.contains("\"app_account_view\""), "{}", sql);
+    }
+    for sql in [Account::CREATE_TABLE_SQL, Account::INSERT_SQL, Account::DELETE_SQL] {
+        assert!(sql.contains("\"app_account_log\""), "{}", sql);
+    }
+}
+
+#[test]
+fn environment_variable_is_the_default() {
+    assert_eq!(Widget::table_name(), format!("{}widgets", ENV_PREFIX));
+    assert!(Widget::SELECT_SQL.contains(&format!("\"{}widgets\"", ENV_PREFIX)), "{}", Widget::SELECT_SQL);
+}
+
+#[test]
+fn attribute_takes_precedence_over_environment() {
+    assert_eq!(Order::table_name(), "app_orders");
+    assert_eq!(Gadget::table_name(), "gadgets");
+}
+
+#[tokio::test]
+async fn prefixed_table_round_trip() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    UserProfile::init_table(&pool).await.unwrap();
+    UserProfile { id: 1, name: "a".to_string() }.insert(&pool).await.unwrap();
+
+    let raw: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM app_user_profile").fetch_one(&pool).await.unwrap();
+    assert_eq!(raw, 1);
+    assert_eq!(UserProfile::find_by_name(&pool, &"a".to_string()).await.unwrap().len(), 1);
+}
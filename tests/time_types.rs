@@ -0,0 +1,51 @@
+//! `time` 库类型的往返测试，需启用 `time` 特性：`cargo test --features time`
+#![cfg(feature = "time")]
+
+use macros::SqlCRUD;
+use time::OffsetDateTime;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[sql_crud(filter)]
+struct Event {
+    #[primary_key]
+    id: i64,
+    happened_at: OffsetDateTime,
+}
+
+#[tokio::test]
+async fn offset_date_time_round_trip() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    assert!(Event::CREATE_TABLE_SQL.contains("\"happened_at\" TIMESTAMP"));
+    Event::init_table(&pool).await.unwrap();
+
+    let event = Event {
+        id: 1,
+        happened_at: OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap(),
+    };
+    event.insert(&pool).await.unwrap();
+
+    let found = Event::find_by_id(&pool, &1).await.unwrap();
+    assert_eq!(found, Some(event));
+}
+
+#[tokio::test]
+async fn filters_by_time_range() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    Event::init_table(&pool).await.unwrap();
+    for (id, timestamp) in [(1, 1_600_000_000), (2, 1_700_000_000), (3, 1_800_000_000)] {
+        Event { id, happened_at: OffsetDateTime::from_unix_timestamp(timestamp).unwrap() }.insert(&pool).await.unwrap();
+    }
+
+    let start = OffsetDateTime::from_unix_timestamp(1_650_000_000).unwrap();
+    let end = OffsetDateTime::from_unix_timestamp(1_750_000_000).unwrap();
+    let events = Event::filter().happened_at_after(&start).happened_at_before(&end).fetch(&pool).await.unwrap();
+    assert_eq!(events.iter().map(|event| event.id).collect::<Vec<_>>(), vec![2]);
+}
@@ -0,0 +1,59 @@
+//! `#[sql_crud(tracing, filter)]` 记录的查询事件的测试
+use macros::SqlCRUD;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "users"]
+#[sql_crud(tracing, filter)]
+struct User {
+    #[primary_key]
+    id: i64,
+    #[queryable]
+    name: String,
+}
+
+/// 收集每个 "executing query" 事件的 `params` 字段
+#[derive(Clone, Default)]
+struct Params(Arc<Mutex<Vec<u64>>>);
+
+struct ParamsVisitor(Option<u64>);
+
+impl Visit for ParamsVisitor {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "params" {
+            self.0 = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+impl<S: tracing::Subscriber> Layer<S> for Params {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = ParamsVisitor(None);
+        event.record(&mut visitor);
+        if let Some(params) = visitor.0 {
+            self.0.lock().unwrap().push(params);
+        }
+    }
+}
+
+#[tokio::test]
+async fn records_the_bound_parameter_count() {
+    let params = Params::default();
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(params.clone()));
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+
+    User::init_table(&pool).await.unwrap();
+    User { id: 1, name: "alice".into() }.insert(&pool).await.unwrap();
+    User::find_by_name(&pool, &"alice".to_string()).await.unwrap();
+    User::find_by_name_in(&pool, &["a".to_string(), "b".to_string(), "c".to_string()]).await.unwrap();
+    // 子句中的 `$`、`?` 字面量不是占位符
+    let found = User::find_where(&pool, "name <> '$?' AND id = $1", |query| query.bind(1)).await.unwrap();
+    assert_eq!(found.len(), 1);
+    User::filter().name_eq("alice").id_in(&[1, 2]).fetch(&pool).await.unwrap();
+
+    assert_eq!(*params.0.lock().unwrap(), vec![0, 2, 1, 3, 1, 3]);
+}
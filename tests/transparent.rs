@@ -0,0 +1,41 @@
+//! `#[sql_transparent]` 新类型主键的完整增删改查测试
+
+use macros::SqlCRUD;
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq)]
+#[sqlx(transparent)]
+struct UserId(i64);
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+struct Member {
+    #[primary_key]
+    #[sql_transparent = "i64"]
+    id: UserId,
+    name: String,
+    #[sql_transparent = "i64"]
+    invited_by: Option<UserId>,
+}
+
+#[tokio::test]
+async fn transparent_key_crud() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    assert!(Member::CREATE_TABLE_SQL.contains("\"id\" BIGINT PRIMARY KEY"));
+    assert!(Member::CREATE_TABLE_SQL.contains("\"invited_by\" BIGINT"));
+    Member::init_table(&pool).await.unwrap();
+
+    let mut member = Member { id: UserId(7), name: "ann".into(), invited_by: None };
+    member.insert(&pool).await.unwrap();
+    assert_eq!(Member::find_by_id(&pool, &UserId(7)).await.unwrap().as_ref(), Some(&member));
+
+    member.name = "anna".into();
+    member.invited_by = Some(UserId(1));
+    member.update(&pool).await.unwrap();
+    assert_eq!(Member::find_by_id(&pool, &UserId(7)).await.unwrap().as_ref(), Some(&member));
+
+    member.delete(&pool).await.unwrap();
+    assert_eq!(Member::find_by_id(&pool, &UserId(7)).await.unwrap(), None);
+}
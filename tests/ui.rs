@@ -0,0 +1,20 @@
+//! 编译期行为的测试，用例位于 `tests/ui`
+#[test]
+fn skip() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/skip_update_delete.rs");
+    cases.compile_fail("tests/ui/skip_update.rs");
+    cases.compile_fail("tests/ui/skip_unknown.rs");
+}
+
+#[test]
+fn vis() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/vis_outside.rs");
+}
+
+#[test]
+fn stream() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/stream_opt_in.rs");
+}
@@ -0,0 +1,11 @@
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[sql_crud(skip(update, updat))]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+fn main() {}
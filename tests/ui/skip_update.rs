@@ -0,0 +1,15 @@
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[sql_crud(skip(update))]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+async fn rename(pool: &sqlx::SqlitePool, user: &User) {
+    User::update(user, pool).await.unwrap();
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+#[sql_crud(skip(update, delete), repository)]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+async fn store(pool: sqlx::SqlitePool, user: &User) -> Result<Option<User>, sqlx::Error> {
+    User::init_table(&pool).await?;
+    user.insert(&pool).await?;
+    UserRepo::new(pool).find_by_id(&user.id).await
+}
+
+fn main() {
+    let _ = store;
+}
@@ -0,0 +1,14 @@
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow)]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+fn rows(pool: &sqlx::SqlitePool) {
+    let _ = User::find_all_stream(pool);
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+mod models {
+    use macros::SqlCRUD;
+
+    #[derive(SqlCRUD, sqlx::FromRow)]
+    #[sql_crud(vis = "pub(in crate::models)")]
+    pub struct User {
+        #[primary_key]
+        pub id: i64,
+        pub name: String,
+    }
+}
+
+async fn load(pool: &sqlx::SqlitePool) {
+    models::User::find_by_id(pool, &1).await.unwrap();
+}
+
+fn main() {}
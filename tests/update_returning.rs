@@ -0,0 +1,52 @@
+//! `#[sql_crud(update_returning)]` 返回更新后记录的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+#[table_name = "widgets"]
+#[sql_crud(update_returning)]
+#[sql_override(update = "UPDATE widgets SET name = UPPER($1), version = $2 WHERE id = $3;")]
+struct Widget {
+    #[primary_key]
+    id: i64,
+    name: String,
+    version: i64,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    Widget::init_table(&pool).await.unwrap();
+    pool
+}
+
+#[test]
+fn appends_returning_to_the_update() {
+    assert_eq!(
+        Widget::UPDATE_RETURNING_SQL,
+        "UPDATE widgets SET name = UPPER($1), version = $2 WHERE id = $3 RETURNING \"id\", \"name\", \"version\";"
+    );
+}
+
+#[tokio::test]
+async fn returns_database_computed_values() {
+    let pool = pool().await;
+    let mut widget = Widget { id: 1, name: "gear".into(), version: 1 };
+    widget.insert(&pool).await.unwrap();
+
+    widget.name = "sprocket".into();
+    widget.version = 2;
+    let updated = widget.update_returning(&pool).await.unwrap();
+    assert_eq!(updated, Widget { id: 1, name: "SPROCKET".into(), version: 2 });
+    assert_eq!(Widget::find_by_id(&pool, &1).await.unwrap(), Some(updated));
+}
+
+#[tokio::test]
+async fn missing_rows_are_not_found() {
+    let pool = pool().await;
+    let widget = Widget { id: 7, name: "ghost".into(), version: 1 };
+    let err = widget.update_returning(&pool).await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::RowNotFound), "{err:?}");
+}
@@ -0,0 +1,106 @@
+//! `upsert_many` 的批量插入与冲突更新测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, Clone, PartialEq)]
+struct Account {
+    #[primary_key]
+    #[auto_increment]
+    id: i64,
+    #[unique]
+    email: String,
+    name: String,
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, Clone, PartialEq)]
+struct Tag {
+    #[primary_key]
+    id: i64,
+    #[unique]
+    name: String,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+fn account(email: &str, name: &str) -> Account {
+    Account { id: 0, email: email.into(), name: name.into() }
+}
+
+#[tokio::test]
+async fn inserts_new_rows_and_updates_existing_ones() {
+    let pool = pool().await;
+    Account::init_table(&pool).await.unwrap();
+    Account::upsert_many(&pool, &[account("a@x.io", "A"), account("b@x.io", "B")]).await.unwrap();
+    Account::upsert_many(&pool, &[account("b@x.io", "Bee"), account("c@x.io", "C")]).await.unwrap();
+
+    let names: Vec<(String, String)> = Account::find_all(&pool).await.unwrap()
+        .into_iter()
+        .map(|a| (a.email, a.name))
+        .collect();
+    assert_eq!(names, [
+        ("a@x.io".to_string(), "A".to_string()),
+        ("b@x.io".to_string(), "Bee".to_string()),
+        ("c@x.io".to_string(), "C".to_string()),
+    ]);
+    Account::upsert_many(&pool, &[]).await.unwrap();
+    assert_eq!(Account::count(&pool).await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn batches_beyond_the_parameter_limit() {
+    let pool = pool().await;
+    Account::init_table(&pool).await.unwrap();
+    // 每行 2 个参数，超过 SQLite 的 32766 个参数上限时分多条语句执行
+    let accounts: Vec<Account> = (0..20_000)
+        .map(|i| account(&format!("{}@x.io", i), "old"))
+        .collect();
+    Account::upsert_many(&pool, &accounts).await.unwrap();
+    let renamed: Vec<Account> = accounts.iter()
+        .map(|a| Account { name: "new".into(), ..a.clone() })
+        .collect();
+    Account::upsert_many(&pool, &renamed).await.unwrap();
+
+    let all = Account::find_all(&pool).await.unwrap();
+    assert_eq!(all.len(), 20_000);
+    assert!(all.iter().all(|a| a.name == "new"));
+}
+
+#[tokio::test]
+async fn conflicts_are_ignored_without_updatable_columns() {
+    let pool = pool().await;
+    Tag::init_table(&pool).await.unwrap();
+    let tags = [Tag { id: 1, name: "rust".into() }, Tag { id: 2, name: "sql".into() }];
+    Tag::upsert_many(&pool, &tags).await.unwrap();
+    Tag::upsert_many(&pool, &[Tag { id: 3, name: "rust".into() }]).await.unwrap();
+    assert_eq!(Tag::find_all(&pool).await.unwrap(), tags);
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, Clone, PartialEq)]
+struct Profile {
+    #[primary_key]
+    id: i64,
+    #[upsert_coalesce]
+    nickname: Option<String>,
+    bio: Option<String>,
+}
+
+#[tokio::test]
+async fn coalesced_columns_keep_existing_values() {
+    let pool = pool().await;
+    Profile::init_table(&pool).await.unwrap();
+    let original = Profile { id: 1, nickname: Some("neo".into()), bio: Some("hi".into()) };
+    Profile::upsert_many(&pool, &[original]).await.unwrap();
+
+    Profile::upsert_many(&pool, &[Profile { id: 1, nickname: None, bio: None }]).await.unwrap();
+    let stored = Profile::find_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(stored, Profile { id: 1, nickname: Some("neo".into()), bio: None });
+
+    Profile::upsert_many(&pool, &[Profile { id: 1, nickname: Some("trinity".into()), bio: None }]).await.unwrap();
+    let stored = Profile::find_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(stored.nickname.as_deref(), Some("trinity"));
+}
@@ -0,0 +1,58 @@
+//! `#[uuid_generate]` 在插入时生成主键的测试
+
+use macros::SqlCRUD;
+use uuid::Uuid;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+struct Session {
+    #[primary_key]
+    #[uuid_generate]
+    #[uuid_storage = "blob"]
+    id: Uuid,
+    user: String,
+}
+
+#[tokio::test]
+async fn generates_uuid_for_nil_key() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    assert!(Session::CREATE_TABLE_SQL.contains("\"id\" BLOB PRIMARY KEY"));
+    Session::init_table(&pool).await.unwrap();
+
+    let mut session = Session { id: Uuid::nil(), user: "ann".into() };
+    session.insert(&pool).await.unwrap();
+    assert!(!session.id.is_nil());
+    assert_eq!(Session::find_by_id(&pool, &session.id).await.unwrap().as_ref(), Some(&session));
+
+    // 已有的值保持不变
+    let id = Uuid::new_v4();
+    let mut existing = Session { id, user: "bob".into() };
+    existing.insert(&pool).await.unwrap();
+    assert_eq!(existing.id, id);
+    assert_eq!(Session::count(&pool).await.unwrap(), 2);
+}
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+struct Token {
+    #[primary_key(generate = "uuid_v4")]
+    id: Uuid,
+    scope: String,
+}
+
+#[tokio::test]
+async fn primary_key_generate_strategy() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    Token::init_table(&pool).await.unwrap();
+
+    let mut token = Token { id: Uuid::default(), scope: "read".into() };
+    token.insert(&pool).await.unwrap();
+    assert!(!token.id.is_nil());
+    assert_eq!(Token::find_by_id(&pool, &token.id).await.unwrap(), Some(token));
+}
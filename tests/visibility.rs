@@ -0,0 +1,33 @@
+//! `#[sql_crud(vis = "pub(crate)")]` 在多模块布局中的测试
+mod models {
+    use macros::SqlCRUD;
+
+    #[derive(SqlCRUD, sqlx::FromRow, Debug, PartialEq)]
+    #[sql_crud(vis = "pub(crate)", repository)]
+    #[table_name = "users"]
+    pub struct User {
+        #[primary_key]
+        pub id: i64,
+        #[queryable]
+        pub name: String,
+    }
+}
+
+mod service {
+    use crate::models::{User, UserRepo};
+
+    pub async fn register(repo: &UserRepo, name: &str) -> User {
+        let user = User { id: 1, name: name.into() };
+        repo.insert(&user).await.unwrap();
+        User::find_by_name(repo.pool(), &user.name).await.unwrap().remove(0)
+    }
+}
+
+#[tokio::test]
+async fn crate_visible_methods_are_usable_from_sibling_modules() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    models::User::init_table(&pool).await.unwrap();
+    let user = service::register(&models::UserRepo::new(pool), "alice").await;
+    assert_eq!(user, models::User { id: 1, name: "alice".into() });
+    assert_eq!(models::User::table_name(), "users");
+}
@@ -0,0 +1,39 @@
+//! `with_transaction` 提交和回滚的测试
+use macros::SqlCRUD;
+
+#[derive(SqlCRUD, sqlx::FromRow, Debug, Clone, PartialEq)]
+#[table_name = "users"]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+async fn pool() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    User::init_table(&pool).await.unwrap();
+    pool
+}
+
+#[tokio::test]
+async fn err_rolls_back_every_write() {
+    let pool = pool().await;
+    let result: Result<(), sqlx::Error> = User::with_transaction(&pool, |tx| Box::pin(async move {
+        User { id: 1, name: "alice".into() }.insert(&mut **tx).await?;
+        User { id: 2, name: "bob".into() }.insert(&mut **tx).await?;
+        Err(sqlx::Error::RowNotFound)
+    })).await;
+    assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+    assert!(User::find_all(&pool).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn ok_commits_and_returns_the_value() {
+    let pool = pool().await;
+    let inserted = User::with_transaction(&pool, |tx| Box::pin(async move {
+        User { id: 1, name: "alice".into() }.insert(&mut **tx).await?;
+        Ok::<_, sqlx::Error>(1)
+    })).await.unwrap();
+    assert_eq!(inserted, 1);
+    assert_eq!(User::find_all(&pool).await.unwrap(), vec![User { id: 1, name: "alice".into() }]);
+}